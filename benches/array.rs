@@ -0,0 +1,60 @@
+use bincode::config;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn decode_u32_array(c: &mut Criterion) {
+    let input: [u32; 64] = core::array::from_fn(|i| i as u32 * 1000);
+
+    let config = config::standard().with_fixed_int_encoding();
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+    c.bench_function("decode [u32; 64]", |b| {
+        b.iter(|| {
+            let _: ([u32; 64], usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+    // The same bytes, decoded one item at a time
+    c.bench_function("decode Vec<u32> of 64 items", |b| {
+        b.iter(|| {
+            let _: (Vec<u32>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+
+    let config = config.with_big_endian();
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+    c.bench_function("decode [u32; 64] big endian", |b| {
+        b.iter(|| {
+            let _: ([u32; 64], usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+fn encode_u32_array(c: &mut Criterion) {
+    let input: [u32; 64] = core::array::from_fn(|i| i as u32 * 1000);
+    let mut buffer = [0u8; 512];
+
+    let config = config::standard().with_fixed_int_encoding();
+    c.bench_function("encode [u32; 64]", |b| {
+        b.iter(|| {
+            black_box(bincode::encode_into_slice(black_box(input), &mut buffer, config).unwrap());
+        })
+    });
+    // The same items, encoded one at a time
+    let items = input.to_vec();
+    c.bench_function("encode Vec<u32> of 64 items", |b| {
+        b.iter(|| {
+            black_box(bincode::encode_into_slice(black_box(&items), &mut buffer, config).unwrap());
+        })
+    });
+
+    let config = config.with_big_endian();
+    c.bench_function("encode [u32; 64] big endian", |b| {
+        b.iter(|| {
+            black_box(bincode::encode_into_slice(black_box(input), &mut buffer, config).unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, decode_u32_array, encode_u32_array);
+criterion_main!(benches);
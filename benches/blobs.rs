@@ -0,0 +1,31 @@
+use bincode::config;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::VecDeque;
+
+fn decode_blobs(c: &mut Criterion) {
+    let input: Vec<Vec<u8>> = (0..10_000u32)
+        .map(|i| (0..i % 32).map(|j| (i ^ j) as u8).collect())
+        .collect();
+
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(&input, config).unwrap();
+    c.bench_function("encode Vec<Vec<u8>> of 10000 blobs", |b| {
+        b.iter(|| black_box(bincode::encode_to_vec(black_box(&input), config).unwrap()))
+    });
+    c.bench_function("decode Vec<Vec<u8>> of 10000 blobs", |b| {
+        b.iter(|| {
+            let _: (Vec<Vec<u8>>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+    // The same bytes, decoded one byte at a time
+    c.bench_function("decode VecDeque<VecDeque<u8>> of 10000 blobs", |b| {
+        b.iter(|| {
+            let _: (VecDeque<VecDeque<u8>>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, decode_blobs);
+criterion_main!(benches);
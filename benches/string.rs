@@ -0,0 +1,32 @@
+use bincode::config;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn decode_string(c: &mut Criterion) {
+    let input: String = "The quick brown fox jumps over the lazy dög. ".repeat(1_500);
+
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(&input, config).unwrap();
+    c.bench_function("decode String of 70 KiB", |b| {
+        b.iter(|| {
+            let _: (String, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+    // The same bytes, from a reader that does not buffer its input
+    c.bench_function("decode String of 70 KiB from std::io::Read", |b| {
+        b.iter(|| {
+            let _: String = black_box(
+                bincode::decode_from_std_read(&mut black_box(&bytes[..]), config).unwrap(),
+            );
+        })
+    });
+    c.bench_function("decode Vec<u8> of 70 KiB", |b| {
+        b.iter(|| {
+            let _: (Vec<u8>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, decode_string);
+criterion_main!(benches);
@@ -3,12 +3,18 @@ use virtue::utils::{parse_tagged_attribute, ParsedAttribute};
 
 pub struct ContainerAttributes {
     pub crate_name: String,
+    /// `#[bincode(transparent)]`, the struct must have exactly one field
+    pub transparent: bool,
+    /// `#[bincode(version = N)]`, the struct is prefixed with a version byte
+    pub version: Option<u8>,
 }
 
 impl Default for ContainerAttributes {
     fn default() -> Self {
         Self {
             crate_name: "::bincode".to_string(),
+            transparent: false,
+            version: None,
         }
     }
 }
@@ -30,6 +36,12 @@ impl FromAttribute for ContainerAttributes {
                         return Err(Error::custom_at("Should be a literal str", val.span()));
                     }
                 }
+                ParsedAttribute::Tag(i) if i.to_string() == "transparent" => {
+                    result.transparent = true;
+                }
+                ParsedAttribute::Property(key, val) if key.to_string() == "version" => {
+                    result.version = Some(parse_version(&val)?);
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -46,6 +58,10 @@ impl FromAttribute for ContainerAttributes {
 #[derive(Default)]
 pub struct FieldAttributes {
     pub with_serde: bool,
+    /// `#[bincode(since = N)]`, the field is only decoded if the stored version is at least `N`
+    pub since: Option<u8>,
+    /// `#[bincode(optional)]`, the field is only written if its bit is set in the presence mask of the struct
+    pub optional: bool,
 }
 
 impl FromAttribute for FieldAttributes {
@@ -60,6 +76,16 @@ impl FromAttribute for FieldAttributes {
                 ParsedAttribute::Tag(i) if i.to_string() == "with_serde" => {
                     result.with_serde = true;
                 }
+                ParsedAttribute::Property(key, val) if key.to_string() == "since" => {
+                    result.since = Some(parse_version(&val)?);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "optional" => {
+                    result.optional = true;
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "flatten" => {
+                    // Fields are always encoded inline without any framing, so a flattened field is
+                    // encoded exactly like any other field.
+                }
                 ParsedAttribute::Tag(i) => {
                     return Err(Error::custom_at("Unknown field attribute", i.span()))
                 }
@@ -72,3 +98,104 @@ impl FromAttribute for FieldAttributes {
         Ok(Some(result))
     }
 }
+
+#[derive(Default)]
+pub struct VariantAttributes {
+    /// `#[bincode(other)]`, the variant holds the tag and the bytes of variants that are not known to this version of the enum
+    pub other: bool,
+    /// `#[bincode(skip_encoding)]`, encoding the variant returns an error, and it is never decoded
+    pub skip_encoding: bool,
+}
+
+impl FromAttribute for VariantAttributes {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "bincode")? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        let mut result = Self::default();
+        for attribute in attributes {
+            match attribute {
+                ParsedAttribute::Tag(i) if i.to_string() == "other" => {
+                    result.other = true;
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "skip_encoding" => {
+                    result.skip_encoding = true;
+                }
+                ParsedAttribute::Tag(i) => {
+                    return Err(Error::custom_at("Unknown variant attribute", i.span()))
+                }
+                ParsedAttribute::Property(key, _) => {
+                    return Err(Error::custom_at("Unknown variant attribute", key.span()))
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(result))
+    }
+}
+
+fn parse_version(val: &Literal) -> Result<u8> {
+    let val_string = val.to_string();
+    val_string
+        .trim_end_matches("u8")
+        .parse()
+        .map_err(|_| Error::custom_at("Should be an integer between 0 and 255", val.span()))
+}
+
+/// The integer type of a `#[repr(..)]` attribute on an enum.
+///
+/// Every integer type is used for the discriminant. Other representations, like `#[repr(C)]`, are ignored and the discriminant is encoded as a `u32`.
+pub struct EnumRepr {
+    pub int_type: &'static str,
+    /// Set if the integer type was given with `#[repr(..)]`
+    pub explicit: bool,
+}
+
+impl EnumRepr {
+    pub fn is_signed(&self) -> bool {
+        self.int_type.starts_with('i')
+    }
+}
+
+impl Default for EnumRepr {
+    fn default() -> Self {
+        Self {
+            int_type: "u32",
+            explicit: false,
+        }
+    }
+}
+
+impl FromAttribute for EnumRepr {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "repr")? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        for attribute in attributes {
+            if let ParsedAttribute::Tag(i) = attribute {
+                let int_type = match i.to_string().as_str() {
+                    "u8" => "u8",
+                    "u16" => "u16",
+                    "u32" => "u32",
+                    "u64" => "u64",
+                    "u128" => "u128",
+                    "usize" => "usize",
+                    "i8" => "i8",
+                    "i16" => "i16",
+                    "i32" => "i32",
+                    "i64" => "i64",
+                    "i128" => "i128",
+                    "isize" => "isize",
+                    _ => continue,
+                };
+                return Ok(Some(Self {
+                    int_type,
+                    explicit: true,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -1,24 +1,64 @@
-use crate::attribute::{ContainerAttributes, FieldAttributes};
+use crate::attribute::{ContainerAttributes, EnumRepr, FieldAttributes, VariantAttributes};
+use virtue::parse::IdentOrIndex;
 use virtue::prelude::*;
 
 const TUPLE_FIELD_PREFIX: &str = "field_";
 
 pub(crate) struct DeriveEnum {
     pub variants: Vec<EnumVariant>,
+    /// The variant marked with `#[bincode(other)]`, which is not part of `variants`
+    pub other: Option<EnumVariant>,
+    /// Explicit discriminants of the variants, e.g. `Move { x: i32 } = 10` or `Up = -1`
+    pub discriminants: Vec<(String, Vec<TokenTree>)>,
     pub attributes: ContainerAttributes,
+    pub repr: EnumRepr,
 }
 
 impl DeriveEnum {
-    fn iter_fields(&self) -> EnumVariantIterator {
+    fn iter_fields(&self) -> EnumVariantIterator<'_> {
         EnumVariantIterator {
             idx: 0,
             last_val: None,
             variants: &self.variants,
+            discriminants: &self.discriminants,
+        }
+    }
+
+    fn has_fixed_values(&self) -> bool {
+        !self.discriminants.is_empty()
+    }
+
+    /// Variants with `#[bincode(skip_encoding)]` can not be encoded, and are never decoded. They still count towards the index of the variants after them.
+    fn has_skipped_variants(&self) -> Result<bool> {
+        for variant in &self.variants {
+            if skips_encoding(variant)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// An enum with a single variant does not encode its discriminant, as it carries no information.
+    /// If the variant has an explicit discriminant, e.g. `A(u8) = 2`, or the enum has an explicit `#[repr(..)]`, it is still encoded.
+    fn has_single_variant(&self) -> bool {
+        self.variants.len() == 1
+            && !self.has_fixed_values()
+            && !self.repr.explicit
+            && self.other.is_none()
+    }
+
+    /// The variant index as an `i128`, for use in `DecodeError::UnexpectedVariant`
+    fn found_variant(&self) -> &'static str {
+        if self.repr.int_type == "i128" {
+            "variant"
+        } else {
+            "variant as i128"
         }
     }
 
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
         let crate_name = self.attributes.crate_name.as_str();
+        let enum_name = generator.target_name().to_string();
         generator
             .impl_for(format!("{}::Encode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
@@ -44,28 +84,13 @@ impl DeriveEnum {
                         self.encode_empty_enum_case(match_body)?;
                     }
                     for (variant_index, variant) in self.iter_fields() {
-                        // Self::Variant
-                        match_body.ident_str("Self");
-                        match_body.puncts("::");
-                        match_body.ident(variant.name.clone());
+                        if skips_encoding(variant)? {
+                            self.encode_skipped_variant(&enum_name, variant, match_body)?;
+                            continue;
+                        }
 
-                        // if we have any fields, declare them here
                         // Self::Variant { a, b, c }
-                        if let Some(delimiter) = variant.fields.delimiter() {
-                            match_body.group(delimiter, |field_body| {
-                                for (idx, field_name) in
-                                    variant.fields.names().into_iter().enumerate()
-                                {
-                                    if idx != 0 {
-                                        field_body.punct(',');
-                                    }
-                                    field_body.push(
-                                        field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
-                                    );
-                                }
-                                Ok(())
-                            })?;
-                        }
+                        self.variant_pattern(variant, match_body)?;
 
                         // Arrow
                         // Self::Variant { a, b, c } =>
@@ -81,48 +106,180 @@ impl DeriveEnum {
                         // }
                         match_body.group(Delimiter::Brace, |body| {
                             // variant index
-                            body.push_parsed(format!("<u32 as {}::Encode>::encode", crate_name))?;
-                            body.group(Delimiter::Parenthesis, |args| {
-                                args.punct('&');
-                                args.group(Delimiter::Parenthesis, |num| {
-                                    num.extend(variant_index);
+                            if !self.has_single_variant() {
+                                body.push_parsed(format!(
+                                    "<{} as {}::Encode>::encode",
+                                    self.repr.int_type, crate_name
+                                ))?;
+                                body.group(Delimiter::Parenthesis, |args| {
+                                    args.punct('&');
+                                    args.group(Delimiter::Parenthesis, |num| {
+                                        num.extend(variant_index);
+                                        Ok(())
+                                    })?;
+                                    args.punct(',');
+                                    args.push_parsed("encoder")?;
                                     Ok(())
                                 })?;
-                                args.punct(',');
-                                args.push_parsed("encoder")?;
-                                Ok(())
-                            })?;
-                            body.punct('?');
-                            body.punct(';');
-                            // If we have any fields, encode them all one by one
-                            for field_name in variant.fields.names() {
-                                let attributes = field_name
-                                    .attributes()
-                                    .get_attribute::<FieldAttributes>()?
-                                    .unwrap_or_default();
-                                if attributes.with_serde {
-                                    body.push_parsed(format!(
-                                        "{0}::Encode::encode(&{0}::serde::Compat({1}), encoder)?;",
-                                        crate_name,
-                                        field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                    ))?;
-                                } else {
-                                    body.push_parsed(format!(
-                                        "{0}::Encode::encode({1}, encoder)?;",
-                                        crate_name,
-                                        field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
-                                    ))?;
-                                }
+                                body.punct('?');
+                                body.punct(';');
+                            }
+                            if self.other.is_some() {
+                                self.encode_length_prefixed_fields(variant, body)?;
+                            } else {
+                                // If we have any fields, encode them all one by one
+                                self.encode_fields(variant, body)?;
                             }
                             body.push_parsed("Ok(())")?;
                             Ok(())
                         })?;
                         match_body.punct(',');
                     }
+                    if let Some(other) = &self.other {
+                        self.encode_other_variant(other, match_body)?;
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Build the pattern that matches a variant and binds its fields, in the form of:
+    /// `Self::Variant { a, b, c }` or `Self::Variant(field_0, field_1)`
+    fn variant_pattern(&self, variant: &EnumVariant, builder: &mut StreamBuilder) -> Result {
+        builder.ident_str("Self");
+        builder.puncts("::");
+        builder.ident(variant.name.clone());
+
+        if let Some(delimiter) = variant.fields.delimiter() {
+            builder.group(delimiter, |field_body| {
+                for (idx, field_name) in variant.fields.names().into_iter().enumerate() {
+                    if idx != 0 {
+                        field_body.punct(',');
+                    }
+                    field_body.push(field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Build the statements that encode the fields of a variant, which are bound by [Self::variant_pattern].
+    fn encode_fields(&self, variant: &EnumVariant, builder: &mut StreamBuilder) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        for field_name in variant.fields.names() {
+            let value = self.field_value(&field_name)?;
+            builder.push_parsed(crate::encode_field(crate_name, &value, &field_name))?;
+        }
+        Ok(())
+    }
+
+    /// The expression that references a field that is bound by [Self::variant_pattern], wrapped in `Compat` if the field is encoded with serde.
+    fn field_value(&self, field_name: &IdentOrIndex) -> Result<String> {
+        let attributes = field_name
+            .attributes()
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        let local = field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX);
+        Ok(if attributes.with_serde {
+            format!("&{}::serde::Compat({})", self.attributes.crate_name, local)
+        } else {
+            local
+        })
+    }
+
+    /// Build the statements that encode the fields of a variant prefixed by their length, so unknown variants can be skipped by decoders with an `other` variant. In the form of:
+    /// ```ignore
+    /// match (|| Some(0usize + bincode::Encode::size_hint::<E::C>(a)? + ..))() {
+    ///     Some(len) => { bincode::enc::Encoder::encode_len(encoder, len)?; <fields> }
+    ///     None => bincode::enc::Encoder::encode_length_prefixed_with(encoder, |encoder| { <fields> Ok(()) })?,
+    /// }
+    /// ```
+    fn encode_length_prefixed_fields(
+        &self,
+        variant: &EnumVariant,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        let mut size_hint = String::from("0usize");
+        for field_name in variant.fields.names() {
+            size_hint += &format!(
+                " + {}::Encode::size_hint::<E::C>({})?",
+                crate_name,
+                self.field_value(&field_name)?
+            );
+        }
+        builder.push_parsed(format!("match (|| Some({}))()", size_hint))?;
+        builder.group(Delimiter::Brace, |arms| {
+            arms.push_parsed("Some(__bincode_len) =>")?;
+            arms.group(Delimiter::Brace, |known| {
+                known.push_parsed(format!(
+                    "{}::enc::Encoder::encode_len(encoder, __bincode_len)?;",
+                    crate_name
+                ))?;
+                self.encode_fields(variant, known)
+            })?;
+            arms.push_parsed(format!(
+                "None => {}::enc::Encoder::encode_length_prefixed_with",
+                crate_name
+            ))?;
+            arms.group(Delimiter::Parenthesis, |args| {
+                args.push_parsed("encoder, |encoder|")?;
+                args.group(Delimiter::Brace, |buffered| {
+                    self.encode_fields(variant, buffered)?;
+                    buffered.push_parsed("Ok(())")?;
                     Ok(())
                 })?;
                 Ok(())
             })?;
+            arms.puncts("?,");
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Build the case of a `#[bincode(skip_encoding)]` variant, in the form of:
+    /// `Self::Variant { .. } => Err(bincode::error::EncodeError::UnsupportedVariant { .. }),`
+    fn encode_skipped_variant(
+        &self,
+        enum_name: &str,
+        variant: &EnumVariant,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        builder.push_parsed(format!(
+            "Self::{1} {{ .. }} => Err({0}::error::EncodeError::UnsupportedVariant {{ type_name: \"{2}\", name: \"{1}\" }}),",
+            self.attributes.crate_name, variant.name, enum_name
+        ))?;
+        Ok(())
+    }
+
+    /// Build the case that encodes the `#[bincode(other)]` variant, in the form of:
+    /// `Self::Other(tag, bytes) => { tag.encode(encoder)?; bytes.encode(encoder)?; Ok(()) },`
+    ///
+    /// The bytes are encoded as a collection, which is the same as the length prefixed fields of a known variant.
+    fn encode_other_variant(&self, other: &EnumVariant, builder: &mut StreamBuilder) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        let names = other.fields.names();
+        self.variant_pattern(other, builder)?;
+        builder.puncts("=>");
+        builder.group(Delimiter::Brace, |body| {
+            body.push_parsed(format!(
+                "<{0} as {1}::Encode>::encode({2}, encoder)?;",
+                self.repr.int_type,
+                crate_name,
+                names[0].to_string_with_prefix(TUPLE_FIELD_PREFIX)
+            ))?;
+            body.push_parsed(format!(
+                "{0}::Encode::encode({1}, encoder)?;",
+                crate_name,
+                names[1].to_string_with_prefix(TUPLE_FIELD_PREFIX)
+            ))?;
+            body.push_parsed("Ok(())")?;
+            Ok(())
+        })?;
+        builder.punct(',');
         Ok(())
     }
 
@@ -134,6 +291,33 @@ impl DeriveEnum {
 
     /// Build the catch-all case for an int-to-enum decode implementation
     fn invalid_variant_case(&self, enum_name: &str, result: &mut StreamBuilder) -> Result {
+        result.ident_str("variant");
+        result.puncts("=>");
+        self.invalid_variant_error(enum_name, result)
+    }
+
+    /// If the enum has an `other` variant, the index of a `#[bincode(skip_encoding)]` variant would be decoded into it.
+    /// Reject it instead, in the form of:
+    /// `variant if variant == idx => Err(bincode::error::DecodeError::UnexpectedVariant { .. }),`
+    fn decode_skipped_variant(
+        &self,
+        enum_name: &str,
+        variant_index: Vec<TokenTree>,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        if self.other.is_none() {
+            return Ok(());
+        }
+        builder.push_parsed("variant if variant ==")?;
+        builder.extend(variant_index);
+        builder.puncts("=>");
+        self.invalid_variant_error(enum_name, builder)?;
+        builder.punct(',');
+        Ok(())
+    }
+
+    /// Build the error that is returned for an invalid variant index
+    fn invalid_variant_error(&self, enum_name: &str, result: &mut StreamBuilder) -> Result {
         let crate_name = self.attributes.crate_name.as_str();
 
         // we'll be generating:
@@ -150,8 +334,6 @@ impl DeriveEnum {
         //   if we have no fixed value variants
         // - bincode::error::AllowedEnumVariants::Allowed(&[<variant1>, <variant2>, ...])
         //   if we have fixed value variants
-        result.ident_str("variant");
-        result.puncts("=>");
         result.ident_str("Err");
         result.group(Delimiter::Parenthesis, |err_inner| {
             err_inner.push_parsed(format!(
@@ -161,7 +343,7 @@ impl DeriveEnum {
             err_inner.group(Delimiter::Brace, |variant_inner| {
                 variant_inner.ident_str("found");
                 variant_inner.punct(':');
-                variant_inner.ident_str("variant");
+                variant_inner.push_parsed(self.found_variant())?;
                 variant_inner.punct(',');
 
                 variant_inner.ident_str("type_name");
@@ -172,8 +354,8 @@ impl DeriveEnum {
                 variant_inner.ident_str("allowed");
                 variant_inner.punct(':');
 
-                if self.variants.iter().any(|i| i.has_fixed_value()) {
-                    // we have fixed values, implement AllowedEnumVariants::Allowed
+                if self.has_fixed_values() || self.has_skipped_variants()? {
+                    // we have fixed values or skipped variants, implement AllowedEnumVariants::Allowed
                     variant_inner.push_parsed(format!(
                         "{}::error::AllowedEnumVariants::Allowed",
                         crate_name
@@ -181,11 +363,30 @@ impl DeriveEnum {
                     variant_inner.group(Delimiter::Parenthesis, |allowed_inner| {
                         allowed_inner.punct('&');
                         allowed_inner.group(Delimiter::Bracket, |allowed_slice| {
-                            for (idx, (ident, _)) in self.iter_fields().enumerate() {
-                                if idx != 0 {
+                            let mut first = true;
+                            for (ident, variant) in self.iter_fields() {
+                                if skips_encoding(variant)? {
+                                    continue;
+                                }
+                                if !first {
                                     allowed_slice.punct(',');
                                 }
-                                allowed_slice.extend(ident);
+                                first = false;
+                                if self.repr.int_type == "i128" {
+                                    allowed_slice.extend(ident);
+                                } else {
+                                    // { const __BINCODE_VARIANT: u8 = !0; __BINCODE_VARIANT as i128 }
+                                    // The constant gives the discriminant the type of the repr, as `!0` on its own would be `-1` as an `i128`
+                                    allowed_slice.group(Delimiter::Brace, |value| {
+                                        value.push_parsed(format!(
+                                            "const __BINCODE_VARIANT: {} =",
+                                            self.repr.int_type
+                                        ))?;
+                                        value.extend(ident);
+                                        value.push_parsed("; __BINCODE_VARIANT as i128")?;
+                                        Ok(())
+                                    })?;
+                                }
                             }
                             Ok(())
                         })?;
@@ -230,65 +431,42 @@ impl DeriveEnum {
                         "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
                         crate_name
                     ))?;
+                } else if self.has_single_variant() {
+                    fn_builder.ident_str("Ok");
+                    fn_builder.group(Delimiter::Parenthesis, |variant_body| {
+                        self.decode_variant(&self.variants[0], false, variant_body)
+                    })?;
                 } else {
                     fn_builder
                         .push_parsed(format!(
-                            "let variant_index = <u32 as {}::Decode>::decode(decoder)?;",
-                            crate_name
+                            "let variant_index = <{} as {}::Decode>::decode(decoder)?;",
+                            self.repr.int_type, crate_name
                         ))?;
                     fn_builder.push_parsed("match variant_index")?;
                     fn_builder.group(Delimiter::Brace, |variant_case| {
                         for (mut variant_index, variant) in self.iter_fields() {
+                            if skips_encoding(variant)? {
+                                self.decode_skipped_variant(&enum_name, variant_index, variant_case)?;
+                                continue;
+                            }
                             // idx => Ok(..)
-                            if variant_index.len() > 1 {
+                            if is_literal(&variant_index) {
+                                variant_case.push(variant_index.remove(0));
+                            } else {
                                 variant_case.push_parsed("x if x == ")?;
                                 variant_case.extend(variant_index);
-                            } else {
-                                variant_case.push(variant_index.remove(0));
                             }
                             variant_case.puncts("=>");
-                            variant_case.ident_str("Ok");
-                            variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
-                                // Self::Variant { }
-                                // Self::Variant { 0: ..., 1: ... 2: ... },
-                                // Self::Variant { a: ..., b: ... c: ... },
-                                variant_case_body.ident_str("Self");
-                                variant_case_body.puncts("::");
-                                variant_case_body.ident(variant.name.clone());
-
-                                variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    let is_tuple = matches!(variant.fields, Fields::Tuple(_));
-                                    for (idx, field) in variant.fields.names().into_iter().enumerate() {
-                                        if is_tuple {
-                                            variant_body.lit_usize(idx);
-                                        } else {
-                                            variant_body.ident(field.unwrap_ident().clone());
-                                        }
-                                        variant_body.punct(':');
-                                        let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                        if attributes.with_serde {
-                                            variant_body
-                                                .push_parsed(format!(
-                                                    "<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?.0,",
-                                                    crate_name
-                                                ))?;
-                                        } else {
-                                            variant_body
-                                                .push_parsed(format!(
-                                                    "{}::Decode::decode(decoder)?,",
-                                                    crate_name
-                                                ))?;
-                                        }
-                                    }
-                                    Ok(())
-                                })?;
-                                Ok(())
-                            })?;
+                            self.decode_known_variant(variant, false, variant_case)?;
                             variant_case.punct(',');
                         }
 
-                        // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
+                        match &self.other {
+                            // unknown idx
+                            Some(other) => self.decode_other_variant(other, false, variant_case),
+                            // invalid idx
+                            None => self.invalid_variant_case(&enum_name, variant_case),
+                        }
                     })?;
                 }
                 Ok(())
@@ -305,7 +483,7 @@ impl DeriveEnum {
         generator.impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
             .modify_generic_constraints(|generics, where_constraints| {
                 for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, format!("{}::enc::BorrowDecode", crate_name)).unwrap();
+                    where_constraints.push_constraint(g, format!("{}::BorrowDecode<'__de>", crate_name)).unwrap();
                 }
             })
             .generate_fn("borrow_decode")
@@ -318,67 +496,195 @@ impl DeriveEnum {
                         "core::result::Result::Err({}::error::DecodeError::EmptyEnum {{ type_name: core::any::type_name::<Self>() }})",
                         crate_name
                     ))?;
+                } else if self.has_single_variant() {
+                    fn_builder.ident_str("Ok");
+                    fn_builder.group(Delimiter::Parenthesis, |variant_body| {
+                        self.decode_variant(&self.variants[0], true, variant_body)
+                    })?;
                 } else {
                     fn_builder
-                        .push_parsed(format!("let variant_index = <u32 as {}::Decode>::decode(decoder)?;", crate_name))?;
+                        .push_parsed(format!("let variant_index = <{} as {}::Decode>::decode(decoder)?;", self.repr.int_type, crate_name))?;
                     fn_builder.push_parsed("match variant_index")?;
                     fn_builder.group(Delimiter::Brace, |variant_case| {
                         for (mut variant_index, variant) in self.iter_fields() {
+                            if skips_encoding(variant)? {
+                                self.decode_skipped_variant(&enum_name, variant_index, variant_case)?;
+                                continue;
+                            }
                             // idx => Ok(..)
-                            if variant_index.len() > 1 {
+                            if is_literal(&variant_index) {
+                                variant_case.push(variant_index.remove(0));
+                            } else {
                                 variant_case.push_parsed("x if x == ")?;
                                 variant_case.extend(variant_index);
-                            } else {
-                                variant_case.push(variant_index.remove(0));
                             }
                             variant_case.puncts("=>");
-                            variant_case.ident_str("Ok");
-                            variant_case.group(Delimiter::Parenthesis, |variant_case_body| {
-                                // Self::Variant { }
-                                // Self::Variant { 0: ..., 1: ... 2: ... },
-                                // Self::Variant { a: ..., b: ... c: ... },
-                                variant_case_body.ident_str("Self");
-                                variant_case_body.puncts("::");
-                                variant_case_body.ident(variant.name.clone());
-
-                                variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    let is_tuple = matches!(variant.fields, Fields::Tuple(_));
-                                    for (idx, field) in variant.fields.names().into_iter().enumerate() {
-                                        if is_tuple {
-                                            variant_body.lit_usize(idx);
-                                        } else {
-                                            variant_body.ident(field.unwrap_ident().clone());
-                                        }
-                                        variant_body.punct(':');
-                                        let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                                        if attributes.with_serde {
-                                            variant_body
-                                                .push_parsed(format!("<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?.0,", crate_name))?;
-                                        } else {
-                                            variant_body.push_parsed(format!("{}::BorrowDecode::borrow_decode(decoder)?,", crate_name))?;
-                                        }
-                                    }
-                                    Ok(())
-                                })?;
-                                Ok(())
-                            })?;
+                            self.decode_known_variant(variant, true, variant_case)?;
                             variant_case.punct(',');
                         }
 
-                        // invalid idx
-                        self.invalid_variant_case(&enum_name, variant_case)
+                        match &self.other {
+                            // unknown idx
+                            Some(other) => self.decode_other_variant(other, true, variant_case),
+                            // invalid idx
+                            None => self.invalid_variant_case(&enum_name, variant_case),
+                        }
                     })?;
                 }
                 Ok(())
             })?;
         Ok(())
     }
+
+    /// Build the decoding of a variant that is known by its index, in the form of:
+    /// `Ok(Self::Variant { .. })`
+    ///
+    /// If the enum has an `other` variant, the fields are length prefixed, and are decoded with:
+    /// `bincode::de::Decoder::decode_length_prefixed_with(decoder, |decoder| Ok(Self::Variant { .. }))`
+    fn decode_known_variant(
+        &self,
+        variant: &EnumVariant,
+        borrow: bool,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        let decode_ok = |builder: &mut StreamBuilder| {
+            builder.ident_str("Ok");
+            builder.group(Delimiter::Parenthesis, |variant_case_body| {
+                self.decode_variant(variant, borrow, variant_case_body)
+            })?;
+            Ok(())
+        };
+        if self.other.is_none() {
+            return decode_ok(builder);
+        }
+        builder.push_parsed(format!(
+            "{}::de::Decoder::decode_length_prefixed_with",
+            crate_name
+        ))?;
+        builder.group(Delimiter::Parenthesis, |args| {
+            args.push_parsed("decoder, |decoder|")?;
+            decode_ok(args)
+        })?;
+        Ok(())
+    }
+
+    /// Build the catch-all case that decodes an unknown variant into the `#[bincode(other)]` variant, in the form of:
+    /// `variant => Ok(Self::Other { 0: variant, 1: bincode::Decode::decode(decoder)? }),`
+    fn decode_other_variant(
+        &self,
+        other: &EnumVariant,
+        borrow: bool,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+        builder.push_parsed("variant => Ok")?;
+        builder.group(Delimiter::Parenthesis, |result| {
+            result.ident_str("Self");
+            result.puncts("::");
+            result.ident(other.name.clone());
+            result.group(Delimiter::Brace, |fields| {
+                let names = other.fields.names();
+                let is_tuple = matches!(other.fields, Fields::Tuple(_));
+                for (idx, field) in names.iter().enumerate() {
+                    if is_tuple {
+                        fields.lit_usize(idx);
+                    } else {
+                        fields.ident(field.unwrap_ident().clone());
+                    }
+                    fields.punct(':');
+                    if idx == 0 {
+                        fields.push_parsed("variant,")?;
+                    } else if borrow {
+                        fields.push_parsed(format!(
+                            "{}::BorrowDecode::borrow_decode(decoder)?,",
+                            crate_name
+                        ))?;
+                    } else {
+                        fields.push_parsed(format!("{}::Decode::decode(decoder)?,", crate_name))?;
+                    }
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+        builder.punct(',');
+        Ok(())
+    }
+
+    /// Build the decoding of a single variant, in the form of:
+    /// `Self::Variant { a: bincode::Decode::decode(decoder)?, ... }`
+    ///
+    /// If `borrow` is set, `BorrowDecode` is used instead of `Decode`.
+    fn decode_variant(
+        &self,
+        variant: &EnumVariant,
+        borrow: bool,
+        builder: &mut StreamBuilder,
+    ) -> Result {
+        let crate_name = self.attributes.crate_name.as_str();
+
+        // Self::Variant { }
+        // Self::Variant { 0: ..., 1: ... 2: ... },
+        // Self::Variant { a: ..., b: ... c: ... },
+        builder.ident_str("Self");
+        builder.puncts("::");
+        builder.ident(variant.name.clone());
+
+        builder.group(Delimiter::Brace, |variant_body| {
+            let is_tuple = matches!(variant.fields, Fields::Tuple(_));
+            for (idx, field) in variant.fields.names().into_iter().enumerate() {
+                if is_tuple {
+                    variant_body.lit_usize(idx);
+                } else {
+                    variant_body.ident(field.unwrap_ident().clone());
+                }
+                variant_body.punct(':');
+                let attributes = field
+                    .attributes()
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                if attributes.with_serde && borrow {
+                    variant_body.push_parsed(format!(
+                        "<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?.0,",
+                        crate_name
+                    ))?;
+                } else if attributes.with_serde {
+                    variant_body.push_parsed(format!(
+                        "<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?.0,",
+                        crate_name
+                    ))?;
+                } else if borrow {
+                    variant_body.push_parsed(format!(
+                        "{}::BorrowDecode::borrow_decode(decoder)?,",
+                        crate_name
+                    ))?;
+                } else {
+                    variant_body
+                        .push_parsed(format!("{}::Decode::decode(decoder)?,", crate_name))?;
+                }
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Returns `true` if the variant is marked with `#[bincode(skip_encoding)]`.
+fn skips_encoding(variant: &EnumVariant) -> Result<bool> {
+    Ok(
+        match variant.attributes.get_attribute::<VariantAttributes>()? {
+            Some(attributes) => attributes.skip_encoding,
+            None => false,
+        },
+    )
 }
 
 struct EnumVariantIterator<'a> {
     variants: &'a [EnumVariant],
+    discriminants: &'a [(String, Vec<TokenTree>)],
     idx: usize,
-    last_val: Option<(Literal, u32)>,
+    last_val: Option<(Vec<TokenTree>, u32)>,
 }
 
 impl<'a> Iterator for EnumVariantIterator<'a> {
@@ -389,21 +695,39 @@ impl<'a> Iterator for EnumVariantIterator<'a> {
         let variant = self.variants.get(self.idx)?;
         self.idx += 1;
 
-        let tokens = if let Fields::Integer(lit) = &variant.fields {
-            let tree = TokenTree::Literal(lit.clone());
-            self.last_val = Some((lit.clone(), 0));
-            vec![tree]
-        } else if let Some((lit, add)) = self.last_val.as_mut() {
+        let fixed_value = self
+            .discriminants
+            .iter()
+            .find(|(name, _)| variant.name.to_string() == *name)
+            .map(|(_, value)| value);
+
+        let tokens = if let Some(value) = fixed_value {
+            // A single literal can be used as a pattern, anything else is wrapped in parentheses
+            let tokens = if is_literal(value) {
+                value.clone()
+            } else {
+                vec![TokenTree::Group(Group::new(
+                    Delimiter::Parenthesis,
+                    value.iter().cloned().collect(),
+                ))]
+            };
+            self.last_val = Some((tokens.clone(), 0));
+            tokens
+        } else if let Some((value, add)) = self.last_val.as_mut() {
             *add += 1;
-            vec![
-                TokenTree::Literal(lit.clone()),
-                TokenTree::Punct(Punct::new('+', Spacing::Alone)),
-                TokenTree::Literal(Literal::u32_suffixed(*add)),
-            ]
+            let mut tokens = value.clone();
+            tokens.push(TokenTree::Punct(Punct::new('+', Spacing::Alone)));
+            tokens.push(TokenTree::Literal(Literal::u32_unsuffixed(*add)));
+            tokens
         } else {
-            vec![TokenTree::Literal(Literal::u32_suffixed(idx as u32))]
+            vec![TokenTree::Literal(Literal::u32_unsuffixed(idx as u32))]
         };
 
         Some((tokens, variant))
     }
 }
+
+/// Returns true if the variant index is a single literal, which can be used as a pattern in a `match`
+fn is_literal(tokens: &[TokenTree]) -> bool {
+    matches!(tokens, [TokenTree::Literal(_)])
+}
@@ -1,6 +1,6 @@
 use crate::attribute::{ContainerAttributes, FieldAttributes};
 use virtue::generate::Generator;
-use virtue::parse::Fields;
+use virtue::parse::{Fields, UnnamedField};
 use virtue::prelude::*;
 
 pub(crate) struct DeriveStruct {
@@ -12,9 +12,60 @@ impl DeriveStruct {
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
         let DeriveStruct { fields, attributes } = self;
         let crate_name = attributes.crate_name;
+        let optional_fields = optional_field_names(&fields)?;
+
+        // Structs with optional fields are encoded by `PartialEncode::encode_partial`, with every optional field present
+        let encode_fields = |fn_body: &mut StreamBuilder| -> Result<()> {
+            if let Some(version) = attributes.version {
+                fn_body.push_parsed(format!(
+                    "<u8 as {}::Encode>::encode(&{}, encoder)?;",
+                    crate_name, version
+                ))?;
+            }
+            if !optional_fields.is_empty() {
+                let mask_type = presence_mask_type(optional_fields.len());
+                let present = if mask_type == "u64" {
+                    "present".to_string()
+                } else {
+                    format!("(present as {})", mask_type)
+                };
+                fn_body.push_parsed(format!(
+                    "let present = present & {}u64;
+                    <{} as {}::Encode>::encode(&{}, encoder)?;",
+                    all_present(optional_fields.len()),
+                    mask_type,
+                    crate_name,
+                    present
+                ))?;
+            }
+            let mut optional_index = 0;
+            for field in fields.names() {
+                let attributes = field
+                    .attributes()
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                let value = if attributes.with_serde {
+                    format!("&{}::serde::Compat(&self.{})", crate_name, field)
+                } else {
+                    format!("&self.{}", field)
+                };
+                let encode = crate::encode_field(&crate_name, &value, &field);
+                if attributes.optional {
+                    fn_body.push_parsed(format!(
+                        "if present & (1 << {}) != 0 {{ {} }}",
+                        optional_index, encode
+                    ))?;
+                    optional_index += 1;
+                } else {
+                    fn_body.push_parsed(encode)?;
+                }
+            }
+            fn_body.push_parsed("Ok(())")?;
+            Ok(())
+        };
 
         generator
-            .impl_for(&format!("{}::Encode", crate_name))
+            .impl_for(format!("{}::Encode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
                 for g in generics.iter_generics() {
                     where_constraints
@@ -31,26 +82,58 @@ impl DeriveStruct {
                 crate_name
             ))
             .body(|fn_body| {
-                for field in fields.names() {
-                    let attributes = field
-                        .attributes()
-                        .get_attribute::<FieldAttributes>()?
-                        .unwrap_or_default();
-                    if attributes.with_serde {
-                        fn_body.push_parsed(format!(
-                            "{0}::Encode::encode(&{0}::serde::Compat(&self.{1}), encoder)?;",
-                            crate_name, field
-                        ))?;
-                    } else {
-                        fn_body.push_parsed(format!(
-                            "{}::Encode::encode(&self.{}, encoder)?;",
-                            crate_name, field
-                        ))?;
-                    }
+                if optional_fields.is_empty() {
+                    encode_fields(fn_body)
+                } else {
+                    fn_body.push_parsed(format!(
+                        "<Self as {}::PartialEncode>::encode_partial(self, {}u64, encoder)",
+                        crate_name,
+                        all_present(optional_fields.len())
+                    ))?;
+                    Ok(())
                 }
-                fn_body.push_parsed("Ok(())")?;
+            })?;
+
+        if optional_fields.is_empty() {
+            return Ok(());
+        }
+        let mut partial_encode = generator.impl_for(format!("{}::PartialEncode", crate_name));
+        partial_encode.modify_generic_constraints(|generics, where_constraints| {
+            for g in generics.iter_generics() {
+                where_constraints
+                    .push_constraint(g, format!("{}::Encode", crate_name))
+                    .unwrap();
+            }
+        });
+        partial_encode
+            .generate_fn("optional_fields")
+            .with_return_type("&'static [&'static str]")
+            .body(|fn_body| {
+                let names: Vec<String> = optional_fields
+                    .iter()
+                    .map(|name| format!("{:?}", name))
+                    .collect();
+                fn_body.push_parsed(format!("&[{}]", names.join(", ")))?;
                 Ok(())
             })?;
+        partial_encode
+            .generate_fn("all_present")
+            .with_return_type("u64")
+            .body(|fn_body| {
+                fn_body.push_parsed(format!("{}u64", all_present(optional_fields.len())))?;
+                Ok(())
+            })?;
+        partial_encode
+            .generate_fn("encode_partial")
+            .with_generic_deps("E", [format!("{}::enc::Encoder", crate_name)])
+            .with_self_arg(virtue::generate::FnSelfArg::RefSelf)
+            .with_arg("present", "u64")
+            .with_arg("encoder", "&mut E")
+            .with_return_type(format!(
+                "core::result::Result<(), {}::error::EncodeError>",
+                crate_name
+            ))
+            .body(encode_fields)?;
         Ok(())
     }
 
@@ -58,19 +141,38 @@ impl DeriveStruct {
         // Remember to keep this mostly in sync with generate_borrow_decode
         let DeriveStruct { fields, attributes } = self;
         let crate_name = attributes.crate_name;
+        let optional_count = optional_field_names(&fields)?.len();
+        let defaulted_generics = defaulted_generics(&fields)?;
 
         generator
             .impl_for(format!("{}::Decode", crate_name))
             .modify_generic_constraints(|generics, where_constraints| {
                 for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, format!("{}::Decode", crate_name)).unwrap();
+                    where_constraints
+                        .push_constraint(g, format!("{}::Decode", crate_name))
+                        .unwrap();
+                    if defaulted_generics.contains(&g.name().to_string()) {
+                        where_constraints
+                            .push_constraint(g, "core::default::Default")
+                            .unwrap();
+                    }
                 }
             })
             .generate_fn("decode")
             .with_generic_deps("D", [format!("{}::de::Decoder", crate_name)])
             .with_arg("decoder", "&mut D")
-            .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
+            .with_return_type(format!(
+                "core::result::Result<Self, {}::error::DecodeError>",
+                crate_name
+            ))
             .body(|fn_body| {
+                if let Some(version) = attributes.version {
+                    fn_body.push_parsed(decode_version(&crate_name, version))?;
+                }
+                if optional_count > 0 {
+                    fn_body.push_parsed(decode_presence(&crate_name, optional_count))?;
+                }
+                let mut optional_index = 0;
                 // Ok(Self {
                 fn_body.ident_str("Ok");
                 fn_body.group(Delimiter::Parenthesis, |ok_group| {
@@ -83,22 +185,25 @@ impl DeriveStruct {
                         //      ...
                         // }
                         for field in fields.names() {
-                            let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                            if attributes.with_serde {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{1}: (<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?).0,",
-                                        crate_name,
-                                        field
-                                    ))?;
+                            let attributes = field
+                                .attributes()
+                                .get_attribute::<FieldAttributes>()?
+                                .unwrap_or_default();
+                            let value = if attributes.with_serde {
+                                format!(
+                                    "(<{0}::serde::Compat<_> as {0}::Decode>::decode(decoder)?).0",
+                                    crate_name
+                                )
                             } else {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{1}: {0}::Decode::decode(decoder)?,",
-                                        crate_name,
-                                        field
-                                    ))?;
-                            }
+                                format!("{0}::Decode::decode(decoder)?", crate_name)
+                            };
+                            let value =
+                                decode_optional(&value, attributes.optional, &mut optional_index);
+                            struct_body.push_parsed(format!(
+                                "{}: {},",
+                                field,
+                                decode_since(&value, attributes.since)
+                            ))?;
                         }
                         Ok(())
                     })?;
@@ -113,12 +218,17 @@ impl DeriveStruct {
         // Remember to keep this mostly in sync with generate_decode
         let DeriveStruct { fields, attributes } = self;
         let crate_name = attributes.crate_name;
+        let optional_count = optional_field_names(&fields)?.len();
+        let defaulted_generics = defaulted_generics(&fields)?;
 
         generator
             .impl_for_with_lifetimes(format!("{}::BorrowDecode", crate_name), ["__de"])
             .modify_generic_constraints(|generics, where_constraints| {
                 for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, format!("{}::BorrowDecode", crate_name)).unwrap();
+                    where_constraints.push_constraint(g, format!("{}::BorrowDecode<'__de>", crate_name)).unwrap();
+                    if defaulted_generics.contains(&g.name().to_string()) {
+                        where_constraints.push_constraint(g, "core::default::Default").unwrap();
+                    }
                 }
             })
             .generate_fn("borrow_decode")
@@ -126,6 +236,13 @@ impl DeriveStruct {
             .with_arg("decoder", "&mut D")
             .with_return_type(format!("core::result::Result<Self, {}::error::DecodeError>", crate_name))
             .body(|fn_body| {
+                if let Some(version) = attributes.version {
+                    fn_body.push_parsed(decode_version(&crate_name, version))?;
+                }
+                if optional_count > 0 {
+                    fn_body.push_parsed(decode_presence(&crate_name, optional_count))?;
+                }
+                let mut optional_index = 0;
                 // Ok(Self {
                 fn_body.ident_str("Ok");
                 fn_body.group(Delimiter::Parenthesis, |ok_group| {
@@ -133,21 +250,16 @@ impl DeriveStruct {
                     ok_group.group(Delimiter::Brace, |struct_body| {
                         for field in fields.names() {
                             let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                            if attributes.with_serde {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{1}: (<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?).0,",
-                                        crate_name,
-                                        field
-                                    ))?;
+                            let value = if attributes.with_serde {
+                                format!(
+                                    "(<{0}::serde::BorrowCompat<_> as {0}::BorrowDecode>::borrow_decode(decoder)?).0",
+                                    crate_name
+                                )
                             } else {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{1}: {0}::BorrowDecode::borrow_decode(decoder)?,",
-                                        crate_name,
-                                        field
-                                    ))?;
-                            }
+                                format!("{0}::BorrowDecode::borrow_decode(decoder)?", crate_name)
+                            };
+                            let value = decode_optional(&value, attributes.optional, &mut optional_index);
+                            struct_body.push_parsed(format!("{}: {},", field, decode_since(&value, attributes.since)))?;
                         }
                         Ok(())
                     })?;
@@ -158,3 +270,121 @@ impl DeriveStruct {
         Ok(())
     }
 }
+
+/// Reads the version byte of a `#[bincode(version = N)]` struct into `__bincode_version`, and rejects versions that are newer than `N`.
+fn decode_version(crate_name: &str, version: u8) -> String {
+    format!(
+        "let __bincode_version = <u8 as {0}::Decode>::decode(decoder)?;
+        if __bincode_version > {1} {{
+            return Err({0}::error::DecodeError::UnsupportedVersion {{ found: __bincode_version, latest: {1} }});
+        }}",
+        crate_name, version
+    )
+}
+
+/// Wraps the expression that decodes a `#[bincode(since = N)]` field, so the field is only read if the stored version is at least `N`. Older data does not contain the field, so it is set to its default value instead.
+fn decode_since(value: &str, since: Option<u8>) -> String {
+    match since {
+        Some(since) => format!(
+            "if __bincode_version >= {} {{ {} }} else {{ core::default::Default::default() }}",
+            since, value
+        ),
+        None => value.to_string(),
+    }
+}
+
+/// The identifiers in the types of the `#[bincode(optional)]` and `#[bincode(since = N)]` fields. These fields are set to their default value if they are not in the data, so the generic parameters among them need a `Default` bound.
+fn defaulted_generics(fields: &Fields) -> Result<Vec<String>> {
+    fn collect_idents(tokens: &[TokenTree], idents: &mut Vec<String>) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => idents.push(ident.to_string()),
+                TokenTree::Group(group) => {
+                    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+                    collect_idents(&tokens, idents);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let fields: Vec<&UnnamedField> = match fields {
+        Fields::Struct(fields) => fields.iter().map(|(_, field)| field).collect(),
+        Fields::Tuple(fields) => fields.iter().collect(),
+        _ => Vec::new(),
+    };
+    let mut idents = Vec::new();
+    for field in fields {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if attributes.optional || attributes.since.is_some() {
+            collect_idents(&field.r#type, &mut idents);
+        }
+    }
+    Ok(idents)
+}
+
+/// The names of the `#[bincode(optional)]` fields, in the order of their bits in the presence mask.
+fn optional_field_names(fields: &Fields) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for field in fields.names() {
+        let attributes = field
+            .attributes()
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if attributes.optional {
+            names.push(field.to_string().trim_start_matches("r#").to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// The presence mask with the bits of `count` optional fields set. This is also implemented as `PartialEncode::all_present`.
+fn all_present(count: usize) -> u64 {
+    if count >= 64 {
+        u64::MAX
+    } else {
+        (1 << count) - 1
+    }
+}
+
+/// The smallest unsigned integer type that has a bit for each of the `count` optional fields.
+fn presence_mask_type(count: usize) -> &'static str {
+    match count {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        _ => "u64",
+    }
+}
+
+/// Reads the presence mask of a struct with `count` optional fields into `__bincode_present` as a `u64`, and rejects masks with bits for fields that the struct does not have.
+fn decode_presence(crate_name: &str, count: usize) -> String {
+    let mask_type = presence_mask_type(count);
+    let cast = if mask_type == "u64" { "" } else { " as u64" };
+    format!(
+        "let __bincode_present = <{2} as {0}::Decode>::decode(decoder)?{3};
+        if __bincode_present & !{1}u64 != 0 {{
+            return Err({0}::error::DecodeError::InvalidPresenceMask {{ type_name: core::any::type_name::<Self>(), mask: __bincode_present }});
+        }}",
+        crate_name,
+        all_present(count),
+        mask_type,
+        cast
+    )
+}
+
+/// Wraps the expression that decodes a `#[bincode(optional)]` field, so the field is only read if its bit is set in the presence mask. Absent fields are set to their default value instead.
+fn decode_optional(value: &str, optional: bool, optional_index: &mut usize) -> String {
+    if !optional {
+        return value.to_string();
+    }
+    let index = *optional_index;
+    *optional_index += 1;
+    format!(
+        "if __bincode_present & (1 << {}) != 0 {{ {} }} else {{ core::default::Default::default() }}",
+        index, value
+    )
+}
@@ -2,7 +2,8 @@ mod attribute;
 mod derive_enum;
 mod derive_struct;
 
-use attribute::ContainerAttributes;
+use attribute::{ContainerAttributes, EnumRepr, FieldAttributes, VariantAttributes};
+use virtue::parse::IdentOrIndex;
 use virtue::prelude::*;
 
 #[proc_macro_derive(Encode, attributes(bincode))]
@@ -11,11 +12,18 @@ pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 }
 
 fn derive_encode_inner(input: TokenStream) -> Result<TokenStream> {
+    let (input, discriminants) =
+        take_variant_discriminants(strip_where_clause_trailing_comma(input));
     let parse = Parse::new(input)?;
     let (mut generator, attributes, body) = parse.into_generator();
+    let repr = attributes.get_attribute::<EnumRepr>()?.unwrap_or_default();
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    check_transparent(&attributes, &body)?;
+    check_version(&attributes, &body)?;
+    check_optional(&attributes, &body)?;
+    check_skip_encoding(&body)?;
 
     match body {
         Body::Struct(body) => {
@@ -25,10 +33,15 @@ fn derive_encode_inner(input: TokenStream) -> Result<TokenStream> {
             }
             .generate_encode(&mut generator)?;
         }
-        Body::Enum(body) => {
+        Body::Enum(mut body) => {
+            check_discriminants(&repr, &discriminants)?;
+            let other = take_other_variant(&mut body.variants, &discriminants)?;
             derive_enum::DeriveEnum {
                 variants: body.variants,
+                other,
+                discriminants,
                 attributes,
+                repr,
             }
             .generate_encode(&mut generator)?;
         }
@@ -44,11 +57,18 @@ pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 }
 
 fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
+    let (input, discriminants) =
+        take_variant_discriminants(strip_where_clause_trailing_comma(input));
     let parse = Parse::new(input)?;
     let (mut generator, attributes, body) = parse.into_generator();
+    let repr = attributes.get_attribute::<EnumRepr>()?.unwrap_or_default();
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    check_transparent(&attributes, &body)?;
+    check_version(&attributes, &body)?;
+    check_optional(&attributes, &body)?;
+    check_skip_encoding(&body)?;
 
     match body {
         Body::Struct(body) => {
@@ -58,10 +78,15 @@ fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
             }
             .generate_decode(&mut generator)?;
         }
-        Body::Enum(body) => {
+        Body::Enum(mut body) => {
+            check_discriminants(&repr, &discriminants)?;
+            let other = take_other_variant(&mut body.variants, &discriminants)?;
             derive_enum::DeriveEnum {
                 variants: body.variants,
+                other,
+                discriminants,
                 attributes,
+                repr,
             }
             .generate_decode(&mut generator)?;
         }
@@ -77,11 +102,18 @@ pub fn derive_borrow_decode(input: proc_macro::TokenStream) -> proc_macro::Token
 }
 
 fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
+    let (input, discriminants) =
+        take_variant_discriminants(strip_where_clause_trailing_comma(input));
     let parse = Parse::new(input)?;
     let (mut generator, attributes, body) = parse.into_generator();
+    let repr = attributes.get_attribute::<EnumRepr>()?.unwrap_or_default();
     let attributes = attributes
         .get_attribute::<ContainerAttributes>()?
         .unwrap_or_default();
+    check_transparent(&attributes, &body)?;
+    check_version(&attributes, &body)?;
+    check_optional(&attributes, &body)?;
+    check_skip_encoding(&body)?;
 
     match body {
         Body::Struct(body) => {
@@ -91,10 +123,15 @@ fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
             }
             .generate_borrow_decode(&mut generator)?;
         }
-        Body::Enum(body) => {
+        Body::Enum(mut body) => {
+            check_discriminants(&repr, &discriminants)?;
+            let other = take_other_variant(&mut body.variants, &discriminants)?;
             derive_enum::DeriveEnum {
                 variants: body.variants,
+                other,
+                discriminants,
                 attributes,
+                repr,
             }
             .generate_borrow_decode(&mut generator)?;
         }
@@ -103,3 +140,309 @@ fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
     generator.export_to_file("BorrowDecode");
     generator.finish()
 }
+
+/// Makes sure that `#[bincode(transparent)]` is only used on structs with exactly one field.
+///
+/// Such a struct is encoded as its field, which the derive already does for every struct. The attribute makes sure this stays true when fields are added.
+fn check_transparent(attributes: &ContainerAttributes, body: &Body) -> Result<()> {
+    if !attributes.transparent {
+        return Ok(());
+    }
+    match body {
+        Body::Struct(body) if body.fields.names().len() == 1 => Ok(()),
+        Body::Struct(_) => Err(Error::custom(
+            "#[bincode(transparent)] requires a struct with exactly one field",
+        )),
+        Body::Enum(_) => Err(Error::custom(
+            "#[bincode(transparent)] is not supported on enums",
+        )),
+    }
+}
+
+/// Makes sure that `#[bincode(version = N)]` is only used on structs, and that every `#[bincode(since = M)]` field is part of a versioned struct with `M <= N`.
+fn check_version(attributes: &ContainerAttributes, body: &Body) -> Result<()> {
+    let fields = match body {
+        Body::Struct(body) => &body.fields,
+        Body::Enum(body) => {
+            if attributes.version.is_some() {
+                return Err(Error::custom(
+                    "#[bincode(version = ..)] is not supported on enums",
+                ));
+            }
+            for variant in &body.variants {
+                for field in variant.fields.names() {
+                    if let Some(attributes) =
+                        field.attributes().get_attribute::<FieldAttributes>()?
+                    {
+                        if attributes.since.is_some() {
+                            return Err(Error::custom(
+                                "#[bincode(since = ..)] is not supported on enums",
+                            ));
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
+    };
+    if attributes.transparent && attributes.version.is_some() {
+        return Err(Error::custom(
+            "#[bincode(transparent)] and #[bincode(version = ..)] can not be combined",
+        ));
+    }
+    for field in fields.names() {
+        let since = field
+            .attributes()
+            .get_attribute::<FieldAttributes>()?
+            .and_then(|a| a.since);
+        match (since, attributes.version) {
+            (Some(_), None) => {
+                return Err(Error::custom(
+                    "#[bincode(since = ..)] requires #[bincode(version = ..)] on the struct",
+                ))
+            }
+            (Some(since), Some(version)) if since > version => {
+                return Err(Error::custom(
+                    "#[bincode(since = ..)] can not be greater than the version of the struct",
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Makes sure that `#[bincode(optional)]` is only used on the fields of structs that are not transparent, and that a struct has at most 64 optional fields, one for every bit of the presence mask.
+fn check_optional(attributes: &ContainerAttributes, body: &Body) -> Result<()> {
+    let fields = match body {
+        Body::Struct(body) => &body.fields,
+        Body::Enum(body) => {
+            for variant in &body.variants {
+                for field in variant.fields.names() {
+                    if let Some(attributes) =
+                        field.attributes().get_attribute::<FieldAttributes>()?
+                    {
+                        if attributes.optional {
+                            return Err(Error::custom(
+                                "#[bincode(optional)] is not supported on enums",
+                            ));
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
+    };
+    let mut count = 0;
+    for field in fields.names() {
+        if let Some(attributes) = field.attributes().get_attribute::<FieldAttributes>()? {
+            if attributes.optional {
+                count += 1;
+            }
+        }
+    }
+    if count > 0 && attributes.transparent {
+        return Err(Error::custom(
+            "#[bincode(transparent)] and #[bincode(optional)] can not be combined",
+        ));
+    }
+    if count > 64 {
+        return Err(Error::custom(
+            "A struct can have at most 64 #[bincode(optional)] fields",
+        ));
+    }
+    Ok(())
+}
+
+/// Makes sure that an enum with `#[bincode(skip_encoding)]` variants has at least one variant that can be encoded and decoded.
+fn check_skip_encoding(body: &Body) -> Result<()> {
+    let variants = match body {
+        Body::Enum(body) if !body.variants.is_empty() => &body.variants,
+        _ => return Ok(()),
+    };
+    for variant in variants {
+        let attributes = variant
+            .attributes
+            .get_attribute::<VariantAttributes>()?
+            .unwrap_or_default();
+        if !attributes.skip_encoding {
+            return Ok(());
+        }
+    }
+    Err(Error::custom(
+        "#[bincode(skip_encoding)] can not be used on every variant",
+    ))
+}
+
+/// A negative discriminant, e.g. `Up = -1`, can not be encoded as the default `u32` variant index, so it requires a signed `#[repr(..)]`.
+fn check_discriminants(repr: &EnumRepr, discriminants: &[(String, Vec<TokenTree>)]) -> Result {
+    if repr.is_signed() {
+        return Ok(());
+    }
+    for (_, value) in discriminants {
+        if let Some(TokenTree::Punct(p)) = value.first() {
+            if p.as_char() == '-' {
+                return Err(Error::custom_at(
+                    "A negative discriminant requires a signed #[repr(..)], e.g. #[repr(i32)]",
+                    p.span(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removes the variant marked with `#[bincode(other)]` from `variants`, and returns it.
+///
+/// The variant must be the last variant, so the indices of the other variants are not affected, and it must have exactly two fields: the tag and the bytes of the unknown variant.
+fn take_other_variant(
+    variants: &mut Vec<EnumVariant>,
+    discriminants: &[(String, Vec<TokenTree>)],
+) -> Result<Option<EnumVariant>> {
+    let mut other_index = None;
+    for (index, variant) in variants.iter().enumerate() {
+        let attributes = variant
+            .attributes
+            .get_attribute::<VariantAttributes>()?
+            .unwrap_or_default();
+        if !attributes.other {
+            continue;
+        }
+        if attributes.skip_encoding {
+            return Err(Error::custom_at(
+                "#[bincode(other)] and #[bincode(skip_encoding)] can not be combined",
+                variant.name.span(),
+            ));
+        }
+        if other_index.is_some() {
+            return Err(Error::custom_at(
+                "Only one variant can have #[bincode(other)]",
+                variant.name.span(),
+            ));
+        }
+        if index + 1 != variants.len() {
+            return Err(Error::custom_at(
+                "#[bincode(other)] must be the last variant",
+                variant.name.span(),
+            ));
+        }
+        if index == 0 {
+            return Err(Error::custom_at(
+                "#[bincode(other)] requires at least one other variant",
+                variant.name.span(),
+            ));
+        }
+        if variant.fields.names().len() != 2 {
+            return Err(Error::custom_at(
+                "#[bincode(other)] requires a variant with two fields, the tag and the bytes",
+                variant.name.span(),
+            ));
+        }
+        let name = variant.name.to_string();
+        if discriminants.iter().any(|(n, _)| *n == name) {
+            return Err(Error::custom_at(
+                "#[bincode(other)] can not have an explicit discriminant",
+                variant.name.span(),
+            ));
+        }
+        other_index = Some(index);
+    }
+    Ok(other_index.map(|index| variants.remove(index)))
+}
+
+/// Generates the statement that encodes a single field, where `value` is an expression that references the field.
+///
+/// The error of the field is wrapped with `EncodeError::with_field`, so the error shows which field failed to encode.
+fn encode_field(crate_name: &str, value: &str, field: &IdentOrIndex) -> String {
+    let name = field.to_string();
+    let name = name.trim_start_matches("r#");
+    format!(
+        "{0}::Encode::encode({1}, encoder).map_err(|e| e.with_field(\"{2}\"))?;",
+        crate_name, value, name
+    )
+}
+
+/// Removes the trailing comma of a `where` clause, e.g. `where T: Clone, { .. }`.
+///
+/// We append our own constraints to the `where` clause of the type, separated by a comma.
+/// If the user's clause already ends with a comma, this would result in `T: Clone,, T: Encode`.
+fn strip_where_clause_trailing_comma(input: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let where_index = tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == "where"));
+    if let Some(where_index) = where_index {
+        let end = tokens[where_index..].iter().position(|t| match t {
+            TokenTree::Group(g) => g.delimiter() == Delimiter::Brace,
+            TokenTree::Punct(p) => p.as_char() == ';',
+            _ => false,
+        });
+        if let Some(end) = end {
+            let comma = where_index + end - 1;
+            if matches!(&tokens[comma], TokenTree::Punct(p) if p.as_char() == ',') {
+                tokens.remove(comma);
+            }
+        }
+    }
+    tokens.into_iter().collect()
+}
+
+/// Removes the explicit discriminants of enum variants, e.g. `Move { x: i32, y: i32 } = 10` or `Up = -1`, and returns them by variant name.
+///
+/// `virtue` only supports a single literal as the discriminant of a unit variant, so we take these out before parsing.
+fn take_variant_discriminants(input: TokenStream) -> (TokenStream, Vec<(String, Vec<TokenTree>)>) {
+    let mut discriminants = Vec::new();
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let enum_index = tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Ident(i) if i.to_string() == "enum"));
+    let body_index = enum_index.and_then(|enum_index| {
+        tokens[enum_index..]
+            .iter()
+            .position(|t| matches!(t, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace))
+            .map(|index| enum_index + index)
+    });
+    let body_index = match body_index {
+        Some(body_index) => body_index,
+        None => return (tokens.into_iter().collect(), discriminants),
+    };
+    let body = match &tokens[body_index] {
+        TokenTree::Group(group) => group.clone(),
+        _ => unreachable!(),
+    };
+
+    let mut variant_tokens: Vec<TokenTree> = body.stream().into_iter().collect();
+    let mut index = 0;
+    while index < variant_tokens.len() {
+        let name = match &variant_tokens[index] {
+            TokenTree::Ident(name) => name.to_string(),
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        // Skip the fields, if any
+        let mut assign = index + 1;
+        if matches!(
+            variant_tokens.get(assign),
+            Some(TokenTree::Group(g)) if g.delimiter() != Delimiter::Bracket
+        ) {
+            assign += 1;
+        }
+        if matches!(variant_tokens.get(assign), Some(TokenTree::Punct(p)) if p.as_char() == '=') {
+            // The discriminant is everything up to the next variant
+            let end = variant_tokens[assign..]
+                .iter()
+                .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ','))
+                .map_or(variant_tokens.len(), |end| assign + end);
+            let value = variant_tokens.drain(assign..end).skip(1).collect();
+            discriminants.push((name, value));
+        }
+        index = assign;
+    }
+
+    let mut new_body = Group::new(body.delimiter(), variant_tokens.into_iter().collect());
+    new_body.set_span(body.span());
+    tokens[body_index] = TokenTree::Group(new_body);
+    (tokens.into_iter().collect(), discriminants)
+}
@@ -6,7 +6,7 @@ use std::ffi::CString;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::num::{NonZeroI128, NonZeroI32, NonZeroU128, NonZeroU32};
 use std::path::PathBuf;
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
 #[derive(bincode::Decode, bincode::Encode, PartialEq, Debug, serde::Serialize, serde::Deserialize, Eq, PartialOrd, Ord)]
 enum AllTypes {
@@ -18,7 +18,6 @@ enum AllTypes {
     Box(Box<u8>),
     BoxSlice(Box<[u8]>),
     CString(CString),
-    SystemTime(SystemTime),
     Duration(Duration),
     PathBuf(PathBuf),
     IpAddr(IpAddr),
@@ -36,6 +35,7 @@ enum AllTypes {
     U128(u128),
     U8(u8),
     // Cow(Cow<'static, [u8]>), Blocked, see comment on decode
+    // SystemTime(SystemTime), Bincode 2 encodes a sign byte to support times before UNIX_EPOCH
 }
 
 fuzz_target!(|data: &[u8]| {
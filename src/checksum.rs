@@ -0,0 +1,224 @@
+//! Append a lightweight checksum to the encoded bytes, and verify it when decoding.
+//!
+//! A [ChecksumWriter] computes a [Checksum] over everything that is written through it, and [ChecksumWriter::finish] appends the checksum as [Checksum::SIZE] big-endian bytes. A [ChecksumReader] computes the same checksum over everything that is read through it, and [ChecksumReader::verify] compares it with the stored checksum. If they are different, [DecodeError::ChecksumMismatch] is returned.
+//!
+//! [Fletcher16] and [Adler32] are provided. These detect accidental corruption, like a flipped bit, but they are not cryptographic hashes and do not protect against tampering.
+//!
+//! ```
+//! use bincode::checksum::Adler32;
+//! use bincode::error::DecodeError;
+//!
+//! let config = bincode::config::standard();
+//! let mut buffer = [0u8; 32];
+//! let len = bincode::checksum::encode_into_slice::<Adler32, _, _>((5u8, 1000u32), &mut buffer, config).unwrap();
+//! assert_eq!(len, 4 + 4);
+//!
+//! let (decoded, _): ((u8, u32), usize) =
+//!     bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len], config).unwrap();
+//! assert_eq!(decoded, (5, 1000));
+//!
+//! buffer[2] ^= 1;
+//! let result: Result<((u8, u32), usize), _> =
+//!     bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len], config);
+//! assert!(matches!(result, Err(DecodeError::ChecksumMismatch { .. })));
+//! ```
+
+use crate::{
+    config::Config,
+    de::{
+        read::{BorrowReader, Reader, SliceReader},
+        BorrowDecode,
+    },
+    enc::{
+        write::{SliceWriter, Writer},
+        Encode,
+    },
+    error::{DecodeError, EncodeError},
+};
+
+/// A checksum that is computed over a stream of bytes.
+pub trait Checksum: Default {
+    /// The amount of bytes that the checksum is stored in, at most 4.
+    const SIZE: usize;
+
+    /// Add `bytes` to the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// The checksum of all the bytes so far. Only the lowest [Checksum::SIZE] bytes are stored.
+    fn value(&self) -> u32;
+}
+
+/// The [Fletcher-16](https://en.wikipedia.org/wiki/Fletcher%27s_checksum) checksum, stored in 2 bytes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fletcher16 {
+    sum1: u16,
+    sum2: u16,
+}
+
+impl Checksum for Fletcher16 {
+    const SIZE: usize = 2;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.sum1 = (self.sum1 + *byte as u16) % 255;
+            self.sum2 = (self.sum2 + self.sum1) % 255;
+        }
+    }
+
+    fn value(&self) -> u32 {
+        ((self.sum2 as u32) << 8) | self.sum1 as u32
+    }
+}
+
+/// The [Adler-32](https://en.wikipedia.org/wiki/Adler-32) checksum, stored in 4 bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Checksum for Adler32 {
+    const SIZE: usize = 4;
+
+    fn update(&mut self, bytes: &[u8]) {
+        const MOD_ADLER: u32 = 65521;
+        for byte in bytes {
+            self.a = (self.a + *byte as u32) % MOD_ADLER;
+            self.b = (self.b + self.a) % MOD_ADLER;
+        }
+    }
+
+    fn value(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+/// A writer that computes the checksum `C` over all the bytes that are written to the inner writer. See the [module documentation](self) for more information.
+pub struct ChecksumWriter<W, C> {
+    writer: W,
+    checksum: C,
+}
+
+impl<W: Writer, C: Checksum> ChecksumWriter<W, C> {
+    /// Create a writer that computes the checksum of all the bytes that are written to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            checksum: C::default(),
+        }
+    }
+
+    /// The checksum of the bytes that were written so far.
+    pub fn checksum(&self) -> u32 {
+        self.checksum.value()
+    }
+
+    /// Append the checksum to the inner writer, and return the inner writer.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        let bytes = self.checksum.value().to_be_bytes();
+        self.writer.write(&bytes[bytes.len() - C::SIZE..])?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Writer, C: Checksum> Writer for ChecksumWriter<W, C> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write(bytes)?;
+        self.checksum.update(bytes);
+        Ok(())
+    }
+}
+
+/// A reader that computes the checksum `C` over all the bytes that are read from the inner reader. See the [module documentation](self) for more information.
+///
+/// This reader does not support `peek_read`, so every byte that is decoded passes through the checksum.
+pub struct ChecksumReader<R, C> {
+    reader: R,
+    checksum: C,
+}
+
+impl<R: Reader, C: Checksum> ChecksumReader<R, C> {
+    /// Create a reader that computes the checksum of all the bytes that are read from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            checksum: C::default(),
+        }
+    }
+
+    /// The checksum of the bytes that were read so far.
+    pub fn checksum(&self) -> u32 {
+        self.checksum.value()
+    }
+
+    /// Read the stored checksum from the inner reader and compare it with the checksum of the bytes that were read so far. Returns the inner reader if they are the same.
+    pub fn verify(mut self) -> Result<R, DecodeError> {
+        let mut bytes = [0u8; 4];
+        self.reader.read(&mut bytes[4 - C::SIZE..])?;
+        let stored = u32::from_be_bytes(bytes);
+        let computed = self.checksum.value();
+        if stored != computed {
+            return Err(DecodeError::ChecksumMismatch { stored, computed });
+        }
+        Ok(self.reader)
+    }
+}
+
+impl<R: Reader, C: Checksum> Reader for ChecksumReader<R, C> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.checksum.update(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        self.reader.remaining_bytes()
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>, C: Checksum> BorrowReader<'storage>
+    for ChecksumReader<R, C>
+{
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.checksum.update(bytes);
+        Ok(bytes)
+    }
+}
+
+/// Encode the given value into the given slice, followed by the checksum `C` of the encoded bytes. Returns the amount of bytes written, including the checksum.
+///
+/// See the [module documentation](self) for an example.
+pub fn encode_into_slice<C: Checksum, E: Encode, Cfg: Config>(
+    val: E,
+    dst: &mut [u8],
+    config: Cfg,
+) -> Result<usize, EncodeError> {
+    let mut writer = ChecksumWriter::<_, C>::new(SliceWriter::new(dst));
+    crate::encode_into_writer(val, &mut writer, config)?;
+    Ok(writer.finish()?.bytes_written())
+}
+
+/// Decode a value that was encoded with [encode_into_slice], and verify its checksum. Returns the value and the amount of bytes read, including the checksum.
+pub fn decode_from_slice<'a, C: Checksum, D: BorrowDecode<'a>, Cfg: Config>(
+    src: &'a [u8],
+    config: Cfg,
+) -> Result<(D, usize), DecodeError> {
+    let mut reader = ChecksumReader::<_, C>::new(SliceReader::new(src));
+    let mut decoder = crate::de::DecoderImpl::<_, Cfg>::new(&mut reader, config);
+    let result = D::borrow_decode(&mut decoder)?;
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    decoder.debug_assert_claims_balanced();
+    let bytes_read = src.len() - reader.verify()?.slice.len();
+    Ok((result, bytes_read))
+}
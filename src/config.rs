@@ -20,6 +20,11 @@
 //! See [Configuration] for more information on the configuration options.
 
 pub(crate) use self::internal::*;
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
 use core::marker::PhantomData;
 
 /// The Configuration struct is used to build bincode configurations. The [Config] trait is implemented
@@ -30,7 +35,14 @@ use core::marker::PhantomData;
 /// - [with_little_endian] and [with_big_endian]
 /// - [with_fixed_int_encoding] and [with_variable_int_encoding]
 /// - [skip_fixed_array_length] and [write_fixed_array_length]
-///
+/// - [with_limit] and [with_no_limit]
+/// - [with_max_collection_len] and [with_no_max_collection_len]
+/// - [with_lenient_bool] and [with_strict_bool]
+/// - [with_string_len_in_chars] and [with_string_len_in_bytes]
+/// - [with_strict_map_ordering] and [with_unchecked_map_ordering]
+/// - [with_max_depth] and [with_no_max_depth]
+/// - [with_alignment] and [with_no_alignment]
+/// - [with_no_allocation] and [with_allocation]
 ///
 /// [with_little_endian]: #method.with_little_endian
 /// [with_big_endian]: #method.with_big_endian
@@ -38,12 +50,47 @@ use core::marker::PhantomData;
 /// [with_variable_int_encoding]: #method.with_variable_int_encoding
 /// [skip_fixed_array_length]: #method.skip_fixed_array_length
 /// [write_fixed_array_length]: #method.write_fixed_array_length
+/// [with_limit]: #method.with_limit
+/// [with_no_limit]: #method.with_no_limit
+/// [with_max_collection_len]: #method.with_max_collection_len
+/// [with_no_max_collection_len]: #method.with_no_max_collection_len
+/// [with_lenient_bool]: #method.with_lenient_bool
+/// [with_strict_bool]: #method.with_strict_bool
+/// [with_string_len_in_chars]: #method.with_string_len_in_chars
+/// [with_string_len_in_bytes]: #method.with_string_len_in_bytes
+/// [with_strict_map_ordering]: #method.with_strict_map_ordering
+/// [with_unchecked_map_ordering]: #method.with_unchecked_map_ordering
+/// [with_max_depth]: #method.with_max_depth
+/// [with_no_max_depth]: #method.with_no_max_depth
+/// [with_alignment]: #method.with_alignment
+/// [with_no_alignment]: #method.with_no_alignment
+/// [with_no_allocation]: #method.with_no_allocation
+/// [with_allocation]: #method.with_allocation
 #[derive(Copy, Clone)]
-pub struct Configuration<E = LittleEndian, I = Varint, A = WriteFixedArrayLength, L = NoLimit> {
+pub struct Configuration<
+    E = LittleEndian,
+    I = Varint,
+    A = WriteFixedArrayLength,
+    L = NoLimit,
+    M = NoMaxCollectionLen,
+    B = StrictBool,
+    S = StringLenBytes,
+    O = UncheckedMapOrdering,
+    R = NoMaxDepth,
+    P = NoAlignment,
+    H = AllowAllocation,
+> {
     _e: PhantomData<E>,
     _i: PhantomData<I>,
     _a: PhantomData<A>,
     _l: PhantomData<L>,
+    _m: PhantomData<M>,
+    _b: PhantomData<B>,
+    _s: PhantomData<S>,
+    _o: PhantomData<O>,
+    _r: PhantomData<R>,
+    _p: PhantomData<P>,
+    _h: PhantomData<H>,
 }
 
 /// The default config for bincode 2.0. By default this will be:
@@ -58,16 +105,36 @@ pub const fn standard() -> Configuration {
 /// - Little endian
 /// - Fixed int length encoding
 /// - Write array lengths
-pub const fn legacy() -> Configuration<LittleEndian, Fixint, WriteFixedArrayLength, NoLimit> {
+pub const fn legacy() -> Configuration<
+    LittleEndian,
+    Fixint,
+    WriteFixedArrayLength,
+    NoLimit,
+    NoMaxCollectionLen,
+    StrictBool,
+    StringLenBytes,
+    UncheckedMapOrdering,
+    NoMaxDepth,
+    NoAlignment,
+    AllowAllocation,
+> {
     generate()
 }
 
-const fn generate<_E, _I, _A, _L>() -> Configuration<_E, _I, _A, _L> {
+const fn generate<_E, _I, _A, _L, _M, _B, _S, _O, _R, _P, _H>(
+) -> Configuration<_E, _I, _A, _L, _M, _B, _S, _O, _R, _P, _H> {
     Configuration {
         _e: PhantomData,
         _i: PhantomData,
         _a: PhantomData,
         _l: PhantomData,
+        _m: PhantomData,
+        _b: PhantomData,
+        _s: PhantomData,
+        _o: PhantomData,
+        _r: PhantomData,
+        _p: PhantomData,
+        _h: PhantomData,
     }
 }
 
@@ -80,14 +147,16 @@ const fn generate<_E, _I, _A, _L>() -> Configuration<_E, _I, _A, _L> {
 // - Add this generic to _every_ function in `Configuration`
 // - Add your new methods
 
-impl<E, I, A, L> Configuration<E, I, A, L> {
+impl<E, I, A, L, M, B, S, O, R, P, H> Configuration<E, I, A, L, M, B, S, O, R, P, H> {
     /// Makes bincode encode all integer types in big endian.
-    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, A, L> {
+    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, A, L, M, B, S, O, R, P, H> {
         generate()
     }
 
     /// Makes bincode encode all integer types in little endian.
-    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, A, L> {
+    pub const fn with_little_endian(
+        self,
+    ) -> Configuration<LittleEndian, I, A, L, M, B, S, O, R, P, H> {
         generate()
     }
 
@@ -100,7 +169,7 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     /// 3. If `2**16 <= u < 2**32`, encode it as a literal byte 252, followed by a u32 with value `u`.
     /// 4. If `2**32 <= u < 2**64`, encode it as a literal byte 253, followed by a u64 with value `u`.
     /// 5. If `2**64 <= u < 2**128`, encode it as a literal byte 254, followed by a
-    ///   u128 with value `u`.
+    ///    u128 with value `u`.
     ///
     /// Then, for signed integers, we first convert to unsigned using the zigzag algorithm,
     /// and then encode them as we do for unsigned integers generally. The reason we use this
@@ -148,7 +217,9 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     ///
     /// Note that u256 and the like are unsupported by this format; if and when they are added to the
     /// language, they may be supported via the extension point given by the 255 byte.
-    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, A, L> {
+    pub const fn with_variable_int_encoding(
+        self,
+    ) -> Configuration<E, Varint, A, L, M, B, S, O, R, P, H> {
         generate()
     }
 
@@ -157,42 +228,242 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     /// * Fixed size integers are encoded directly
     /// * Enum discriminants are encoded as u32
     /// * Lengths and usize are encoded as u64
-    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, A, L> {
+    pub const fn with_fixed_int_encoding(
+        self,
+    ) -> Configuration<E, Fixint, A, L, M, B, S, O, R, P, H> {
         generate()
     }
 
     /// Skip writing the length of fixed size arrays (`[u8; N]`) before writing the array
     ///
+    /// This also applies to nested arrays, so a `[[u8; 2]; 3]` is written as 6 bytes without any length prefixes.
+    ///
     /// **NOTE:** This is not supported if you're using the `bincode::serde::*` functions, the `#[bincode(with_serde)]` attribute, or the `Compat` struct.
-    pub const fn skip_fixed_array_length(self) -> Configuration<E, I, SkipFixedArrayLength, L> {
+    pub const fn skip_fixed_array_length(
+        self,
+    ) -> Configuration<E, I, SkipFixedArrayLength, L, M, B, S, O, R, P, H> {
         generate()
     }
 
     /// Write the length of fixed size arrays (`[u8; N]`) before writing the array
-    pub const fn write_fixed_array_length(self) -> Configuration<E, I, WriteFixedArrayLength, L> {
+    pub const fn write_fixed_array_length(
+        self,
+    ) -> Configuration<E, I, WriteFixedArrayLength, L, M, B, S, O, R, P, H> {
         generate()
     }
 
     /// Sets the byte limit to `limit`.
-    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, A, Limit<N>> {
+    pub const fn with_limit<const N: usize>(
+        self,
+    ) -> Configuration<E, I, A, Limit<N>, M, B, S, O, R, P, H> {
         generate()
     }
 
     /// Clear the byte limit.
-    pub const fn with_no_limit(self) -> Configuration<E, I, A, NoLimit> {
+    pub const fn with_no_limit(self) -> Configuration<E, I, A, NoLimit, M, B, S, O, R, P, H> {
+        generate()
+    }
+
+    /// Sets the maximum amount of elements in a single collection to `N`.
+    ///
+    /// Decoding a collection whose length prefix exceeds `N` will return [DecodeError::CollectionTooLong] before anything is allocated.
+    ///
+    /// This also replaces the [default maximum length] of collections of zero-sized types, like `Vec<()>`.
+    ///
+    /// [DecodeError::CollectionTooLong]: crate::error::DecodeError::CollectionTooLong
+    /// [default maximum length]: crate::de::DEFAULT_MAX_ZST_CONTAINER_LEN
+    pub const fn with_max_collection_len<const N: usize>(
+        self,
+    ) -> Configuration<E, I, A, L, MaxCollectionLen<N>, B, S, O, R, P, H> {
+        generate()
+    }
+
+    /// Clear the maximum collection length.
+    pub const fn with_no_max_collection_len(
+        self,
+    ) -> Configuration<E, I, A, L, NoMaxCollectionLen, B, S, O, R, P, H> {
+        generate()
+    }
+
+    /// Decode any non-zero byte as `true` when decoding a `bool`.
+    ///
+    /// This can be used to read data written by other encoders that do not restrict bools to `0` and `1`. Encoding is not affected; bools are always written as `0` or `1`.
+    pub const fn with_lenient_bool(
+        self,
+    ) -> Configuration<E, I, A, L, M, LenientBool, S, O, R, P, H> {
+        generate()
+    }
+
+    /// Only accept `0` and `1` when decoding a `bool`. Any other value will return [DecodeError::InvalidBooleanValue]. This is the default.
+    ///
+    /// [DecodeError::InvalidBooleanValue]: crate::error::DecodeError::InvalidBooleanValue
+    pub const fn with_strict_bool(self) -> Configuration<E, I, A, L, M, StrictBool, S, O, R, P, H> {
+        generate()
+    }
+
+    /// Prefix strings with the amount of `char`s in the string, instead of the amount of bytes.
+    ///
+    /// This can be used to read and write formats that store the character count of a string. The string itself is still encoded as UTF-8. When decoding, the characters are read and validated one at a time.
+    ///
+    /// **NOTE:** Borrowing a `&str` with this option requires a reader that implements `peek_read`, like the slice reader.
+    pub const fn with_string_len_in_chars(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, StringLenChars, O, R, P, H> {
+        generate()
+    }
+
+    /// Prefix strings with the amount of bytes in the string. This is the default.
+    pub const fn with_string_len_in_bytes(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, StringLenBytes, O, R, P, H> {
+        generate()
+    }
+
+    /// Require the keys of a `BTreeMap` to be in strictly increasing order when decoding, like they are written by the encoder.
+    ///
+    /// Decoding a map with keys that are out of order or duplicated will return [DecodeError::InvalidMapOrdering]. This can be used to detect data that was tampered with or not written by bincode.
+    ///
+    /// [DecodeError::InvalidMapOrdering]: crate::error::DecodeError::InvalidMapOrdering
+    pub const fn with_strict_map_ordering(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, StrictMapOrdering, R, P, H> {
+        generate()
+    }
+
+    /// Accept the keys of a `BTreeMap` in any order when decoding. Duplicate keys overwrite the earlier value. This is the default.
+    pub const fn with_unchecked_map_ordering(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, UncheckedMapOrdering, R, P, H> {
+        generate()
+    }
+
+    /// Sets the maximum nesting depth to `N` when decoding.
+    ///
+    /// Every `Box`, `Rc`, `Arc` and collection, e.g. a `Vec` or a `BTreeMap`, that is decoded counts as one level of nesting. Decoding data that nests deeper than `N` levels will return [DecodeError::DepthLimitExceeded] instead of overflowing the stack, e.g. for a linked list like `Option<Box<Node>>`.
+    ///
+    /// [DecodeError::DepthLimitExceeded]: crate::error::DecodeError::DepthLimitExceeded
+    pub const fn with_max_depth<const N: usize>(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, MaxDepth<N>, P, H> {
+        generate()
+    }
+
+    /// Clear the maximum nesting depth. This is the default.
+    pub const fn with_no_max_depth(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, NoMaxDepth, P, H> {
+        generate()
+    }
+
+    /// Pads the output of the top-level encode functions, like [encode_to_vec], to a multiple of `N` bytes.
+    ///
+    /// The encoded value is followed by a single byte with the amount of padding, and then that many zero bytes. The top-level decode functions, like [decode_from_slice], read and check the padding after the value. The padding is not written or read when calling `Encode::encode` and `Decode::decode` directly.
+    ///
+    /// `N` must be between 1 and 256, otherwise this function fails to compile.
+    ///
+    /// [encode_to_vec]: crate::encode_to_vec
+    /// [decode_from_slice]: crate::decode_from_slice
+    pub const fn with_alignment<const N: usize>(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, R, Alignment<N>, H> {
+        const {
+            assert!(
+                N >= 1 && N <= 256,
+                "The alignment must be between 1 and 256"
+            )
+        };
+        generate()
+    }
+
+    /// Do not pad the encoded output. This is the default.
+    pub const fn with_no_alignment(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, R, NoAlignment, H> {
+        generate()
+    }
+
+    /// Return [DecodeError::AllocationDisallowed] when decoding any type that allocates on the heap, like a `Vec`, `String` or `Box`.
+    ///
+    /// The error is returned as soon as such a type is decoded, even if it would be empty. This can be used to make sure that the types decoded by a `no_std` decoder never call the allocator. The exception is [Decode::decode_in_place] for a `Vec`, which only fails if the existing `Vec` does not have the capacity for the decoded items.
+    ///
+    /// [Decode::decode_in_place]: crate::Decode::decode_in_place
+    ///
+    /// [DecodeError::AllocationDisallowed]: crate::error::DecodeError::AllocationDisallowed
+    pub const fn with_no_allocation(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, R, P, NoAllocation> {
+        generate()
+    }
+
+    /// Allow decoding types that allocate on the heap. This is the default.
+    pub const fn with_allocation(
+        self,
+    ) -> Configuration<E, I, A, L, M, B, S, O, R, P, AllowAllocation> {
         generate()
     }
 }
 
 /// Indicates a type is valid for controlling the bincode configuration
+///
+/// A reference to a config is a config as well, so a config that is only available by reference can be passed directly to functions like [decode_from_slice] and [encode_to_vec].
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// struct Settings {
+///     name: String,
+///     config: bincode::config::Configuration,
+/// }
+///
+/// let settings = Settings {
+///     name: String::from("default"),
+///     config: bincode::config::standard(),
+/// };
+///
+/// let bytes = bincode::encode_to_vec(&settings.name, &settings.config).unwrap();
+/// let (name, _): (String, usize) = bincode::decode_from_slice(&bytes, &settings.config).unwrap();
+/// assert_eq!(name, settings.name);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// [decode_from_slice]: crate::decode_from_slice
+/// [encode_to_vec]: crate::encode_to_vec
 pub trait Config:
     InternalEndianConfig
     + InternalArrayLengthConfig
     + InternalIntEncodingConfig
     + InternalLimitConfig
+    + InternalMaxCollectionLenConfig
+    + InternalBoolConfig
+    + InternalStringLenConfig
+    + InternalMapOrderingConfig
+    + InternalMaxDepthConfig
+    + InternalAlignmentConfig
+    + InternalAllocationConfig
+    + InternalOwnedConfig
     + Copy
     + Clone
 {
+    /// Returns a [ConfigDescriptor] that describes the options of this configuration.
+    ///
+    /// The descriptor can be stored alongside encoded data, so a reader can later pick a matching configuration.
+    fn descriptor(&self) -> ConfigDescriptor {
+        ConfigDescriptor {
+            big_endian: Self::ENDIAN == Endian::Big,
+            fixed_int_encoding: Self::INT_ENCODING == IntEncoding::Fixed,
+            skip_fixed_array_length: Self::SKIP_FIXED_ARRAY_LENGTH,
+            limit: Self::LIMIT,
+            max_collection_len: Self::MAX_COLLECTION_LEN,
+            lenient_bool: Self::LENIENT_BOOL,
+            string_len_in_chars: Self::STRING_LEN_IN_CHARS,
+            strict_map_ordering: Self::STRICT_MAP_ORDERING,
+            max_depth: Self::MAX_DEPTH,
+            alignment: Self::ALIGNMENT,
+            no_allocation: !Self::ALLOW_ALLOCATION,
+        }
+    }
 }
 
 impl<T> Config for T where
@@ -200,11 +471,107 @@ impl<T> Config for T where
         + InternalArrayLengthConfig
         + InternalIntEncodingConfig
         + InternalLimitConfig
+        + InternalMaxCollectionLenConfig
+        + InternalBoolConfig
+        + InternalStringLenConfig
+        + InternalMapOrderingConfig
+        + InternalMaxDepthConfig
+        + InternalAlignmentConfig
+        + InternalAllocationConfig
+        + InternalOwnedConfig
         + Copy
         + Clone
 {
 }
 
+/// A portable description of the options of a [Config], as returned by [Config::descriptor].
+///
+/// Configurations are types, so they can not be stored or picked at runtime. A `ConfigDescriptor` implements [Encode] and [Decode], so it can be stored alongside encoded data. A reader can then decode the descriptor and select the configuration that matches it.
+///
+/// The descriptor is encoded as its fields in declaration order. Use the same configuration (e.g. [standard]) to encode and decode the descriptor itself.
+///
+/// ```
+/// use bincode::config::{Config, ConfigDescriptor};
+///
+/// let config = bincode::config::legacy().with_big_endian();
+/// let mut header = [0u8; 32];
+/// bincode::encode_into_slice(config.descriptor(), &mut header, bincode::config::standard()).unwrap();
+/// let mut data = [0u8; 4];
+/// bincode::encode_into_slice(5u32, &mut data, config).unwrap();
+///
+/// let (descriptor, _): (ConfigDescriptor, usize) =
+///     bincode::decode_from_slice(&header, bincode::config::standard()).unwrap();
+/// let value: u32 = if descriptor == bincode::config::legacy().with_big_endian().descriptor() {
+///     bincode::decode_from_slice(&data, bincode::config::legacy().with_big_endian()).unwrap().0
+/// } else {
+///     bincode::decode_from_slice(&data, bincode::config::standard()).unwrap().0
+/// };
+/// assert_eq!(value, 5);
+/// ```
+///
+/// [Encode]: crate::Encode
+/// [Decode]: crate::Decode
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConfigDescriptor {
+    /// `true` for [Configuration::with_big_endian], `false` for [Configuration::with_little_endian]
+    pub big_endian: bool,
+    /// `true` for [Configuration::with_fixed_int_encoding], `false` for [Configuration::with_variable_int_encoding]
+    pub fixed_int_encoding: bool,
+    /// `true` for [Configuration::skip_fixed_array_length], `false` for [Configuration::write_fixed_array_length]
+    pub skip_fixed_array_length: bool,
+    /// The limit set with [Configuration::with_limit], if any
+    pub limit: Option<usize>,
+    /// The maximum collection length set with [Configuration::with_max_collection_len], if any
+    pub max_collection_len: Option<usize>,
+    /// `true` for [Configuration::with_lenient_bool], `false` for [Configuration::with_strict_bool]
+    pub lenient_bool: bool,
+    /// `true` for [Configuration::with_string_len_in_chars], `false` for [Configuration::with_string_len_in_bytes]
+    pub string_len_in_chars: bool,
+    /// `true` for [Configuration::with_strict_map_ordering], `false` for [Configuration::with_unchecked_map_ordering]
+    pub strict_map_ordering: bool,
+    /// The maximum nesting depth set with [Configuration::with_max_depth], if any
+    pub max_depth: Option<usize>,
+    /// The alignment set with [Configuration::with_alignment], if any
+    pub alignment: Option<usize>,
+    /// `true` for [Configuration::with_no_allocation], `false` for [Configuration::with_allocation]
+    pub no_allocation: bool,
+}
+
+impl Encode for ConfigDescriptor {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.big_endian.encode(encoder)?;
+        self.fixed_int_encoding.encode(encoder)?;
+        self.skip_fixed_array_length.encode(encoder)?;
+        self.limit.encode(encoder)?;
+        self.max_collection_len.encode(encoder)?;
+        self.lenient_bool.encode(encoder)?;
+        self.string_len_in_chars.encode(encoder)?;
+        self.strict_map_ordering.encode(encoder)?;
+        self.max_depth.encode(encoder)?;
+        self.alignment.encode(encoder)?;
+        self.no_allocation.encode(encoder)
+    }
+}
+
+impl Decode for ConfigDescriptor {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            big_endian: Decode::decode(decoder)?,
+            fixed_int_encoding: Decode::decode(decoder)?,
+            skip_fixed_array_length: Decode::decode(decoder)?,
+            limit: Decode::decode(decoder)?,
+            max_collection_len: Decode::decode(decoder)?,
+            lenient_bool: Decode::decode(decoder)?,
+            string_len_in_chars: Decode::decode(decoder)?,
+            strict_map_ordering: Decode::decode(decoder)?,
+            max_depth: Decode::decode(decoder)?,
+            alignment: Decode::decode(decoder)?,
+            no_allocation: Decode::decode(decoder)?,
+        })
+    }
+}
+
 #[doc(hidden)]
 #[derive(Copy, Clone)]
 pub struct BigEndian {}
@@ -267,6 +634,104 @@ impl<const N: usize> InternalLimitConfig for Limit<N> {
     const LIMIT: Option<usize> = Some(N);
 }
 
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoMaxCollectionLen {}
+impl InternalMaxCollectionLenConfig for NoMaxCollectionLen {
+    const MAX_COLLECTION_LEN: Option<usize> = None;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct MaxCollectionLen<const N: usize> {}
+impl<const N: usize> InternalMaxCollectionLenConfig for MaxCollectionLen<N> {
+    const MAX_COLLECTION_LEN: Option<usize> = Some(N);
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoMaxDepth {}
+impl InternalMaxDepthConfig for NoMaxDepth {
+    const MAX_DEPTH: Option<usize> = None;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct MaxDepth<const N: usize> {}
+impl<const N: usize> InternalMaxDepthConfig for MaxDepth<N> {
+    const MAX_DEPTH: Option<usize> = Some(N);
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoAlignment {}
+impl InternalAlignmentConfig for NoAlignment {
+    const ALIGNMENT: Option<usize> = None;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct Alignment<const N: usize> {}
+impl<const N: usize> InternalAlignmentConfig for Alignment<N> {
+    const ALIGNMENT: Option<usize> = Some(N);
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct AllowAllocation {}
+impl InternalAllocationConfig for AllowAllocation {
+    const ALLOW_ALLOCATION: bool = true;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoAllocation {}
+impl InternalAllocationConfig for NoAllocation {
+    const ALLOW_ALLOCATION: bool = false;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct StrictBool {}
+impl InternalBoolConfig for StrictBool {
+    const LENIENT_BOOL: bool = false;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct LenientBool {}
+impl InternalBoolConfig for LenientBool {
+    const LENIENT_BOOL: bool = true;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct StringLenBytes {}
+impl InternalStringLenConfig for StringLenBytes {
+    const STRING_LEN_IN_CHARS: bool = false;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct StringLenChars {}
+impl InternalStringLenConfig for StringLenChars {
+    const STRING_LEN_IN_CHARS: bool = true;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct UncheckedMapOrdering {}
+impl InternalMapOrderingConfig for UncheckedMapOrdering {
+    const STRICT_MAP_ORDERING: bool = false;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct StrictMapOrdering {}
+impl InternalMapOrderingConfig for StrictMapOrdering {
+    const STRICT_MAP_ORDERING: bool = true;
+}
+
 mod internal {
     use super::Configuration;
 
@@ -274,10 +739,16 @@ mod internal {
         const ENDIAN: Endian;
     }
 
-    impl<E: InternalEndianConfig, I, A, L> InternalEndianConfig for Configuration<E, I, A, L> {
+    impl<E: InternalEndianConfig, I, A, L, M, B, S, O, R, P, H> InternalEndianConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
         const ENDIAN: Endian = E::ENDIAN;
     }
 
+    impl<T: InternalEndianConfig> InternalEndianConfig for &T {
+        const ENDIAN: Endian = T::ENDIAN;
+    }
+
     #[derive(PartialEq, Eq)]
     pub enum Endian {
         Little,
@@ -288,12 +759,16 @@ mod internal {
         const INT_ENCODING: IntEncoding;
     }
 
-    impl<E, I: InternalIntEncodingConfig, A, L> InternalIntEncodingConfig
-        for Configuration<E, I, A, L>
+    impl<E, I: InternalIntEncodingConfig, A, L, M, B, S, O, R, P, H> InternalIntEncodingConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
     {
         const INT_ENCODING: IntEncoding = I::INT_ENCODING;
     }
 
+    impl<T: InternalIntEncodingConfig> InternalIntEncodingConfig for &T {
+        const INT_ENCODING: IntEncoding = T::INT_ENCODING;
+    }
+
     #[derive(PartialEq, Eq)]
     pub enum IntEncoding {
         Fixed,
@@ -304,17 +779,142 @@ mod internal {
         const SKIP_FIXED_ARRAY_LENGTH: bool;
     }
 
-    impl<E, I, A: InternalArrayLengthConfig, L> InternalArrayLengthConfig
-        for Configuration<E, I, A, L>
+    impl<E, I, A: InternalArrayLengthConfig, L, M, B, S, O, R, P, H> InternalArrayLengthConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
     {
         const SKIP_FIXED_ARRAY_LENGTH: bool = A::SKIP_FIXED_ARRAY_LENGTH;
     }
 
+    impl<T: InternalArrayLengthConfig> InternalArrayLengthConfig for &T {
+        const SKIP_FIXED_ARRAY_LENGTH: bool = T::SKIP_FIXED_ARRAY_LENGTH;
+    }
+
     pub trait InternalLimitConfig {
         const LIMIT: Option<usize>;
     }
 
-    impl<E, I, A, L: InternalLimitConfig> InternalLimitConfig for Configuration<E, I, A, L> {
+    impl<E, I, A, L: InternalLimitConfig, M, B, S, O, R, P, H> InternalLimitConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
         const LIMIT: Option<usize> = L::LIMIT;
     }
+
+    impl<T: InternalLimitConfig> InternalLimitConfig for &T {
+        const LIMIT: Option<usize> = T::LIMIT;
+    }
+
+    pub trait InternalMaxCollectionLenConfig {
+        const MAX_COLLECTION_LEN: Option<usize>;
+    }
+
+    impl<E, I, A, L, M: InternalMaxCollectionLenConfig, B, S, O, R, P, H>
+        InternalMaxCollectionLenConfig for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const MAX_COLLECTION_LEN: Option<usize> = M::MAX_COLLECTION_LEN;
+    }
+
+    impl<T: InternalMaxCollectionLenConfig> InternalMaxCollectionLenConfig for &T {
+        const MAX_COLLECTION_LEN: Option<usize> = T::MAX_COLLECTION_LEN;
+    }
+
+    pub trait InternalBoolConfig {
+        const LENIENT_BOOL: bool;
+    }
+
+    impl<E, I, A, L, M, B: InternalBoolConfig, S, O, R, P, H> InternalBoolConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const LENIENT_BOOL: bool = B::LENIENT_BOOL;
+    }
+
+    impl<T: InternalBoolConfig> InternalBoolConfig for &T {
+        const LENIENT_BOOL: bool = T::LENIENT_BOOL;
+    }
+
+    pub trait InternalStringLenConfig {
+        const STRING_LEN_IN_CHARS: bool;
+    }
+
+    impl<E, I, A, L, M, B, S: InternalStringLenConfig, O, R, P, H> InternalStringLenConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const STRING_LEN_IN_CHARS: bool = S::STRING_LEN_IN_CHARS;
+    }
+
+    impl<T: InternalStringLenConfig> InternalStringLenConfig for &T {
+        const STRING_LEN_IN_CHARS: bool = T::STRING_LEN_IN_CHARS;
+    }
+
+    pub trait InternalMapOrderingConfig {
+        const STRICT_MAP_ORDERING: bool;
+    }
+
+    impl<E, I, A, L, M, B, S, O: InternalMapOrderingConfig, R, P, H> InternalMapOrderingConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const STRICT_MAP_ORDERING: bool = O::STRICT_MAP_ORDERING;
+    }
+
+    impl<T: InternalMapOrderingConfig> InternalMapOrderingConfig for &T {
+        const STRICT_MAP_ORDERING: bool = T::STRICT_MAP_ORDERING;
+    }
+
+    pub trait InternalMaxDepthConfig {
+        const MAX_DEPTH: Option<usize>;
+    }
+
+    impl<E, I, A, L, M, B, S, O, R: InternalMaxDepthConfig, P, H> InternalMaxDepthConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const MAX_DEPTH: Option<usize> = R::MAX_DEPTH;
+    }
+
+    impl<T: InternalMaxDepthConfig> InternalMaxDepthConfig for &T {
+        const MAX_DEPTH: Option<usize> = T::MAX_DEPTH;
+    }
+
+    pub trait InternalAlignmentConfig {
+        const ALIGNMENT: Option<usize>;
+    }
+
+    impl<E, I, A, L, M, B, S, O, R, P: InternalAlignmentConfig, H> InternalAlignmentConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const ALIGNMENT: Option<usize> = P::ALIGNMENT;
+    }
+
+    impl<T: InternalAlignmentConfig> InternalAlignmentConfig for &T {
+        const ALIGNMENT: Option<usize> = T::ALIGNMENT;
+    }
+
+    pub trait InternalAllocationConfig {
+        const ALLOW_ALLOCATION: bool;
+    }
+
+    impl<E, I, A, L, M, B, S, O, R, P, H: InternalAllocationConfig> InternalAllocationConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    {
+        const ALLOW_ALLOCATION: bool = H::ALLOW_ALLOCATION;
+    }
+
+    impl<T: InternalAllocationConfig> InternalAllocationConfig for &T {
+        const ALLOW_ALLOCATION: bool = T::ALLOW_ALLOCATION;
+    }
+
+    pub trait InternalOwnedConfig {
+        /// The same config without any references, so it can be used where a `'static` type is needed, e.g. with `Any`
+        type Owned: Copy + 'static;
+    }
+
+    impl<E, I, A, L, M, B, S, O, R, P, H> InternalOwnedConfig
+        for Configuration<E, I, A, L, M, B, S, O, R, P, H>
+    where
+        Self: Copy + 'static,
+    {
+        type Owned = Self;
+    }
+
+    impl<T: InternalOwnedConfig> InternalOwnedConfig for &T {
+        type Owned = T::Owned;
+    }
 }
@@ -1,8 +1,9 @@
 use super::{
-    read::{BorrowReader, Reader},
-    BorrowDecoder, Decoder,
+    read::{BorrowReader, LimitReader, Reader},
+    BorrowDecoder, Decoder, DEFAULT_MAX_ZST_CONTAINER_LEN,
 };
 use crate::{config::Config, error::DecodeError, utils::Sealed};
+use core::any::Any;
 
 /// A Decoder that reads bytes from a given reader `R`.
 ///
@@ -20,35 +21,97 @@ use crate::{config::Config, error::DecodeError, utils::Sealed};
 /// // this u32 can be any Decode
 /// let value = u32::decode(&mut decoder).unwrap();
 /// ```
-pub struct DecoderImpl<R, C: Config> {
-    reader: R,
+pub struct DecoderImpl<R, C: Config, Ctx = ()> {
+    /// The limit of the reader is the end of the length prefixed value that is being decoded, if any
+    reader: LimitReader<R>,
     config: C,
     bytes_read: usize,
+    /// The bytes claimed by `claim_container_read` that have not been un-claimed yet
+    #[cfg(debug_assertions)]
+    container_bytes_claimed: usize,
+    /// The amount of nested values that are currently being decoded
+    depth: usize,
+    context: Ctx,
 }
 
 impl<R: Reader, C: Config> DecoderImpl<R, C> {
     /// Construct a new Decoder
     pub fn new(reader: R, config: C) -> DecoderImpl<R, C> {
+        Self::with_context(reader, config, ())
+    }
+}
+
+impl<R: Reader, C: Config, Ctx: Any> DecoderImpl<R, C, Ctx> {
+    /// Construct a new Decoder with the given context. `Decode` implementations can access the context through [Decoder::context].
+    ///
+    /// ```
+    /// # use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    /// # use bincode::error::DecodeError;
+    /// struct Interned(&'static str);
+    ///
+    /// impl Decode for Interned {
+    ///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         let index = usize::decode(decoder)?;
+    ///         let table = decoder
+    ///             .context()
+    ///             .and_then(|ctx| ctx.downcast_ref::<Vec<&'static str>>())
+    ///             .ok_or(DecodeError::Other("Missing string table"))?;
+    ///         Ok(Interned(table[index]))
+    ///     }
+    /// }
+    ///
+    /// let table = vec!["foo", "bar"];
+    /// let mut decoder = DecoderImpl::with_context(
+    ///     SliceReader::new(&[1]),
+    ///     bincode::config::standard(),
+    ///     table,
+    /// );
+    /// let value = Interned::decode(&mut decoder).unwrap();
+    /// assert_eq!(value.0, "bar");
+    /// ```
+    pub fn with_context(reader: R, config: C, context: Ctx) -> DecoderImpl<R, C, Ctx> {
         DecoderImpl {
-            reader,
+            reader: LimitReader::new(reader, usize::MAX),
             config,
             bytes_read: 0,
+            #[cfg(debug_assertions)]
+            container_bytes_claimed: 0,
+            depth: 0,
+            context,
         }
     }
+
+    /// Return the context of this decoder
+    pub fn into_context(self) -> Ctx {
+        self.context
+    }
+}
+
+impl<R, C: Config, Ctx> DecoderImpl<R, C, Ctx> {
+    /// Panics if a `Decode` implementation claimed a container with [Decoder::claim_container_read] without un-claiming all of its items. This is only checked when `debug_assertions` are enabled.
+    #[inline]
+    pub(crate) fn debug_assert_claims_balanced(&self) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.container_bytes_claimed == 0,
+            "{} bytes were claimed with `claim_container_read` but never un-claimed with `unclaim_bytes_read`",
+            self.container_bytes_claimed
+        );
+    }
 }
 
-impl<R, C: Config> Sealed for DecoderImpl<R, C> {}
+impl<R, C: Config, Ctx> Sealed for DecoderImpl<R, C, Ctx> {}
 
-impl<'de, R: BorrowReader<'de>, C: Config> BorrowDecoder<'de> for DecoderImpl<R, C> {
-    type BR = R;
+impl<'de, R: BorrowReader<'de>, C: Config, Ctx: Any> BorrowDecoder<'de> for DecoderImpl<R, C, Ctx> {
+    type BR = LimitReader<R>;
 
     fn borrow_reader(&mut self) -> &mut Self::BR {
         &mut self.reader
     }
 }
 
-impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
-    type R = R;
+impl<R: Reader, C: Config, Ctx: Any> Decoder for DecoderImpl<R, C, Ctx> {
+    type R = LimitReader<R>;
 
     type C = C;
 
@@ -60,6 +123,10 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
         &self.config
     }
 
+    fn context(&mut self) -> Option<&mut dyn Any> {
+        Some(&mut self.context)
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
         // C::LIMIT is a const so this check should get compiled away
@@ -79,12 +146,78 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
         }
     }
 
+    fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
+        let size = core::mem::size_of::<T>();
+        if size == 0 {
+            // If a max collection length is configured, `decode_slice_len` has already checked `len`
+            if C::MAX_COLLECTION_LEN.is_none() && len > DEFAULT_MAX_ZST_CONTAINER_LEN {
+                return Err(DecodeError::CollectionTooLong {
+                    len,
+                    max: DEFAULT_MAX_ZST_CONTAINER_LEN,
+                });
+            }
+        } else if C::LIMIT.is_some() {
+            match len.checked_mul(size) {
+                Some(val) => self.claim_bytes_read(val)?,
+                None => return Err(DecodeError::LimitExceeded),
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            self.container_bytes_claimed = self
+                .container_bytes_claimed
+                .saturating_add(len.saturating_mul(size));
+        }
+        Ok(())
+    }
+
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
+        #[cfg(debug_assertions)]
+        {
+            self.container_bytes_claimed = self.container_bytes_claimed.saturating_sub(n);
+        }
         // C::LIMIT is a const so this check should get compiled away
         if C::LIMIT.is_some() {
             // We should always be claiming more than we unclaim, so this should never underflow
             self.bytes_read -= n;
         }
     }
+
+    #[inline]
+    fn enter_nested(&mut self) -> Result<(), DecodeError> {
+        // C::MAX_DEPTH is a const so this check should get compiled away
+        if let Some(max) = C::MAX_DEPTH {
+            if self.depth >= max {
+                return Err(DecodeError::DepthLimitExceeded { max });
+            }
+            self.depth += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn exit_nested(&mut self) {
+        if C::MAX_DEPTH.is_some() {
+            self.depth -= 1;
+        }
+    }
+
+    fn enter_bounded(&mut self, len: usize) -> Result<usize, DecodeError> {
+        let remaining = self.reader.remaining;
+        if len > remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: len - remaining,
+            });
+        }
+        self.reader.remaining = len;
+        Ok(remaining - len)
+    }
+
+    fn exit_bounded(&mut self, outside: usize) -> usize {
+        let unread = self.reader.remaining;
+        self.reader.remaining = outside + unread;
+        unread
+    }
 }
@@ -1,22 +1,20 @@
-use super::{
-    read::{BorrowReader, Reader},
-    BorrowDecode, BorrowDecoder, Decode, Decoder,
-};
+use super::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder};
 use crate::{
     config::{
-        Endian, IntEncoding, InternalArrayLengthConfig, InternalEndianConfig,
-        InternalIntEncodingConfig,
+        Endian, IntEncoding, InternalArrayLengthConfig, InternalBoolConfig, InternalEndianConfig,
+        InternalIntEncodingConfig, InternalStringLenConfig,
     },
     error::{DecodeError, IntegerType},
 };
 use core::{
-    any::TypeId,
-    cell::{Cell, RefCell},
+    cell::{Cell, OnceCell, RefCell},
+    cmp::Reverse,
     num::{
-        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
-        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+        FpCategory, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+        NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     },
     ops::{Bound, Range, RangeInclusive},
+    sync::atomic,
     time::Duration,
 };
 
@@ -25,6 +23,7 @@ impl Decode for bool {
         match u8::decode(decoder)? {
             0 => Ok(false),
             1 => Ok(true),
+            _ if D::C::LENIENT_BOOL => Ok(true),
             x => Err(DecodeError::InvalidBooleanValue(x)),
         }
     }
@@ -178,9 +177,7 @@ impl Decode for usize {
                     Endian::Big => u64::from_be_bytes(bytes),
                 };
 
-                value
-                    .try_into()
-                    .map_err(|_| DecodeError::OutsideUsizeRange(value))
+                super::usize_from_u64(value)
             }
         }
     }
@@ -402,8 +399,7 @@ impl Decode for char {
 impl<'a, 'de: 'a> BorrowDecode<'de> for &'a [u8] {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let len = super::decode_slice_len(decoder)?;
-        decoder.claim_bytes_read(len)?;
-        decoder.borrow_reader().take_bytes(len)
+        decoder.borrow_slice(len)
     }
 }
 
@@ -421,11 +417,34 @@ impl<'a, 'de: 'a> BorrowDecode<'de> for Option<&'a [u8]> {
 
 impl<'a, 'de: 'a> BorrowDecode<'de> for &'a str {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let slice = <&[u8]>::borrow_decode(decoder)?;
+        let slice = if D::C::STRING_LEN_IN_CHARS {
+            let len = super::decode_slice_len(decoder)?;
+            let byte_len = str_byte_len(decoder.borrow_reader(), len)?;
+            decoder.borrow_slice(byte_len)?
+        } else {
+            <&[u8]>::borrow_decode(decoder)?
+        };
         core::str::from_utf8(slice).map_err(DecodeError::Utf8)
     }
 }
 
+/// Find the amount of bytes that the next `char_len` chars take up, by peeking at the first byte of every char.
+fn str_byte_len<R: Reader>(reader: &R, char_len: usize) -> Result<usize, DecodeError> {
+    let mut byte_len = 0;
+    for _ in 0..char_len {
+        let first = match reader.peek_read(byte_len + 1) {
+            Some(bytes) => bytes[byte_len],
+            None => return Err(DecodeError::UnexpectedEnd { additional: 1 }),
+        };
+        let width = utf8_char_width(first);
+        if width == 0 {
+            return Err(DecodeError::InvalidCharEncoding([first, 0, 0, 0]));
+        }
+        byte_len += width;
+    }
+    Ok(byte_len)
+}
+
 impl<'a, 'de: 'a> BorrowDecode<'de> for Option<&'a str> {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         match super::decode_option_variant(decoder, core::any::type_name::<Option<&str>>())? {
@@ -453,19 +472,36 @@ where
             }
         }
 
-        decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
-
-        // Optimize for `[u8; N]`
-        if TypeId::of::<u8>() == TypeId::of::<T>() {
-            let mut buf = [0u8; N];
-            decoder.reader().read(&mut buf)?;
-            let ptr = &mut buf as *mut _ as *mut [T; N];
-
-            // Safety: we know that T is a u8, so it is perfectly safe to
-            // translate an array of u8 into an array of T
-            let res = unsafe { ptr.read() };
-            Ok(res)
+        // Optimize for arrays of integers that are encoded as their raw bytes, e.g. `[u8; N]`
+        if let Some(size) = crate::utils::raw_int_size::<T, D::C>() {
+            decoder.claim_bytes_read(core::mem::size_of::<[T; N]>())?;
+            // Safety: we know that T is an integer type, for which all bit patterns are valid
+            let mut array: [T; N] = unsafe { core::mem::zeroed() };
+            // Safety: the slice covers exactly the memory of `array`, which is initialized above
+            let bytes = unsafe {
+                core::slice::from_raw_parts_mut(
+                    &mut array as *mut [T; N] as *mut u8,
+                    core::mem::size_of::<[T; N]>(),
+                )
+            };
+            decoder.reader().read(bytes)?;
+
+            let native_endian = if cfg!(target_endian = "little") {
+                Endian::Little
+            } else {
+                Endian::Big
+            };
+            if size > 1 && D::C::ENDIAN != native_endian {
+                for int in bytes.chunks_exact_mut(size) {
+                    int.reverse();
+                }
+            }
+            Ok(array)
         } else {
+            // The length of an array is known at compile time, so arrays of zero-sized types don't need to be bounded
+            if core::mem::size_of::<T>() != 0 {
+                decoder.claim_container_read::<T>(N)?;
+            }
             let result = super::impl_core::collect_into_array(&mut (0..N).map(|_| {
                 // See the documentation on `unclaim_bytes_read` as to why we're doing this here
                 decoder.unclaim_bytes_read(core::mem::size_of::<T>());
@@ -479,6 +515,7 @@ where
     }
 }
 
+/// `()` is encoded as zero bytes, so decoding it does not read anything.
 impl Decode for () {
     fn decode<D: Decoder>(_: &mut D) -> Result<Self, DecodeError> {
         Ok(())
@@ -523,7 +560,7 @@ where
                 Ok(Err(u))
             }
             x => Err(DecodeError::UnexpectedVariant {
-                found: x as u32,
+                found: x.into(),
                 allowed: crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
                 type_name: core::any::type_name::<Result<T, U>>(),
             }),
@@ -551,6 +588,28 @@ where
     }
 }
 
+impl<T> Decode for OnceCell<T>
+where
+    T: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match Option::<T>::decode(decoder)? {
+            Some(t) => Ok(OnceCell::from(t)),
+            None => Ok(OnceCell::new()),
+        }
+    }
+}
+
+impl<T> Decode for Reverse<T>
+where
+    T: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let t = T::decode(decoder)?;
+        Ok(Reverse(t))
+    }
+}
+
 impl Decode for Duration {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         const NANOS_PER_SEC: u64 = 1_000_000_000;
@@ -596,13 +655,47 @@ where
             2 => Ok(Bound::Excluded(T::decode(decoder)?)),
             x => Err(DecodeError::UnexpectedVariant {
                 allowed: crate::error::AllowedEnumVariants::Range { max: 2, min: 0 },
-                found: x,
+                found: x.into(),
                 type_name: core::any::type_name::<Bound<T>>(),
             }),
         }
     }
 }
 
+impl Decode for FpCategory {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(FpCategory::Nan),
+            1 => Ok(FpCategory::Infinite),
+            2 => Ok(FpCategory::Zero),
+            3 => Ok(FpCategory::Subnormal),
+            4 => Ok(FpCategory::Normal),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: crate::error::AllowedEnumVariants::Range { max: 4, min: 0 },
+                found: x.into(),
+                type_name: core::any::type_name::<FpCategory>(),
+            }),
+        }
+    }
+}
+
+impl Decode for atomic::Ordering {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(atomic::Ordering::Relaxed),
+            1 => Ok(atomic::Ordering::Release),
+            2 => Ok(atomic::Ordering::Acquire),
+            3 => Ok(atomic::Ordering::AcqRel),
+            4 => Ok(atomic::Ordering::SeqCst),
+            x => Err(DecodeError::UnexpectedVariant {
+                allowed: crate::error::AllowedEnumVariants::Range { max: 4, min: 0 },
+                found: x.into(),
+                type_name: core::any::type_name::<atomic::Ordering>(),
+            }),
+        }
+    }
+}
+
 const UTF8_CHAR_WIDTH: [u8; 256] = [
     1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
     1, // 0x1F
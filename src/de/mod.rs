@@ -7,15 +7,19 @@ mod impls;
 
 use self::read::{BorrowReader, Reader};
 use crate::{
-    config::{Config, InternalLimitConfig},
+    config::{Config, InternalAlignmentConfig, InternalMaxCollectionLenConfig},
     error::DecodeError,
     utils::Sealed,
 };
+use core::ops::Bound;
 
 pub mod read;
 
 pub use self::decoder::DecoderImpl;
 
+/// The maximum amount of zero-sized items in a container when no max collection length is configured. See [Decoder::claim_container_read] for more information.
+pub const DEFAULT_MAX_ZST_CONTAINER_LEN: usize = 1 << 20;
+
 /// Trait that makes a type able to be decoded, akin to serde's `DeserializeOwned` trait.
 ///
 /// This trait should be implemented for types which do not have references to data in the reader. For types that contain e.g. `&str` and `&[u8]`, implement [BorrowDecode] instead.
@@ -74,6 +78,14 @@ pub use self::decoder::DecoderImpl;
 pub trait Decode: for<'de> BorrowDecode<'de> {
     /// Attempt to decode this type with the given [Decode].
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError>;
+
+    /// Decode into an existing value, akin to serde's `deserialize_in_place`. This allows reusing the resources of `place`, e.g. a `Vec<T>` is decoded into its existing allocation.
+    ///
+    /// The default implementation replaces `place` with the result of [Decode::decode]. If decoding fails, `place` is left in a valid but unspecified state.
+    fn decode_in_place<D: Decoder>(decoder: &mut D, place: &mut Self) -> Result<(), DecodeError> {
+        *place = Self::decode(decoder)?;
+        Ok(())
+    }
 }
 
 /// Trait that makes a type able to be decoded, akin to serde's `Deserialize` trait.
@@ -92,6 +104,50 @@ impl<'de, T: Decode> BorrowDecode<'de> for T {
     }
 }
 
+/// A stateful version of [Decode], akin to serde's `DeserializeSeed` trait.
+///
+/// The seed is consumed to decode a `Self::Value`, so it can carry runtime information that is not stored in the encoded data, e.g. a schema, a lookup table or the amount of items that follow. Use [decode_seed_from_slice] to decode a seed from a slice, or call [DecodeSeed::decode] from within another `Decode` implementation.
+///
+/// ```
+/// use bincode::de::{DecodeSeed, Decoder};
+/// use bincode::error::DecodeError;
+/// use bincode::Decode;
+///
+/// /// Decodes a `u32` that was stored in units of `self.0`
+/// struct Scale(f64);
+///
+/// impl DecodeSeed for Scale {
+///     type Value = f64;
+///
+///     fn decode<D: Decoder>(self, decoder: &mut D) -> Result<f64, DecodeError> {
+///         Ok(u32::decode(decoder)? as f64 * self.0)
+///     }
+/// }
+///
+/// let (value, _) =
+///     bincode::decode_seed_from_slice(Scale(0.5), &[5], bincode::config::standard()).unwrap();
+/// assert_eq!(value, 2.5);
+/// ```
+///
+/// Every [Decode] type can be decoded with a `PhantomData<T>` seed.
+///
+/// [decode_seed_from_slice]: crate::decode_seed_from_slice
+pub trait DecodeSeed: Sized {
+    /// The type that is decoded
+    type Value;
+
+    /// Attempt to decode a `Self::Value` with the given [Decoder].
+    fn decode<D: Decoder>(self, decoder: &mut D) -> Result<Self::Value, DecodeError>;
+}
+
+impl<T: Decode> DecodeSeed for core::marker::PhantomData<T> {
+    type Value = T;
+
+    fn decode<D: Decoder>(self, decoder: &mut D) -> Result<T, DecodeError> {
+        T::decode(decoder)
+    }
+}
+
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
 pub trait Decoder: Sealed {
     /// The concrete [Reader] type
@@ -106,22 +162,28 @@ pub trait Decoder: Sealed {
     /// Returns a reference to the config
     fn config(&self) -> &Self::C;
 
+    /// Returns the context that was given to [DecoderImpl::with_context]. This is always `Some`; a decoder created with [DecoderImpl::new] has `()` as its context.
+    ///
+    /// This can be used by `Decode` implementations that need external state, e.g. a table to resolve interned values. Use [Any::downcast_mut] or [Any::downcast_ref] to get the concrete type of the context.
+    ///
+    /// [Any::downcast_mut]: core::any::Any#method.downcast_mut
+    /// [Any::downcast_ref]: core::any::Any#method.downcast_ref
+    fn context(&mut self) -> Option<&mut dyn core::any::Any>;
+
     /// Claim that `n` bytes are going to be read from the decoder.
     /// This can be used to validate `Configuration::Limit<N>()`.
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError>;
 
     /// Claim that we're going to read a container which contains `len` entries of `T`.
     /// This will correctly handle overflowing if `len * size_of::<T>() > usize::max_value`
-    fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
-        if <Self::C as InternalLimitConfig>::LIMIT.is_some() {
-            match len.checked_mul(core::mem::size_of::<T>()) {
-                Some(val) => self.claim_bytes_read(val),
-                None => Err(DecodeError::LimitExceeded),
-            }
-        } else {
-            Ok(())
-        }
-    }
+    ///
+    /// If `T` is a zero-sized type, no bytes are claimed. Instead `len` is bounded by the configured [max collection length], or by [DEFAULT_MAX_ZST_CONTAINER_LEN] if none is configured.
+    /// Without this, a container of e.g. `()` with a crafted length would make the decoder loop almost indefinitely.
+    ///
+    /// Every claimed item must be un-claimed with [Decoder::unclaim_bytes_read] while decoding the container. When `debug_assertions` are enabled, bincode panics at the end of a successful decode if this did not happen.
+    ///
+    /// [max collection length]: crate::config::Configuration::with_max_collection_len
+    fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError>;
 
     /// Notify the decoder that `n` bytes are being reclaimed.
     ///
@@ -149,10 +211,9 @@ pub trait Decoder: Sealed {
     /// # }
     /// impl<T: Decode> Decode for Container<T> {
     ///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-    ///         let len = u64::decode(decoder)?;
-    ///         let len: usize = len.try_into().map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+    ///         let len = decoder.decode_len()?;
     ///         // Make sure we don't allocate too much memory
-    ///         decoder.claim_bytes_read(len * core::mem::size_of::<T>());
+    ///         decoder.claim_container_read::<T>(len)?;
     ///
     ///         let mut result = Container::with_capacity(len);
     ///         for _ in 0..len {
@@ -165,6 +226,238 @@ pub trait Decoder: Sealed {
     /// }
     /// ```
     fn unclaim_bytes_read(&mut self, n: usize);
+
+    /// Notify the decoder that a nested value is going to be decoded, e.g. the contents of a `Box`.
+    ///
+    /// Returns [DecodeError::DepthLimitExceeded] if this exceeds the [max depth] of the configuration. Every successful call must be followed by a call to [Decoder::exit_nested] once the nested value is decoded, even if decoding it failed. [Decoder::decode_nested_with] takes care of this.
+    ///
+    /// ```
+    /// # use bincode::de::{Decode, Decoder};
+    /// # use bincode::error::DecodeError;
+    /// struct Wrapper<T>(Box<T>);
+    ///
+    /// impl<T: Decode> Decode for Wrapper<T> {
+    ///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         decoder.enter_nested()?;
+    ///         let value = T::decode(decoder);
+    ///         decoder.exit_nested();
+    ///         Ok(Wrapper(Box::new(value?)))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [max depth]: crate::config::Configuration::with_max_depth
+    fn enter_nested(&mut self) -> Result<(), DecodeError>;
+
+    /// Notify the decoder that a nested value, started with [Decoder::enter_nested], is done decoding.
+    fn exit_nested(&mut self);
+
+    /// Decode a nested value, e.g. the items of a collection, by calling `decode` with this decoder between [Decoder::enter_nested] and [Decoder::exit_nested].
+    ///
+    /// The depth is restored when `decode` returns, even if it failed.
+    fn decode_nested_with<T, F>(&mut self, decode: F) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> Result<T, DecodeError>,
+    {
+        self.enter_nested()?;
+        let value = decode(self);
+        self.exit_nested();
+        value
+    }
+
+    /// Limit the reader of this decoder to the next `len` bytes, e.g. for a value that is prefixed by its length. Returns [DecodeError::UnexpectedEnd] if the current limit has less than `len` bytes left.
+    ///
+    /// Returns the amount of bytes after the new limit that could be read before. This amount must be passed to [Decoder::exit_bounded] once the bounded value is decoded, even if decoding it failed. [Decoder::decode_length_prefixed_with] takes care of this.
+    fn enter_bounded(&mut self, len: usize) -> Result<usize, DecodeError>;
+
+    /// Restore the limit of the reader from before [Decoder::enter_bounded], where `outside` is the amount that `enter_bounded` returned. Returns the amount of bytes of the bounded value that were not read.
+    fn exit_bounded(&mut self, outside: usize) -> usize;
+
+    /// Check that the reader has no bytes left, e.g. at the end of a custom `Decode` implementation of a strictly framed message. Returns [DecodeError::TrailingBytes] if there are any.
+    ///
+    /// Bincode has no look-ahead mechanism, so this relies on [Reader::peek_read]. Readers that can not peek, like an `std::io::Read`, always return `Ok(())`. Buffered readers like `std::io::BufReader` only detect the bytes they have already buffered.
+    ///
+    /// ```
+    /// # use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    /// # use bincode::error::DecodeError;
+    /// let config = bincode::config::standard();
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&[1, 2]), config);
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 1);
+    /// assert_eq!(decoder.finish(), Err(DecodeError::TrailingBytes));
+    /// assert_eq!(u8::decode(&mut decoder).unwrap(), 2);
+    /// assert_eq!(decoder.finish(), Ok(()));
+    /// ```
+    fn finish(&mut self) -> Result<(), DecodeError> {
+        if self.reader().peek_read(1).is_some() {
+            Err(DecodeError::TrailingBytes)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Read exactly `N` bytes from the reader with a single read, e.g. to decode a fixed-size header.
+    ///
+    /// The bytes count towards the configured limit. Unlike decoding a `[u8; N]`, no length prefix is read, regardless of the configuration.
+    ///
+    /// ```
+    /// # use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    /// let bytes = [0xCA, 0xFE, 0xBA, 0xBE, 5];
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard());
+    /// let magic: [u8; 4] = decoder.read_array().unwrap();
+    /// assert_eq!(magic, [0xCA, 0xFE, 0xBA, 0xBE]);
+    /// assert_eq!(u32::decode(&mut decoder).unwrap(), 5);
+    /// ```
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        self.claim_bytes_read(N)?;
+        let mut array = [0u8; N];
+        self.reader().read(&mut array)?;
+        Ok(array)
+    }
+
+    /// Read a `u16` as 2 little-endian bytes, regardless of the endianness and int encoding of the configuration.
+    ///
+    /// This and the other `read_*_le` and `read_*_be` functions are meant for fixed-width fields of other formats, like the header of a network protocol. The bytes count towards the configured limit.
+    fn read_u16_le(&mut self) -> Result<u16, DecodeError> {
+        self.read_array().map(u16::from_le_bytes)
+    }
+
+    /// Read a `u16` as 2 big-endian bytes, regardless of the configuration. See [Decoder::read_u16_le].
+    fn read_u16_be(&mut self) -> Result<u16, DecodeError> {
+        self.read_array().map(u16::from_be_bytes)
+    }
+
+    /// Read a `u32` as 4 little-endian bytes, regardless of the configuration. See [Decoder::read_u16_le].
+    ///
+    /// ```
+    /// # use bincode::de::{read::SliceReader, Decode, Decoder, DecoderImpl};
+    /// // A frame with a raw length, followed by a varint
+    /// let bytes = [1, 0, 0, 0, 251, 0, 1];
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard());
+    /// assert_eq!(decoder.read_u32_le().unwrap(), 1);
+    /// assert_eq!(u32::decode(&mut decoder).unwrap(), 256);
+    /// ```
+    fn read_u32_le(&mut self) -> Result<u32, DecodeError> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    /// Read a `u32` as 4 big-endian bytes, regardless of the configuration. See [Decoder::read_u16_le].
+    fn read_u32_be(&mut self) -> Result<u32, DecodeError> {
+        self.read_array().map(u32::from_be_bytes)
+    }
+
+    /// Read a `u64` as 8 little-endian bytes, regardless of the configuration. See [Decoder::read_u16_le].
+    fn read_u64_le(&mut self) -> Result<u64, DecodeError> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    /// Read a `u64` as 8 big-endian bytes, regardless of the configuration. See [Decoder::read_u16_le].
+    fn read_u64_be(&mut self) -> Result<u64, DecodeError> {
+        self.read_array().map(u64::from_be_bytes)
+    }
+
+    /// Skip the next `n` bytes of the reader without decoding them.
+    ///
+    /// The skipped bytes count towards the configured limit, but are never stored. This is useful to e.g. ignore a length-prefixed blob that this version of your type does not understand.
+    fn skip_bytes(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.claim_bytes_read(n)?;
+        skip_reader_bytes(self.reader(), n)
+    }
+
+    /// Read bytes until `delim` is found, e.g. to decode a NUL-terminated string of another format. The delimiter is consumed, but not included in the returned bytes.
+    ///
+    /// The bytes and the delimiter count towards the configured limit. If the reader ends before the delimiter is found, [DecodeError::UnexpectedEnd] is returned.
+    ///
+    /// ```
+    /// # use bincode::de::{read::SliceReader, Decoder, DecoderImpl};
+    /// let bytes = b"key\0value\0";
+    /// let mut decoder = DecoderImpl::new(SliceReader::new(bytes), bincode::config::standard());
+    /// assert_eq!(decoder.read_until(0).unwrap(), b"key");
+    /// assert_eq!(decoder.read_until(0).unwrap(), b"value");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, delim: u8) -> Result<alloc::vec::Vec<u8>, DecodeError>
+    where
+        Self: Sized,
+    {
+        check_allocation::<alloc::vec::Vec<u8>, _>(self)?;
+        let mut bytes = alloc::vec::Vec::new();
+        loop {
+            self.claim_bytes_read(1)?;
+            let mut byte = [0u8];
+            self.reader().read(&mut byte)?;
+            if byte[0] == delim {
+                return Ok(bytes);
+            }
+            bytes.push(byte[0]);
+        }
+    }
+
+    /// Decode the length of a collection, with the same encoding that the built-in collections like `Vec<T>` use.
+    ///
+    /// The length is checked against the configured [max collection length]. The length is not claimed against the byte limit; use [Decoder::claim_container_read] for that before allocating the collection.
+    ///
+    /// This can be used together with [Encoder::encode_len] to implement `Encode` and `Decode` for custom collections.
+    ///
+    /// [max collection length]: crate::config::Configuration::with_max_collection_len
+    /// [Encoder::encode_len]: crate::enc::Encoder::encode_len
+    fn decode_len(&mut self) -> Result<usize, DecodeError>
+    where
+        Self: Sized,
+    {
+        decode_slice_len(self)
+    }
+
+    /// Decode a value that was encoded with [Encoder::encode_length_prefixed].
+    ///
+    /// The value will not be able to read past the encoded length. If the value did not use all of its bytes, the remaining bytes are skipped. This allows newer versions of a type to append data that older versions will ignore.
+    ///
+    /// The value is decoded with the [context](Decoder::context), the nesting depth and the configured limit of this decoder.
+    ///
+    /// [Encoder::encode_length_prefixed]: crate::enc::Encoder::encode_length_prefixed
+    fn decode_length_prefixed<T: Decode>(&mut self) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+    {
+        self.decode_length_prefixed_with(|decoder| T::decode(decoder))
+    }
+
+    /// Like [Decoder::decode_length_prefixed], but the value is decoded by calling `decode` with this decoder, which can not read past the encoded length while `decode` runs.
+    ///
+    /// This allows decoding a length prefixed value that consists of multiple parts, e.g. the fields of an enum variant.
+    fn decode_length_prefixed_with<T, F>(&mut self, decode: F) -> Result<T, DecodeError>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> Result<T, DecodeError>,
+    {
+        let len = decode_slice_len(self)?;
+
+        let outside = self.enter_bounded(len)?;
+        let value = decode(self);
+        let unread = self.exit_bounded(outside);
+        let value = value?;
+
+        // The value claimed the bytes that it read, the bytes that it did not read are claimed here
+        self.skip_bytes(unread)?;
+        Ok(value)
+    }
+}
+
+/// Read and discard the next `n` bytes of the reader, without claiming them.
+fn skip_reader_bytes<R: Reader>(reader: &mut R, n: usize) -> Result<(), DecodeError> {
+    if reader.peek_read(n).is_some() {
+        reader.consume(n);
+        return Ok(());
+    }
+
+    let mut buffer = [0u8; 64];
+    let mut remaining = n;
+    while remaining > 0 {
+        let len = remaining.min(buffer.len());
+        reader.read(&mut buffer[..len])?;
+        remaining -= len;
+    }
+    Ok(())
 }
 
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
@@ -176,9 +469,35 @@ pub trait BorrowDecoder<'de>: Decoder {
 
     /// Rerturns a mutable reference to the borrow reader
     fn borrow_reader(&mut self) -> &mut Self::BR;
+
+    /// Borrow the next `len` bytes of the input.
+    ///
+    /// The bytes count towards the configured limit. This is the building block for types that borrow from the input, like `&[u8]` and `&str`.
+    ///
+    /// ```
+    /// # use bincode::de::{BorrowDecode, BorrowDecoder};
+    /// # use bincode::error::DecodeError;
+    /// /// A fixed-size header that is borrowed from the input
+    /// struct Header<'a>(&'a [u8]);
+    ///
+    /// impl<'de: 'a, 'a> BorrowDecode<'de> for Header<'a> {
+    ///     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         decoder.borrow_slice(4).map(Header)
+    ///     }
+    /// }
+    ///
+    /// let (header, len): (Header, usize) =
+    ///     bincode::decode_from_slice(&[1, 2, 3, 4, 5], bincode::config::standard()).unwrap();
+    /// assert_eq!(header.0, &[1, 2, 3, 4]);
+    /// assert_eq!(len, 4);
+    /// ```
+    fn borrow_slice(&mut self, len: usize) -> Result<&'de [u8], DecodeError> {
+        self.claim_bytes_read(len)?;
+        self.borrow_reader().take_bytes(len)
+    }
 }
 
-impl<'a, T> Decoder for &'a mut T
+impl<T> Decoder for &mut T
 where
     T: Decoder,
 {
@@ -194,18 +513,52 @@ where
         T::config(self)
     }
 
+    fn context(&mut self) -> Option<&mut dyn core::any::Any> {
+        T::context(self)
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
         T::claim_bytes_read(self, n)
     }
 
+    #[inline]
+    fn claim_container_read<U>(&mut self, len: usize) -> Result<(), DecodeError> {
+        T::claim_container_read::<U>(self, len)
+    }
+
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
         T::unclaim_bytes_read(self, n)
     }
+
+    #[inline]
+    fn enter_nested(&mut self) -> Result<(), DecodeError> {
+        T::enter_nested(self)
+    }
+
+    #[inline]
+    fn exit_nested(&mut self) {
+        T::exit_nested(self)
+    }
+
+    #[inline]
+    fn enter_bounded(&mut self, len: usize) -> Result<usize, DecodeError> {
+        T::enter_bounded(self, len)
+    }
+
+    #[inline]
+    fn exit_bounded(&mut self, outside: usize) -> usize {
+        T::exit_bounded(self, outside)
+    }
+
+    #[inline]
+    fn skip_bytes(&mut self, n: usize) -> Result<(), DecodeError> {
+        T::skip_bytes(self, n)
+    }
 }
 
-impl<'a, 'de, T> BorrowDecoder<'de> for &'a mut T
+impl<'de, T> BorrowDecoder<'de> for &mut T
 where
     T: BorrowDecoder<'de>,
 {
@@ -214,6 +567,11 @@ where
     fn borrow_reader(&mut self) -> &mut Self::BR {
         T::borrow_reader(self)
     }
+
+    #[inline]
+    fn borrow_slice(&mut self, len: usize) -> Result<&'de [u8], DecodeError> {
+        T::borrow_slice(self, len)
+    }
 }
 
 /// Decodes only the option variant from the decoder. Will not read any more data than that.
@@ -227,7 +585,7 @@ pub(crate) fn decode_option_variant<D: Decoder>(
         0 => Ok(None),
         1 => Ok(Some(())),
         x => Err(DecodeError::UnexpectedVariant {
-            found: x as u32,
+            found: x.into(),
             allowed: crate::error::AllowedEnumVariants::Range { max: 1, min: 0 },
             type_name,
         }),
@@ -237,7 +595,129 @@ pub(crate) fn decode_option_variant<D: Decoder>(
 /// Decodes the length of any slice, container, etc from the decoder
 #[inline]
 pub(crate) fn decode_slice_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
-    let v = u64::decode(decoder)?;
+    let len = len_from_u64(u64::decode(decoder)?)?;
+    // C::MAX_COLLECTION_LEN is a const so this check should get compiled away
+    if let Some(max) = <D::C as InternalMaxCollectionLenConfig>::MAX_COLLECTION_LEN {
+        if len > max {
+            return Err(DecodeError::CollectionTooLong { len, max });
+        }
+    }
+    Ok(len)
+}
+
+/// Returns [DecodeError::AllocationDisallowed] if the configuration of the decoder does not allow decoding `T`, because it allocates. See [Configuration::with_no_allocation].
+///
+/// [Configuration::with_no_allocation]: crate::config::Configuration::with_no_allocation
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn check_allocation<T: ?Sized, D: Decoder>(_decoder: &D) -> Result<(), DecodeError> {
+    if <D::C as crate::config::InternalAllocationConfig>::ALLOW_ALLOCATION {
+        Ok(())
+    } else {
+        Err(DecodeError::AllocationDisallowed {
+            type_name: core::any::type_name::<T>(),
+        })
+    }
+}
+
+/// Reads and checks the padding of [Configuration::with_alignment] after a value. Does nothing if no alignment is configured.
+///
+/// [Configuration::with_alignment]: crate::config::Configuration::with_alignment
+pub(crate) fn decode_alignment_padding<D: Decoder>(decoder: &mut D) -> Result<(), DecodeError> {
+    if let Some(alignment) = <D::C as InternalAlignmentConfig>::ALIGNMENT {
+        let padding = usize::from(u8::decode(decoder)?);
+        if padding >= alignment {
+            return Err(DecodeError::InvalidAlignmentPadding { alignment });
+        }
+        decoder.claim_bytes_read(padding)?;
+        let mut bytes = [0u8; 255];
+        decoder.reader().read(&mut bytes[..padding])?;
+        if bytes[..padding].iter().any(|byte| *byte != 0) {
+            return Err(DecodeError::InvalidAlignmentPadding { alignment });
+        }
+    }
+    Ok(())
+}
+
+/// Converts a decoded length to a `usize`, without truncating it on targets where `usize` is smaller than 64 bits.
+#[inline]
+pub(crate) fn len_from_u64(value: u64) -> Result<usize, DecodeError> {
+    try_from_u64(value).ok_or(DecodeError::IntegerOverflow { value })
+}
 
-    v.try_into().map_err(|_| DecodeError::OutsideUsizeRange(v))
+/// Converts a decoded `usize` to a `usize` on this target, without truncating it on targets where `usize` is smaller than 64 bits.
+#[inline]
+pub(crate) fn usize_from_u64(value: u64) -> Result<usize, DecodeError> {
+    try_from_u64(value).ok_or(DecodeError::OutsideUsizeRange(value))
+}
+
+/// Converts a decoded `u64` to `T`. This is generic so tests can simulate a target with a smaller `usize`.
+#[inline]
+fn try_from_u64<T: TryFrom<u64>>(value: u64) -> Option<T> {
+    T::try_from(value).ok()
+}
+
+/// The amount of items to reserve up front for a collection of `len` items, where every item is encoded as at least `min_item_size` bytes.
+///
+/// If the reader knows how many bytes it has left, a corrupted or malicious `len` can not make us reserve room for more items than the input could possibly contain.
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+pub(crate) fn reserve_hint<D: Decoder>(decoder: &mut D, len: usize, min_item_size: usize) -> usize {
+    match decoder.reader().remaining_bytes() {
+        Some(remaining) if min_item_size > 0 => len.min(remaining / min_item_size),
+        _ => len,
+    }
+}
+
+/// Decodes the `(Bound<T>, Bound<T>)` of a range that was encoded with [encode_range_bounds].
+///
+/// The returned tuple implements `RangeBounds<T>`, so it can be used in place of the original range.
+///
+/// ```
+/// # use bincode::de::{decode_range_bounds, read::SliceReader, DecoderImpl};
+/// # use core::ops::{Bound, RangeBounds};
+/// let config = bincode::config::standard();
+/// let bytes = [1, 2, 2, 5];
+///
+/// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+/// let bounds = decode_range_bounds::<u32, _>(&mut decoder).unwrap();
+/// assert_eq!(bounds, (Bound::Included(2), Bound::Excluded(5)));
+/// assert!(bounds.contains(&4));
+/// assert!(!bounds.contains(&5));
+/// ```
+///
+/// [encode_range_bounds]: crate::enc::encode_range_bounds
+pub fn decode_range_bounds<T, D>(decoder: &mut D) -> Result<(Bound<T>, Bound<T>), DecodeError>
+where
+    T: Decode,
+    D: Decoder,
+{
+    let start = Bound::<T>::decode(decoder)?;
+    let end = Bound::<T>::decode(decoder)?;
+    Ok((start, end))
+}
+
+#[test]
+fn test_try_from_u64() {
+    // A 32-bit target
+    assert_eq!(try_from_u64::<u32>(u32::MAX as u64), Some(u32::MAX));
+    assert_eq!(try_from_u64::<u32>(u32::MAX as u64 + 1), None);
+    assert_eq!(try_from_u64::<u32>(u64::MAX), None);
+
+    // A 16-bit target
+    assert_eq!(try_from_u64::<u16>(300), Some(300));
+    assert_eq!(try_from_u64::<u16>(70_000), None);
+
+    assert_eq!(usize_from_u64(5), Ok(5));
+    assert_eq!(len_from_u64(5), Ok(5));
+    if cfg!(target_pointer_width = "32") {
+        let value = u32::MAX as u64 + 1;
+        assert_eq!(
+            usize_from_u64(value),
+            Err(DecodeError::OutsideUsizeRange(value))
+        );
+        assert_eq!(
+            len_from_u64(value),
+            Err(DecodeError::IntegerOverflow { value })
+        );
+    }
 }
@@ -29,6 +29,14 @@ pub trait Reader {
     /// must be provided so that subsequent reads or peek-reads do not return the same bytes
     #[inline]
     fn consume(&mut self, _: usize) {}
+
+    /// The amount of bytes that are left in this reader, if known.
+    ///
+    /// This is only a hint. Collections use it to avoid reserving room for more items than the remaining input could possibly contain, e.g. when the length of a collection is corrupted. Returning `None` is always valid.
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl<T> Reader for &mut T
@@ -49,6 +57,11 @@ where
     fn consume(&mut self, n: usize) {
         (*self).consume(n)
     }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        (**self).remaining_bytes()
+    }
 }
 
 /// A reader for borrowed data. Implementors of this must also implement the [Reader] trait. See the module documentation for more information.
@@ -59,6 +72,16 @@ pub trait BorrowReader<'storage>: Reader {
     fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError>;
 }
 
+impl<'storage, T> BorrowReader<'storage> for &mut T
+where
+    T: BorrowReader<'storage>,
+{
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        (**self).take_bytes(length)
+    }
+}
+
 /// A reader type for `&[u8]` slices. Implements both [Reader] and [BorrowReader], and thus can be used for borrowed data.
 pub struct SliceReader<'storage> {
     pub(crate) slice: &'storage [u8],
@@ -95,6 +118,11 @@ impl<'storage> Reader for SliceReader<'storage> {
     fn consume(&mut self, n: usize) {
         self.slice = self.slice.get(n..).unwrap_or_default();
     }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
 }
 
 impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
@@ -110,3 +138,273 @@ impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
         Ok(read_slice)
     }
 }
+
+/// A reader over several `&[u8]` segments, which are read as if they were one contiguous slice.
+///
+/// This is useful when the encoded data arrives in multiple buffers, e.g. from a scatter-gather read, because the buffers don't have to be copied into one slice first. Values can straddle the boundary between two segments.
+///
+/// `ChainReader` does not implement [BorrowReader], because borrowed data could be split over multiple segments.
+///
+/// ```
+/// # use bincode::de::read::ChainReader;
+/// // The `u16` is split over both segments
+/// let segments: [&[u8]; 2] = [&[1, 0x34], &[0x12]];
+/// let decoded: (u8, u16) =
+///     bincode::decode_from_reader(ChainReader::new(&segments), bincode::config::legacy()).unwrap();
+/// assert_eq!(decoded, (1, 0x1234));
+/// ```
+pub struct ChainReader<'storage> {
+    current: &'storage [u8],
+    segments: &'storage [&'storage [u8]],
+}
+
+impl<'storage> ChainReader<'storage> {
+    /// Constructs a reader that reads `segments` in order
+    pub fn new(segments: &'storage [&'storage [u8]]) -> ChainReader<'storage> {
+        let mut reader = ChainReader {
+            current: &[],
+            segments,
+        };
+        reader.next_segment();
+        reader
+    }
+
+    /// Skip to the next segment that is not empty, if the current segment is exhausted
+    fn next_segment(&mut self) {
+        while self.current.is_empty() {
+            match self.segments.split_first() {
+                Some((first, rest)) => {
+                    self.current = first;
+                    self.segments = rest;
+                }
+                None => return,
+            }
+        }
+    }
+}
+
+impl<'storage> Reader for ChainReader<'storage> {
+    #[inline]
+    fn read(&mut self, mut bytes: &mut [u8]) -> Result<(), DecodeError> {
+        while !bytes.is_empty() {
+            if self.current.is_empty() {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: bytes.len(),
+                });
+            }
+            let len = bytes.len().min(self.current.len());
+            let (read_slice, remaining) = self.current.split_at(len);
+            bytes[..len].copy_from_slice(read_slice);
+            bytes = &mut bytes[len..];
+            self.current = remaining;
+            self.next_segment();
+        }
+        Ok(())
+    }
+
+    /// Only bytes within the current segment can be peeked, `None` is returned if the bytes straddle two segments
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&'storage [u8]> {
+        self.current.get(..n)
+    }
+
+    #[inline]
+    fn consume(&mut self, mut n: usize) {
+        while n > 0 && !self.current.is_empty() {
+            let len = n.min(self.current.len());
+            self.current = &self.current[len..];
+            n -= len;
+            self.next_segment();
+        }
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        Some(self.current.len() + self.segments.iter().map(|s| s.len()).sum::<usize>())
+    }
+}
+
+/// A reader that will only read up to `limit` bytes from the inner reader. Reading past the limit will return [DecodeError::UnexpectedEnd], even if the inner reader has more data.
+///
+/// This is useful for decoding untrusted frames, so that a malicious length inside of a frame can not read into the next frame.
+///
+/// ```
+/// # use bincode::de::{read::{LimitReader, SliceReader}, DecoderImpl, Decode};
+/// # use bincode::error::DecodeError;
+/// // A frame of 2 bytes, followed by the start of the next frame
+/// let input = [5, 10, 99];
+/// let reader = LimitReader::new(SliceReader::new(&input), 2);
+/// let mut decoder = DecoderImpl::new(reader, bincode::config::standard());
+///
+/// assert_eq!(<(u8, u8)>::decode(&mut decoder).unwrap(), (5, 10));
+/// assert!(matches!(
+///     u8::decode(&mut decoder),
+///     Err(DecodeError::UnexpectedEnd { additional: 1 })
+/// ));
+/// ```
+pub struct LimitReader<R> {
+    pub(crate) reader: R,
+    pub(crate) remaining: usize,
+}
+
+impl<R: Reader> LimitReader<R> {
+    /// Constructs a reader that will read at most `limit` bytes from `reader`
+    pub fn new(reader: R, limit: usize) -> Self {
+        Self {
+            reader,
+            remaining: limit,
+        }
+    }
+
+    /// The amount of bytes that can still be read from this reader
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Return the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reader> Reader for LimitReader<R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        if bytes.len() > self.remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: bytes.len() - self.remaining,
+            });
+        }
+        self.reader.read(bytes)?;
+        self.remaining -= bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&[u8]> {
+        if n > self.remaining {
+            return None;
+        }
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.remaining = self.remaining.saturating_sub(n);
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        Some(match self.reader.remaining_bytes() {
+            Some(inner) => inner.min(self.remaining),
+            None => self.remaining,
+        })
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>> BorrowReader<'storage> for LimitReader<R> {
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        if length > self.remaining {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: length - self.remaining,
+            });
+        }
+        let bytes = self.reader.take_bytes(length)?;
+        self.remaining -= length;
+        Ok(bytes)
+    }
+}
+
+/// A reader that calls `callback` with the total amount of bytes consumed so far, every time at least `interval` more bytes have been read from the inner reader.
+///
+/// This can be used to report the progress of decoding a large value, e.g. from a file in a `std::io::BufReader`. The amounts passed to the callback are strictly increasing.
+///
+/// ```
+/// # use bincode::de::read::{ProgressReader, SliceReader};
+/// let bytes = [0u8; 10];
+/// let mut progress = Vec::new();
+/// let reader = ProgressReader::new(SliceReader::new(&bytes), 4, |consumed| progress.push(consumed));
+/// let decoded: [u8; 10] =
+///     bincode::decode_from_reader(reader, bincode::config::standard().skip_fixed_array_length()).unwrap();
+/// assert_eq!(decoded, [0; 10]);
+/// assert_eq!(progress, [10]);
+/// ```
+pub struct ProgressReader<R, F> {
+    reader: R,
+    interval: usize,
+    bytes_consumed: usize,
+    next_report: usize,
+    callback: F,
+}
+
+impl<R: Reader, F: FnMut(usize)> ProgressReader<R, F> {
+    /// Constructs a reader that calls `callback` every `interval` bytes that are read from `reader`. An `interval` of 0 is treated as 1.
+    pub fn new(reader: R, interval: usize, callback: F) -> Self {
+        let interval = interval.max(1);
+        Self {
+            reader,
+            interval,
+            bytes_consumed: 0,
+            next_report: interval,
+            callback,
+        }
+    }
+
+    /// The amount of bytes that have been read so far
+    pub fn bytes_consumed(&self) -> usize {
+        self.bytes_consumed
+    }
+
+    /// Return the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.bytes_consumed += n;
+        if self.bytes_consumed >= self.next_report {
+            (self.callback)(self.bytes_consumed);
+            // Report again once the next multiple of `interval` is reached
+            let last_multiple = self.bytes_consumed - self.bytes_consumed % self.interval;
+            self.next_report = last_multiple.saturating_add(self.interval);
+        }
+    }
+}
+
+impl<R: Reader, F: FnMut(usize)> Reader for ProgressReader<R, F> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.advance(bytes.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.advance(n);
+    }
+
+    #[inline]
+    fn remaining_bytes(&self) -> Option<usize> {
+        self.reader.remaining_bytes()
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>, F: FnMut(usize)> BorrowReader<'storage>
+    for ProgressReader<R, F>
+{
+    #[inline]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.advance(length);
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::time::Duration;
+
+/// Encodes a `Duration` as a single `u32` of milliseconds, instead of its seconds and nanoseconds. This is a lot smaller when sub-millisecond precision is not needed.
+///
+/// The duration is rounded to the nearest millisecond, with halfway cases rounded up. Durations of more than `u32::MAX` milliseconds, about 49.7 days, return [EncodeError::DurationMillisOverflow].
+///
+/// ```
+/// use bincode::DurationMillis;
+/// use core::time::Duration;
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = [0u8; 8];
+/// let len = bincode::encode_into_slice(DurationMillis(Duration::from_micros(1_500)), &mut bytes, config).unwrap();
+/// // Encoded as `2u32`
+/// assert_eq!(&bytes[..len], [2]);
+///
+/// let (decoded, _): (DurationMillis, usize) = bincode::decode_from_slice(&bytes[..len], config).unwrap();
+/// assert_eq!(decoded.0, Duration::from_millis(2));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationMillis(pub Duration);
+
+impl DurationMillis {
+    /// Returns the rounded amount of milliseconds that is encoded, or `None` if it does not fit in a `u32`.
+    pub fn to_millis(&self) -> Option<u32> {
+        let mut millis = self.0.as_millis();
+        if self.0.subsec_nanos() % 1_000_000 >= 500_000 {
+            millis += 1;
+        }
+        u32::try_from(millis).ok()
+    }
+}
+
+impl Encode for DurationMillis {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self.to_millis() {
+            Some(millis) => millis.encode(encoder),
+            None => Err(EncodeError::DurationMillisOverflow { duration: self.0 }),
+        }
+    }
+}
+
+impl Decode for DurationMillis {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self(Duration::from_millis(u32::decode(decoder)?.into())))
+    }
+}
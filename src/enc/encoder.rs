@@ -1,5 +1,6 @@
 use super::{write::Writer, Encoder};
-use crate::{config::Config, utils::Sealed};
+use crate::{config::Config, error::EncodeError, utils::Sealed};
+use core::any::Any;
 
 /// An Encoder that writes bytes into a given writer `W`.
 ///
@@ -20,24 +21,76 @@ use crate::{config::Config, utils::Sealed};
 /// assert_eq!(encoder.into_writer().bytes_written(), 4);
 /// assert_eq!(slice, [0, 0, 0, 5]);
 /// ```
-pub struct EncoderImpl<W: Writer, C: Config> {
+pub struct EncoderImpl<W: Writer, C: Config, Ctx = ()> {
     writer: W,
     config: C,
+    context: Ctx,
 }
 
 impl<W: Writer, C: Config> EncoderImpl<W, C> {
     /// Create a new Encoder
     pub fn new(writer: W, config: C) -> EncoderImpl<W, C> {
-        EncoderImpl { writer, config }
+        Self::with_context(writer, config, ())
+    }
+}
+
+impl<W: Writer, C: Config, Ctx: Any> EncoderImpl<W, C, Ctx> {
+    /// Create a new Encoder with the given context. `Encode` implementations can access the context through [Encoder::context].
+    ///
+    /// ```
+    /// # use bincode::enc::{write::SliceWriter, Encode, Encoder, EncoderImpl};
+    /// # use bincode::error::EncodeError;
+    /// struct Interned(&'static str);
+    ///
+    /// impl Encode for Interned {
+    ///     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+    ///         let table = encoder
+    ///             .context()
+    ///             .and_then(|ctx| ctx.downcast_mut::<Vec<&'static str>>())
+    ///             .ok_or(EncodeError::Other("Missing string table"))?;
+    ///         let index = match table.iter().position(|s| *s == self.0) {
+    ///             Some(index) => index,
+    ///             None => {
+    ///                 table.push(self.0);
+    ///                 table.len() - 1
+    ///             }
+    ///         };
+    ///         index.encode(encoder)
+    ///     }
+    /// }
+    ///
+    /// let slice: &mut [u8] = &mut [0, 0, 0];
+    /// let mut encoder = EncoderImpl::with_context(
+    ///     SliceWriter::new(slice),
+    ///     bincode::config::standard(),
+    ///     Vec::<&'static str>::new(),
+    /// );
+    /// (Interned("foo"), Interned("bar"), Interned("foo"))
+    ///     .encode(&mut encoder)
+    ///     .unwrap();
+    /// assert_eq!(encoder.into_context(), ["foo", "bar"]);
+    /// assert_eq!(slice, [0, 1, 0]);
+    /// ```
+    pub fn with_context(writer: W, config: C, context: Ctx) -> EncoderImpl<W, C, Ctx> {
+        EncoderImpl {
+            writer,
+            config,
+            context,
+        }
     }
 
     /// Return the underlying writer
     pub fn into_writer(self) -> W {
         self.writer
     }
+
+    /// Return the context of this encoder
+    pub fn into_context(self) -> Ctx {
+        self.context
+    }
 }
 
-impl<W: Writer, C: Config> Encoder for EncoderImpl<W, C> {
+impl<W: Writer, C: Config, Ctx: Any> Encoder for EncoderImpl<W, C, Ctx> {
     type W = W;
 
     type C = C;
@@ -49,6 +102,93 @@ impl<W: Writer, C: Config> Encoder for EncoderImpl<W, C> {
     fn config(&self) -> &Self::C {
         &self.config
     }
+
+    fn context(&mut self) -> Option<&mut dyn Any> {
+        Some(&mut self.context)
+    }
+}
+
+impl<W: Writer, C: Config, Ctx> Sealed for EncoderImpl<W, C, Ctx> {}
+
+/// The encoder that is passed to the callback of [Encoder::encode_length_prefixed_with].
+///
+/// It encodes into a buffer, so the length of the value is known before it is written to the outer encoder. The [config](Encoder::config) and the [context](Encoder::context) are those of the outer encoder.
+pub struct LengthPrefixedEncoder<'a, C> {
+    writer: LengthPrefixedWriter,
+    config: C,
+    context: Option<&'a mut dyn Any>,
+}
+
+impl<'a, C: Config> LengthPrefixedEncoder<'a, C> {
+    pub(crate) fn new(config: C, context: Option<&'a mut dyn Any>) -> Self {
+        Self {
+            writer: LengthPrefixedWriter::default(),
+            config,
+            context,
+        }
+    }
+
+    pub(crate) fn into_writer(self) -> LengthPrefixedWriter {
+        self.writer
+    }
 }
 
-impl<W: Writer, C: Config> Sealed for EncoderImpl<W, C> {}
+impl<C: Config> Encoder for LengthPrefixedEncoder<'_, C> {
+    type W = LengthPrefixedWriter;
+
+    type C = C;
+
+    fn writer(&mut self) -> &mut Self::W {
+        &mut self.writer
+    }
+
+    fn config(&self) -> &Self::C {
+        &self.config
+    }
+
+    fn context(&mut self) -> Option<&mut dyn Any> {
+        match &mut self.context {
+            Some(context) => Some(&mut **context),
+            None => None,
+        }
+    }
+}
+
+impl<C> Sealed for LengthPrefixedEncoder<'_, C> {}
+
+/// The writer of a [LengthPrefixedEncoder], which collects the encoded bytes in a buffer.
+///
+/// The buffer requires the `alloc` feature. Without it, writing any bytes returns an error.
+#[derive(Default)]
+pub struct LengthPrefixedWriter {
+    #[cfg(feature = "alloc")]
+    buffer: alloc::vec::Vec<u8>,
+}
+
+impl LengthPrefixedWriter {
+    /// The bytes that were written so far
+    pub(crate) fn bytes(&self) -> &[u8] {
+        #[cfg(feature = "alloc")]
+        return &self.buffer;
+        #[cfg(not(feature = "alloc"))]
+        return &[];
+    }
+}
+
+impl Writer for LengthPrefixedWriter {
+    #[cfg(feature = "alloc")]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        Err(EncodeError::Other(
+            "Encoding a length prefixed value of unknown size requires the alloc feature",
+        ))
+    }
+}
@@ -1,5 +1,5 @@
 use super::{Encode, Encoder};
-use crate::error::EncodeError;
+use crate::{config::Config, error::EncodeError};
 
 impl<A> Encode for (A,)
 where
@@ -9,6 +9,10 @@ where
         self.0.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        self.0.size_hint::<_C>()
+    }
 }
 
 impl<A, B> Encode for (A, B)
@@ -21,6 +25,12 @@ where
         self.1.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C> Encode for (A, B, C)
@@ -35,6 +45,13 @@ where
         self.2.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C, D> Encode for (A, B, C, D)
@@ -51,6 +68,14 @@ where
         self.3.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        size = size.checked_add(self.3.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C, D, E> Encode for (A, B, C, D, E)
@@ -69,6 +94,15 @@ where
         self.4.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        size = size.checked_add(self.3.size_hint::<_C>()?)?;
+        size = size.checked_add(self.4.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C, D, E, F> Encode for (A, B, C, D, E, F)
@@ -89,6 +123,16 @@ where
         self.5.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        size = size.checked_add(self.3.size_hint::<_C>()?)?;
+        size = size.checked_add(self.4.size_hint::<_C>()?)?;
+        size = size.checked_add(self.5.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C, D, E, F, G> Encode for (A, B, C, D, E, F, G)
@@ -111,6 +155,17 @@ where
         self.6.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        size = size.checked_add(self.3.size_hint::<_C>()?)?;
+        size = size.checked_add(self.4.size_hint::<_C>()?)?;
+        size = size.checked_add(self.5.size_hint::<_C>()?)?;
+        size = size.checked_add(self.6.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
 
 impl<A, B, C, D, E, F, G, H> Encode for (A, B, C, D, E, F, G, H)
@@ -135,4 +190,16 @@ where
         self.7.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<_C: Config>(&self) -> Option<usize> {
+        let mut size = self.0.size_hint::<_C>()?;
+        size = size.checked_add(self.1.size_hint::<_C>()?)?;
+        size = size.checked_add(self.2.size_hint::<_C>()?)?;
+        size = size.checked_add(self.3.size_hint::<_C>()?)?;
+        size = size.checked_add(self.4.size_hint::<_C>()?)?;
+        size = size.checked_add(self.5.size_hint::<_C>()?)?;
+        size = size.checked_add(self.6.size_hint::<_C>()?)?;
+        size = size.checked_add(self.7.size_hint::<_C>()?)?;
+        Some(size)
+    }
 }
@@ -1,50 +1,81 @@
 use super::{write::Writer, Encode, Encoder};
 use crate::{
     config::{
-        Endian, IntEncoding, InternalArrayLengthConfig, InternalEndianConfig,
-        InternalIntEncodingConfig,
+        Config, Endian, IntEncoding, InternalArrayLengthConfig, InternalEndianConfig,
+        InternalIntEncodingConfig, InternalStringLenConfig,
     },
     error::EncodeError,
 };
 use core::{
-    cell::{Cell, RefCell},
+    cell::{Cell, OnceCell, RefCell},
+    cmp::Reverse,
     marker::PhantomData,
     num::{
-        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
-        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+        FpCategory, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+        NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     },
     ops::{Bound, Range, RangeInclusive},
+    sync::atomic,
     time::Duration,
 };
 
+/// The size of an integer of type `T`, which is `varint_size` bytes with variable int encoding
+fn int_size_hint<C: Config, T>(varint_size: usize) -> Option<usize> {
+    Some(match C::INT_ENCODING {
+        IntEncoding::Variable => varint_size,
+        IntEncoding::Fixed => core::mem::size_of::<T>(),
+    })
+}
+
+/// `()` is encoded as zero bytes.
 impl Encode for () {
     fn encode<E: Encoder>(&self, _: &mut E) -> Result<(), EncodeError> {
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(0)
+    }
 }
 
 impl<T> Encode for PhantomData<T> {
     fn encode<E: Encoder>(&self, _: &mut E) -> Result<(), EncodeError> {
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(0)
+    }
 }
 
 impl Encode for bool {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         if *self { 1u8 } else { 0u8 }.encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(1)
+    }
 }
 
 impl Encode for u8 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         encoder.writer().write(&[*self])
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(1)
+    }
 }
 
 impl Encode for NonZeroU8 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for u16 {
@@ -59,12 +90,20 @@ impl Encode for u16 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, u16>(crate::varint::varint_size(*self as u128))
+    }
 }
 
 impl Encode for NonZeroU16 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for u32 {
@@ -79,12 +118,20 @@ impl Encode for u32 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, u32>(crate::varint::varint_size(*self as u128))
+    }
 }
 
 impl Encode for NonZeroU32 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for u64 {
@@ -99,12 +146,20 @@ impl Encode for u64 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, u64>(crate::varint::varint_size(*self as u128))
+    }
 }
 
 impl Encode for NonZeroU64 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for u128 {
@@ -119,12 +174,20 @@ impl Encode for u128 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, u128>(crate::varint::varint_size(*self))
+    }
 }
 
 impl Encode for NonZeroU128 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for usize {
@@ -139,24 +202,40 @@ impl Encode for usize {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, usize>(crate::varint::varint_size(*self as u128))
+    }
 }
 
 impl Encode for NonZeroUsize {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for i8 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         encoder.writer().write(&[*self as u8])
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(1)
+    }
 }
 
 impl Encode for NonZeroI8 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for i16 {
@@ -171,12 +250,20 @@ impl Encode for i16 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, i16>(crate::varint::varint_size_signed(*self as i128))
+    }
 }
 
 impl Encode for NonZeroI16 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for i32 {
@@ -191,12 +278,20 @@ impl Encode for i32 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, i32>(crate::varint::varint_size_signed(*self as i128))
+    }
 }
 
 impl Encode for NonZeroI32 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for i64 {
@@ -211,12 +306,20 @@ impl Encode for i64 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, i64>(crate::varint::varint_size_signed(*self as i128))
+    }
 }
 
 impl Encode for NonZeroI64 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for i128 {
@@ -231,12 +334,20 @@ impl Encode for i128 {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, i128>(crate::varint::varint_size_signed(*self))
+    }
 }
 
 impl Encode for NonZeroI128 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for isize {
@@ -251,12 +362,20 @@ impl Encode for isize {
             },
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        int_size_hint::<C, isize>(crate::varint::varint_size_signed(*self as i128))
+    }
 }
 
 impl Encode for NonZeroIsize {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.get().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl Encode for f32 {
@@ -266,6 +385,10 @@ impl Encode for f32 {
             Endian::Little => encoder.writer().write(&self.to_le_bytes()),
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(4)
+    }
 }
 
 impl Encode for f64 {
@@ -275,18 +398,48 @@ impl Encode for f64 {
             Endian::Little => encoder.writer().write(&self.to_le_bytes()),
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(8)
+    }
 }
 
 impl Encode for char {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         encode_utf8(encoder.writer(), *self)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        Some(self.len_utf8())
+    }
 }
 
-impl Encode for &'_ [u8] {
+/// A slice is encoded as its length, followed by its items. A `[u8]` is written as its raw bytes at once.
+impl<T> Encode for [T]
+where
+    T: Encode,
+{
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         super::encode_slice_len(encoder, self.len())?;
-        encoder.writer().write(self)
+        if crate::utils::is_same_type::<T, u8>() {
+            // Safety: T is u8, so the slice contains exactly `len` bytes
+            let bytes =
+                unsafe { core::slice::from_raw_parts(self.as_ptr() as *const u8, self.len()) };
+            return encoder.writer().write(bytes);
+        }
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        if crate::utils::is_same_type::<T, u8>() {
+            return (self.len() as u64)
+                .size_hint::<C>()?
+                .checked_add(self.len());
+        }
+        super::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
     }
 }
 
@@ -324,30 +477,23 @@ fn encode_utf8(writer: &mut impl Writer, c: char) -> Result<(), EncodeError> {
     }
 }
 
-// BlockedTODO: https://github.com/rust-lang/rust/issues/37653
-//
-// We'll want to implement encoding for both &[u8] and &[T: Encode],
-// but those implementations overlap because u8 also implements Encodeabl
-//
-// default impl Encode for &'_ [u8] {
-//     fn encode<E: Encode>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-//         encoder.encode_slice(*self)
-//     }
-// }
-//
-// impl<T: Encode> Encode for &'_ [T] {
-//     fn encode<E: Encode>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-//         self.len().encode(encoder)?;
-//         for item in self.iter() {
-//             item.encode(encoder)?;
-//         }
-//         Ok(())
-//     }
-// }
-
 impl Encode for &'_ str {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        self.as_bytes().encode(encoder)
+        if E::C::STRING_LEN_IN_CHARS {
+            super::encode_slice_len(encoder, self.chars().count())?;
+            encoder.writer().write(self.as_bytes())
+        } else {
+            self.as_bytes().encode(encoder)
+        }
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        let len = if C::STRING_LEN_IN_CHARS {
+            self.chars().count()
+        } else {
+            self.len()
+        };
+        (len as u64).size_hint::<C>()?.checked_add(self.len())
     }
 }
 
@@ -359,11 +505,55 @@ where
         if !E::C::SKIP_FIXED_ARRAY_LENGTH {
             super::encode_slice_len(encoder, N)?;
         }
+
+        // Optimize for arrays of integers that are encoded as their raw bytes, e.g. `[u8; N]`
+        if let Some(size) = crate::utils::raw_int_size::<T, E::C>() {
+            let native_endian = if cfg!(target_endian = "little") {
+                Endian::Little
+            } else {
+                Endian::Big
+            };
+            if size == 1 || E::C::ENDIAN == native_endian {
+                // Safety: we know that T is an integer type, which has no padding bytes, and the slice covers exactly the memory of `self`
+                let bytes = unsafe {
+                    core::slice::from_raw_parts(
+                        self as *const [T; N] as *const u8,
+                        core::mem::size_of::<[T; N]>(),
+                    )
+                };
+                return encoder.writer().write(bytes);
+            }
+
+            // Safety: we know that T is an integer type, so copying its bits does not duplicate any ownership
+            let mut array: [T; N] = unsafe { core::ptr::read(self) };
+            // Safety: the slice covers exactly the memory of `array`
+            let bytes = unsafe {
+                core::slice::from_raw_parts_mut(
+                    &mut array as *mut [T; N] as *mut u8,
+                    core::mem::size_of::<[T; N]>(),
+                )
+            };
+            for int in bytes.chunks_exact_mut(size) {
+                int.reverse();
+            }
+            return encoder.writer().write(bytes);
+        }
+
         for item in self.iter() {
             item.encode(encoder)?;
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        if C::SKIP_FIXED_ARRAY_LENGTH {
+            self.iter().try_fold(0usize, |size, item| {
+                size.checked_add(item.size_hint::<C>()?)
+            })
+        } else {
+            super::collection_size_hint::<C>(N, self.iter().map(T::size_hint::<C>))
+        }
+    }
 }
 
 impl<T> Encode for Option<T>
@@ -377,6 +567,13 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        match self {
+            Some(val) => val.size_hint::<C>()?.checked_add(1),
+            None => Some(1),
+        }
+    }
 }
 
 impl<T, U> Encode for Result<T, U>
@@ -396,6 +593,13 @@ where
             }
         }
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        match self {
+            Ok(val) => val.size_hint::<C>()?.checked_add(0u32.size_hint::<C>()?),
+            Err(err) => err.size_hint::<C>()?.checked_add(1u32.size_hint::<C>()?),
+        }
+    }
 }
 
 impl<T> Encode for Cell<T>
@@ -405,6 +609,10 @@ where
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         T::encode(&self.get(), encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
 }
 
 impl<T> Encode for RefCell<T>
@@ -420,6 +628,37 @@ where
             })?;
         T::encode(&borrow_guard, encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.try_borrow().ok()?.size_hint::<C>()
+    }
+}
+
+/// A `OnceCell` is encoded like an `Option<T>`, which is `None` if the cell is not initialized.
+impl<T> Encode for OnceCell<T>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.get().encode(encoder)
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.get().size_hint::<C>()
+    }
+}
+
+impl<T> Encode for Reverse<T>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.0.size_hint::<C>()
+    }
 }
 
 impl Encode for Duration {
@@ -428,6 +667,12 @@ impl Encode for Duration {
         self.subsec_nanos().encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.as_secs()
+            .size_hint::<C>()?
+            .checked_add(self.subsec_nanos().size_hint::<C>()?)
+    }
 }
 
 impl<T> Encode for Range<T>
@@ -439,6 +684,12 @@ where
         self.end.encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.start
+            .size_hint::<C>()?
+            .checked_add(self.end.size_hint::<C>()?)
+    }
 }
 
 impl<T> Encode for RangeInclusive<T>
@@ -450,6 +701,12 @@ where
         self.end().encode(encoder)?;
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.start()
+            .size_hint::<C>()?
+            .checked_add(self.end().size_hint::<C>()?)
+    }
 }
 
 impl<T> Encode for Bound<T>
@@ -474,11 +731,44 @@ where
     }
 }
 
-impl<'a, T> Encode for &'a T
+/// `FpCategory` is encoded as a single `u8`.
+impl Encode for FpCategory {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let variant: u8 = match self {
+            FpCategory::Nan => 0,
+            FpCategory::Infinite => 1,
+            FpCategory::Zero => 2,
+            FpCategory::Subnormal => 3,
+            FpCategory::Normal => 4,
+        };
+        variant.encode(encoder)
+    }
+}
+
+/// `atomic::Ordering` is encoded as a single `u8`.
+impl Encode for atomic::Ordering {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let variant: u8 = match self {
+            atomic::Ordering::Relaxed => 0,
+            atomic::Ordering::Release => 1,
+            atomic::Ordering::Acquire => 2,
+            atomic::Ordering::AcqRel => 3,
+            atomic::Ordering::SeqCst => 4,
+            _ => return Err(EncodeError::Other("Unknown atomic ordering")),
+        };
+        variant.encode(encoder)
+    }
+}
+
+impl<T> Encode for &T
 where
-    T: Encode,
+    T: Encode + ?Sized,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         T::encode(self, encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        T::size_hint::<C>(self)
+    }
 }
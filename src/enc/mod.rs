@@ -5,11 +5,16 @@ mod impl_tuples;
 mod impls;
 
 use self::write::Writer;
-use crate::{config::Config, error::EncodeError, utils::Sealed};
+use crate::{
+    config::{Config, InternalAlignmentConfig},
+    error::EncodeError,
+    utils::Sealed,
+};
+use core::ops::RangeBounds;
 
 pub mod write;
 
-pub use self::encoder::EncoderImpl;
+pub use self::encoder::{EncoderImpl, LengthPrefixedEncoder, LengthPrefixedWriter};
 
 /// Any source that can be encoded. This trait should be implemented for all types that you want to be able to use with any of the `encode_with` methods.
 ///
@@ -47,10 +52,31 @@ pub use self::encoder::EncoderImpl;
 /// ```
 ///
 /// From here you can add/remove fields, or add custom logic.
-
 pub trait Encode {
     /// Encode a given type.
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError>;
+
+    /// Returns the exact amount of bytes that [Encode::encode] writes with the config `C`, or `None` if this can not be calculated without encoding the value. The default implementation returns `None`.
+    ///
+    /// This is implemented for the primitive types and arrays. Collections, tuples and wrappers like `Option<T>` add up the hints of their items, and return `None` if the size of any item is unknown. [encode_to_vec] uses this to allocate its buffer up front.
+    ///
+    /// ```
+    /// use bincode::{config::{self, Config}, Encode};
+    ///
+    /// fn size_hint<T: Encode, C: Config>(value: &T, _config: C) -> Option<usize> {
+    ///     value.size_hint::<C>()
+    /// }
+    ///
+    /// assert_eq!(size_hint(&5u32, config::standard()), Some(1));
+    /// assert_eq!(size_hint(&5u32, config::legacy()), Some(4));
+    /// // The length of the array, followed by the items
+    /// assert_eq!(size_hint(&[1u16, 1000], config::standard()), Some(1 + 1 + 3));
+    /// ```
+    ///
+    /// [encode_to_vec]: crate::encode_to_vec
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Helper trait to encode basic types into.
@@ -66,9 +92,67 @@ pub trait Encoder: Sealed {
 
     /// Returns a reference to the config
     fn config(&self) -> &Self::C;
+
+    /// Returns the context that was given to [EncoderImpl::with_context]. This is always `Some`; a encoder created with [EncoderImpl::new] has `()` as its context.
+    ///
+    /// This can be used by `Encode` implementations that need external state, e.g. an interner that assigns indices to values. Use [Any::downcast_mut] or [Any::downcast_ref] to get the concrete type of the context.
+    ///
+    /// [Any::downcast_mut]: core::any::Any#method.downcast_mut
+    /// [Any::downcast_ref]: core::any::Any#method.downcast_ref
+    fn context(&mut self) -> Option<&mut dyn core::any::Any>;
+
+    /// Encode the length of a collection, with the same encoding that the built-in collections like `Vec<T>` use.
+    ///
+    /// This can be used together with [Decoder::decode_len] to implement `Encode` and `Decode` for custom collections.
+    ///
+    /// [Decoder::decode_len]: crate::de::Decoder::decode_len
+    fn encode_len(&mut self, len: usize) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+    {
+        encode_slice_len(self, len)
+    }
+
+    /// Encode `value`, prefixed by the amount of bytes it encodes to.
+    ///
+    /// If [Encode::size_hint] returns the size of `value`, the length is written directly followed by `value`. Otherwise `value` is encoded into a buffer first with [Encoder::encode_length_prefixed_with], which requires the `alloc` feature. Either way `value` is encoded only once, with the [context](Encoder::context) of this encoder.
+    ///
+    /// Readers can use [Decoder::decode_length_prefixed] to decode the value again, or skip over it entirely by decoding the length followed by [Decoder::skip_bytes].
+    ///
+    /// [Decoder::decode_length_prefixed]: crate::de::Decoder::decode_length_prefixed
+    /// [Decoder::skip_bytes]: crate::de::Decoder::skip_bytes
+    fn encode_length_prefixed<T: Encode + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+    {
+        match value.size_hint::<Self::C>() {
+            Some(len) => {
+                encode_slice_len(self, len)?;
+                value.encode(self)
+            }
+            None => self.encode_length_prefixed_with(|encoder| value.encode(encoder)),
+        }
+    }
+
+    /// Like [Encoder::encode_length_prefixed], but the value is encoded by calling `encode` with a [LengthPrefixedEncoder].
+    ///
+    /// The `LengthPrefixedEncoder` collects the encoded bytes in a buffer, which are then written to this encoder after their length. This allows encoding a length prefixed value that consists of multiple parts, e.g. the fields of an enum variant. Without the `alloc` feature the buffer can not hold any bytes, and writing to it returns [EncodeError::Other].
+    fn encode_length_prefixed_with<F>(&mut self, encode: F) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+        F: FnOnce(&mut LengthPrefixedEncoder<'_, Self::C>) -> Result<(), EncodeError>,
+    {
+        let config = *self.config();
+        let mut encoder = LengthPrefixedEncoder::new(config, self.context());
+        encode(&mut encoder)?;
+        let writer = encoder.into_writer();
+
+        encode_slice_len(self, writer.bytes().len())?;
+        self.writer().write(writer.bytes())
+    }
 }
 
-impl<'a, T> Encoder for &'a mut T
+impl<T> Encoder for &mut T
 where
     T: Encoder,
 {
@@ -83,6 +167,10 @@ where
     fn config(&self) -> &Self::C {
         T::config(self)
     }
+
+    fn context(&mut self) -> Option<&mut dyn core::any::Any> {
+        T::context(self)
+    }
 }
 
 /// Encode the variant of the given option. Will not encode the option itself.
@@ -102,3 +190,69 @@ pub(crate) fn encode_option_variant<E: Encoder, T>(
 pub(crate) fn encode_slice_len<E: Encoder>(encoder: &mut E, len: usize) -> Result<(), EncodeError> {
     (len as u64).encode(encoder)
 }
+
+/// Writes the padding of [Configuration::with_alignment] after a value of `len` bytes, so the output is a multiple of the alignment. Does nothing if no alignment is configured.
+///
+/// [Configuration::with_alignment]: crate::config::Configuration::with_alignment
+pub(crate) fn write_alignment_padding<C: Config, W: Writer>(
+    writer: &mut W,
+    len: usize,
+) -> Result<(), EncodeError> {
+    if let Some(alignment) = <C as InternalAlignmentConfig>::ALIGNMENT {
+        // The padding length byte counts towards the aligned length as well
+        let padding = (alignment - (len + 1) % alignment) % alignment;
+        writer.write(&[padding as u8])?;
+        writer.write_repeated(0, padding)?;
+    }
+    Ok(())
+}
+
+/// The size hint of a collection of `len` items, which is encoded as its length followed by the items with the given size hints
+pub(crate) fn collection_size_hint<C: Config>(
+    len: usize,
+    item_hints: impl IntoIterator<Item = Option<usize>>,
+) -> Option<usize> {
+    item_hints
+        .into_iter()
+        .try_fold((len as u64).size_hint::<C>()?, |size, hint| {
+            size.checked_add(hint?)
+        })
+}
+
+/// Encodes any range as the `(Bound<T>, Bound<T>)` of its start and end bound.
+///
+/// This allows every kind of range, e.g. `Range`, `RangeInclusive`, `RangeFrom` and `RangeFull`, to be stored in the same format. Use [decode_range_bounds] to decode the bounds again.
+///
+/// ```
+/// # use bincode::enc::{encode_range_bounds, Encode, Encoder};
+/// # use bincode::error::EncodeError;
+/// # use core::ops::Bound;
+/// struct Selection(std::ops::RangeInclusive<u32>);
+///
+/// impl Encode for Selection {
+///     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+///         encode_range_bounds(&self.0, encoder)
+///     }
+/// }
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = [0u8; 16];
+/// let len = bincode::encode_into_slice(Selection(2..=5), &mut bytes, config).unwrap();
+/// // `Included(2)`, followed by `Included(5)`
+/// assert_eq!(&bytes[..len], [1, 2, 1, 5]);
+/// // Equal to encoding the start and end bound of the range
+/// let mut bounds = [0u8; 16];
+/// let bounds_len = bincode::encode_into_slice((Bound::Included(2u32), Bound::Included(5u32)), &mut bounds, config).unwrap();
+/// assert_eq!(&bytes[..len], &bounds[..bounds_len]);
+/// ```
+///
+/// [decode_range_bounds]: crate::de::decode_range_bounds
+pub fn encode_range_bounds<T, R, E>(range: &R, encoder: &mut E) -> Result<(), EncodeError>
+where
+    T: Encode,
+    R: RangeBounds<T> + ?Sized,
+    E: Encoder,
+{
+    range.start_bound().encode(encoder)?;
+    range.end_bound().encode(encoder)
+}
@@ -10,6 +10,20 @@ use crate::error::EncodeError;
 pub trait Writer {
     /// Write `bytes` to the underlying writer. Exactly `bytes.len()` bytes must be written, or else an error should be returned.
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
+
+    /// Write `count` copies of `byte` to the underlying writer. This is useful for e.g. padding.
+    ///
+    /// The default implementation writes the bytes in small chunks. Writers that can extend their storage in one go should override this.
+    fn write_repeated(&mut self, byte: u8, count: usize) -> Result<(), EncodeError> {
+        let chunk = [byte; 64];
+        let mut remaining = count;
+        while remaining > 0 {
+            let len = remaining.min(chunk.len());
+            self.write(&chunk[..len])?;
+            remaining -= len;
+        }
+        Ok(())
+    }
 }
 
 impl<T: Writer> Writer for &mut T {
@@ -17,6 +31,11 @@ impl<T: Writer> Writer for &mut T {
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
         (**self).write(bytes)
     }
+
+    #[inline]
+    fn write_repeated(&mut self, byte: u8, count: usize) -> Result<(), EncodeError> {
+        (**self).write_repeated(byte, count)
+    }
 }
 
 /// A helper struct that implements `Writer` for a `&[u8]` slice.
@@ -50,13 +69,20 @@ impl<'storage> SliceWriter<'storage> {
     pub fn bytes_written(&self) -> usize {
         self.original_length - self.slice.len()
     }
+
+    fn buffer_full(&self, additional: usize) -> EncodeError {
+        EncodeError::BufferFull {
+            required: self.bytes_written().saturating_add(additional),
+            capacity: self.original_length,
+        }
+    }
 }
 
 impl<'storage> Writer for SliceWriter<'storage> {
     #[inline(always)]
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
         if bytes.len() > self.slice.len() {
-            return Err(EncodeError::UnexpectedEnd);
+            return Err(self.buffer_full(bytes.len()));
         }
         let (a, b) = core::mem::take(&mut self.slice).split_at_mut(bytes.len());
         a.copy_from_slice(bytes);
@@ -64,4 +90,139 @@ impl<'storage> Writer for SliceWriter<'storage> {
 
         Ok(())
     }
+
+    #[inline]
+    fn write_repeated(&mut self, byte: u8, count: usize) -> Result<(), EncodeError> {
+        if count > self.slice.len() {
+            return Err(self.buffer_full(count));
+        }
+        let (a, b) = core::mem::take(&mut self.slice).split_at_mut(count);
+        a.fill(byte);
+        self.slice = b;
+
+        Ok(())
+    }
+}
+
+/// A writer that counts how many bytes were written. This is useful for e.g. pre-allocating buffers before writing to them.
+///
+/// ```
+/// use bincode::enc::write::{Writer, SizeWriter};
+///
+/// let mut writer = SizeWriter::default();
+/// writer.write(&[1, 2, 3, 4, 5]).unwrap();
+/// writer.write_repeated(0, 10).unwrap();
+///
+/// assert_eq!(writer.bytes_written, 15);
+/// ```
+#[derive(Default)]
+pub struct SizeWriter {
+    /// the amount of written bytes so far
+    pub bytes_written: usize,
+}
+
+impl Writer for SizeWriter {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.bytes_written += bytes.len();
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn write_repeated(&mut self, _: u8, count: usize) -> Result<(), EncodeError> {
+        self.bytes_written += count;
+
+        Ok(())
+    }
+}
+
+/// A writer that forwards every write to two writers. This can be used to encode a value once, and send the bytes to two destinations.
+///
+/// Each write goes to `a` first, and then to `b`. If writing to `a` fails, `b` is not written to.
+///
+/// ```
+/// use bincode::enc::write::{SizeWriter, SliceWriter, TeeWriter};
+///
+/// let mut buffer = [0u8; 16];
+/// let mut writer = TeeWriter::new(SliceWriter::new(&mut buffer), SizeWriter::default());
+/// bincode::encode_into_writer(&(5u8, 1000u32), &mut writer, bincode::config::standard()).unwrap();
+///
+/// let (slice_writer, size_writer) = writer.into_inner();
+/// assert_eq!(slice_writer.bytes_written(), 4);
+/// assert_eq!(size_writer.bytes_written, 4);
+/// assert_eq!(buffer[..4], [5, 251, 232, 3]);
+/// ```
+pub struct TeeWriter<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Writer, B: Writer> TeeWriter<A, B> {
+    /// Create a writer that writes to both `a` and `b`.
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    /// Return the two inner writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: Writer, B: Writer> Writer for TeeWriter<A, B> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.a.write(bytes)?;
+        self.b.write(bytes)
+    }
+
+    #[inline]
+    fn write_repeated(&mut self, byte: u8, count: usize) -> Result<(), EncodeError> {
+        self.a.write_repeated(byte, count)?;
+        self.b.write_repeated(byte, count)
+    }
+}
+
+/// A writer that passes every chunk of bytes to a callback, instead of storing them. This can be used to stream the encoded bytes to e.g. a socket or a hasher, without needing a buffer for the whole value.
+///
+/// The chunks are passed on as they are written by the encoder, so they can be as small as a single byte. If the callback returns an error, encoding stops with that error.
+///
+/// ```
+/// use bincode::enc::write::CallbackWriter;
+///
+/// let mut chunks = 0;
+/// let mut sum = 0u32;
+/// let writer = CallbackWriter::new(|chunk: &[u8]| {
+///     chunks += 1;
+///     sum += chunk.iter().map(|b| *b as u32).sum::<u32>();
+///     Ok(())
+/// });
+/// bincode::encode_into_writer(&(5u8, 1000u32), writer, bincode::config::standard()).unwrap();
+///
+/// // The varint marker and the bytes of `1000u32` are written separately
+/// assert_eq!(chunks, 3);
+/// assert_eq!(sum, 5 + 251 + 232 + 3);
+/// ```
+pub struct CallbackWriter<F> {
+    callback: F,
+}
+
+impl<F: FnMut(&[u8]) -> Result<(), EncodeError>> CallbackWriter<F> {
+    /// Create a writer that calls `callback` with every chunk that is written.
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+
+    /// Return the callback.
+    pub fn into_inner(self) -> F {
+        self.callback
+    }
+}
+
+impl<F: FnMut(&[u8]) -> Result<(), EncodeError>> Writer for CallbackWriter<F> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        (self.callback)(bytes)
+    }
 }
@@ -7,6 +7,16 @@ pub enum EncodeError {
     /// The writer ran out of storage.
     UnexpectedEnd,
 
+    /// The slice that is being encoded into is too small. Encoding into a larger slice will succeed, or get further.
+    ///
+    /// This is returned by [SliceWriter](crate::enc::write::SliceWriter), e.g. when using [encode_into_slice](crate::encode_into_slice).
+    BufferFull {
+        /// The amount of bytes the slice needs to have for the current write to succeed
+        required: usize,
+        /// The length of the slice
+        capacity: usize,
+    },
+
     /// The RefCell<T> is already borrowed
     RefCellAlreadyBorrowed {
         /// The inner borrow error
@@ -22,7 +32,42 @@ pub enum EncodeError {
     #[cfg(feature = "alloc")]
     OtherString(alloc::string::String),
 
-    /// A `std::path::Path` was being encoded but did not contain a valid `&str` representation
+    /// A [FixedPoint](crate::FixedPoint) was encoded, but its value multiplied by the scale was not finite, or did not fit in an `i64`
+    FixedPointOverflow {
+        /// The value that was being encoded
+        value: f64,
+        /// The scale of the `FixedPoint`
+        scale: u32,
+    },
+
+    /// A [DurationMillis](crate::DurationMillis) was encoded, but its duration rounded to milliseconds did not fit in a `u32`
+    DurationMillisOverflow {
+        /// The duration that was being encoded
+        duration: core::time::Duration,
+    },
+
+    /// An enum variant marked with `#[bincode(skip_encoding)]` was encoded. These variants can not be encoded, and are never decoded.
+    UnsupportedVariant {
+        /// The type name of the enum
+        type_name: &'static str,
+        /// The name of the variant
+        name: &'static str,
+    },
+
+    /// [PartialStruct::try_with_field](crate::PartialStruct::try_with_field) was called with a name that is not one of the `#[bincode(optional)]` fields of the struct
+    UnknownOptionalField {
+        /// The type name of the struct
+        type_name: &'static str,
+    },
+
+    /// A trait object was encoded with a [TypeRegistry](crate::registry::TypeRegistry), but its concrete type was not registered
+    #[cfg(feature = "alloc")]
+    UnregisteredType {
+        /// The type name of the trait object
+        type_name: &'static str,
+    },
+
+    /// A `std::path::Path` was being encoded but did not contain a valid `&str` representation. This only happens on platforms other than unix and windows, where paths are encoded as strings.
     #[cfg(feature = "std")]
     InvalidPathCharacters,
 
@@ -44,6 +89,9 @@ pub enum EncodeError {
 
     /// The encoder tried to encode a `SystemTime`, but it was before `SystemTime::UNIX_EPOCH`
     #[cfg(feature = "std")]
+    #[deprecated(
+        note = "SystemTime is encoded with a sign byte, so times before UNIX_EPOCH can be encoded and this error is no longer returned"
+    )]
     InvalidSystemTime {
         /// The error that was thrown by the SystemTime
         inner: std::time::SystemTimeError,
@@ -54,6 +102,17 @@ pub enum EncodeError {
     #[cfg(feature = "serde")]
     /// A serde-specific error that occurred while decoding.
     Serde(crate::features::serde::EncodeError),
+
+    /// Encoding a field of a derived type failed.
+    ///
+    /// The `Encode` derive wraps the errors of every field with [EncodeError::with_field]. If the field is itself a derived type, `inner` can be another `InField`, so together they describe the path to the field that failed.
+    #[cfg(feature = "alloc")]
+    InField {
+        /// The name of the field, or its index for tuple fields
+        name: &'static str,
+        /// The error that occurred while encoding the field
+        inner: alloc::boxed::Box<EncodeError>,
+    },
 }
 
 impl core::fmt::Display for EncodeError {
@@ -63,6 +122,26 @@ impl core::fmt::Display for EncodeError {
     }
 }
 
+impl EncodeError {
+    /// Wrap this error in [EncodeError::InField], to indicate that it occurred while encoding the field `name`.
+    ///
+    /// Without the `alloc` feature, the error is returned unchanged.
+    pub fn with_field(self, name: &'static str) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::InField {
+                name,
+                inner: alloc::boxed::Box::new(self),
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = name;
+            self
+        }
+    }
+}
+
 /// Errors that can be encountered by decoding a type
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
@@ -77,9 +156,44 @@ pub enum DecodeError {
         additional: usize,
     },
 
+    /// [Decoder::finish](crate::de::Decoder::finish) was called, but the reader still has bytes left.
+    TrailingBytes,
+
     /// The given configuration limit was exceeded
     LimitExceeded,
 
+    /// The decoder tried to decode a collection with `len` elements, but the configuration or the capacity of the collection only allows up to `max` elements.
+    CollectionTooLong {
+        /// The length of the collection found in the binary format.
+        len: usize,
+        /// The maximum length allowed by the configuration or the collection.
+        max: usize,
+    },
+
+    /// The decoder tried to decode a type that allocates on the heap, but the configuration does not allow [allocation].
+    ///
+    /// [allocation]: crate::config::Configuration::with_no_allocation
+    AllocationDisallowed {
+        /// The type that was being decoded
+        type_name: &'static str,
+    },
+
+    /// The padding after a value that was encoded with an [alignment] is invalid. Either the amount of padding is not less than the alignment, or the padding contains bytes that are not zero.
+    ///
+    /// [alignment]: crate::config::Configuration::with_alignment
+    InvalidAlignmentPadding {
+        /// The alignment of the configuration.
+        alignment: usize,
+    },
+
+    /// The decoder tried to decode a value that is nested deeper than the configured [max depth].
+    ///
+    /// [max depth]: crate::config::Configuration::with_max_depth
+    DepthLimitExceeded {
+        /// The maximum depth allowed by the configuration.
+        max: usize,
+    },
+
     /// Invalid type was found. The decoder tried to read type `expected`, but found type `found` instead.
     InvalidIntegerType {
         /// The type that was being read from the reader
@@ -102,8 +216,8 @@ pub enum DecodeError {
         /// The variants that are allowed
         allowed: AllowedEnumVariants,
 
-        /// The index of the enum that the decoder encountered
-        found: u32,
+        /// The index of the enum that the decoder encountered. For a derived enum with a `#[repr(..)]`, this is the discriminant that was decoded, where a discriminant of a `#[repr(u128)]` enum above `i128::MAX` wraps around.
+        found: i128,
     },
 
     /// The decoder tried to decode a `str`, but an utf8 error was encountered.
@@ -115,6 +229,15 @@ pub enum DecodeError {
     /// The decoder tried to decode a `bool` and failed. The given value is what is actually read.
     InvalidBooleanValue(u8),
 
+    /// The decoder tried to decode a [BitPacked], but the last byte has bits set after the last bool.
+    ///
+    /// [BitPacked]: crate::BitPacked
+    #[cfg(feature = "alloc")]
+    InvalidBitPadding {
+        /// The last byte that was read
+        byte: u8,
+    },
+
     /// The decoder tried to decode an array of length `required`, but the binary data contained an array of length `found`.
     ArrayLengthMismatch {
         /// The length of the array required by the rust type.
@@ -131,6 +254,23 @@ pub enum DecodeError {
     /// cause this error.
     OutsideUsizeRange(u64),
 
+    /// The decoder tried to decode the length of a collection, but the length does not fit in a `usize` on this target.
+    ///
+    /// The length is rejected instead of truncated, which would decode e.g. a length over `u32::MAX` as a much smaller length on a 32 bit target.
+    IntegerOverflow {
+        /// The length that was decoded
+        value: u64,
+    },
+
+    /// The decoder tried to decode a LEB128 integer with [leb128::decode_u64] or [leb128::decode_i64], but the value does not fit in 64 bits.
+    ///
+    /// [leb128::decode_u64]: crate::leb128::decode_u64
+    /// [leb128::decode_i64]: crate::leb128::decode_i64
+    Leb128Overflow,
+
+    /// A varint was encoded with more bytes than needed for its value, e.g. `5` encoded with the `u16` marker byte. Every value has exactly one valid varint encoding.
+    NonCanonicalVarint,
+
     /// Tried to decode an enum with no variants
     EmptyEnum {
         /// The type that was being decoded
@@ -147,13 +287,72 @@ pub enum DecodeError {
         nanos: u32,
     },
 
+    /// The decoder tried to decode a map with keys that are not in strictly increasing order. This is only checked if [Configuration::with_strict_map_ordering] is used.
+    ///
+    /// [Configuration::with_strict_map_ordering]: crate::config::Configuration::with_strict_map_ordering
+    InvalidMapOrdering {
+        /// The index of the entry whose key is not greater than the key of the previous entry
+        index: usize,
+    },
+
+    /// The decoder tried to decode a [SparseVec] with an entry whose index is not less than the length of the vector, or not greater than the index of the previous entry.
+    ///
+    /// [SparseVec]: crate::SparseVec
+    #[cfg(feature = "alloc")]
+    InvalidSparseIndex {
+        /// The position of the invalid entry
+        position: usize,
+        /// The index of the invalid entry
+        index: usize,
+    },
+
+    /// The decoder tried to decode a struct with `#[bincode(version = N)]`, but the stored version is newer than `N`.
+    UnsupportedVersion {
+        /// The version that was stored in the data
+        found: u8,
+        /// The latest version that the struct supports
+        latest: u8,
+    },
+
+    /// The checksum that was stored after the data is different from the checksum of the data. See the [checksum](crate::checksum) module.
+    ChecksumMismatch {
+        /// The checksum that was stored after the data
+        stored: u32,
+        /// The checksum of the data that was read
+        computed: u32,
+    },
+
+    /// The decoder tried to decode a struct with `#[bincode(optional)]` fields, but the presence mask has bits set for fields that the struct does not have.
+    InvalidPresenceMask {
+        /// The type name of the struct that was being decoded
+        type_name: &'static str,
+        /// The presence mask that was found
+        mask: u64,
+    },
+
+    /// The decoder tried to decode a trait object with a [TypeRegistry](crate::registry::TypeRegistry), but no type was registered with the encoded tag
+    #[cfg(feature = "alloc")]
+    UnregisteredTag {
+        /// The tag that was found
+        tag: u32,
+    },
+
     /// The decoder tried to decode a SystemTime and overflowed
     InvalidSystemTime {
-        /// The duration which could not have been added to
+        /// The duration which could not have been added to or subtracted from
         /// [`UNIX_EPOCH`](std::time::SystemTime::UNIX_EPOCH)
         duration: core::time::Duration,
     },
 
+    /// The decoder tried to decode a [SystemTimeNanos], but the amount of nanoseconds is outside of the range of `SystemTime`
+    ///
+    /// [SystemTimeNanos]: crate::SystemTimeNanos
+    #[cfg(feature = "std")]
+    InvalidSystemTimeNanos {
+        /// The nanoseconds since [`UNIX_EPOCH`](std::time::SystemTime::UNIX_EPOCH) that were found
+        nanos: i128,
+    },
+
     /// The decoder tried to decode a `CString`, but the incoming data contained a 0 byte
     #[cfg(feature = "std")]
     CStringNulError {
@@ -161,6 +360,36 @@ pub enum DecodeError {
         inner: std::ffi::NulError,
     },
 
+    /// The decoder tried to decode an `OsString` on windows, but the incoming data was not valid [WTF-8](https://simonsapin.github.io/wtf-8/). This happens when reading a path that was encoded on unix, and contains bytes that are not valid unicode.
+    #[cfg(feature = "std")]
+    InvalidWtf8 {
+        /// The index of the first byte that is not valid WTF-8
+        index: usize,
+    },
+
+    /// An uncommon error occurred, see the inner text for more information
+    Other(&'static str),
+
+    /// The decoder tried to allocate a container, but the allocation failed.
+    ///
+    /// This usually means that the encoded length of the container is invalid. Consider setting a limit with [Configuration::with_limit] to reject these lengths before allocating.
+    ///
+    /// [Configuration::with_limit]: crate::config::Configuration::with_limit
+    #[cfg(feature = "alloc")]
+    OutOfMemory {
+        /// The inner exception
+        inner: alloc::collections::TryReserveError,
+    },
+
+    /// The decoder tried to allocate a `hashbrown::HashMap` or `hashbrown::HashSet`, but the allocation failed.
+    ///
+    /// This is the same as [DecodeError::OutOfMemory], for the error type of the `hashbrown` crate.
+    #[cfg(feature = "hashbrown")]
+    HashbrownOutOfMemory {
+        /// The inner exception
+        inner: hashbrown::TryReserveError,
+    },
+
     /// An uncommon error occurred, see the inner text for more information
     #[cfg(feature = "alloc")]
     OtherString(alloc::string::String),
@@ -194,6 +423,23 @@ impl DecodeError {
     }
 }
 
+/// Errors that can be encountered by [transcode](crate::transcode)
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub enum TranscodeError {
+    /// The input could not be decoded with the original configuration
+    Decode(DecodeError),
+    /// The value could not be encoded with the new configuration
+    Encode(EncodeError),
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 /// Indicates which enum variants are allowed
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
@@ -202,7 +448,7 @@ pub enum AllowedEnumVariants {
     #[allow(missing_docs)]
     Range { min: u32, max: u32 },
     /// Each one of these values is allowed
-    Allowed(&'static [u32]),
+    Allowed(&'static [i128]),
 }
 
 /// Integer types. Used by [DecodeError]. These types have no purpose other than being shown in errors.
@@ -1,9 +1,17 @@
 use crate::{de::Decode, enc::Encode};
-use core::sync::atomic::{
-    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
-    AtomicU64, AtomicU8, AtomicUsize, Ordering,
-};
-
+use core::sync::atomic::Ordering;
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicBool, AtomicI8, AtomicU8};
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::{AtomicI16, AtomicU16};
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicI32, AtomicU32};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicIsize, AtomicUsize};
+
+#[cfg(target_has_atomic = "8")]
 impl Encode for AtomicBool {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -13,12 +21,14 @@ impl Encode for AtomicBool {
     }
 }
 
+#[cfg(target_has_atomic = "8")]
 impl Decode for AtomicBool {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicBool::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "8")]
 impl Encode for AtomicU8 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -28,12 +38,14 @@ impl Encode for AtomicU8 {
     }
 }
 
+#[cfg(target_has_atomic = "8")]
 impl Decode for AtomicU8 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicU8::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "16")]
 impl Encode for AtomicU16 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -43,12 +55,14 @@ impl Encode for AtomicU16 {
     }
 }
 
+#[cfg(target_has_atomic = "16")]
 impl Decode for AtomicU16 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicU16::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "32")]
 impl Encode for AtomicU32 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -58,12 +72,14 @@ impl Encode for AtomicU32 {
     }
 }
 
+#[cfg(target_has_atomic = "32")]
 impl Decode for AtomicU32 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicU32::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Encode for AtomicU64 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -73,12 +89,14 @@ impl Encode for AtomicU64 {
     }
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Decode for AtomicU64 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicU64::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl Encode for AtomicUsize {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -88,12 +106,14 @@ impl Encode for AtomicUsize {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl Decode for AtomicUsize {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicUsize::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "8")]
 impl Encode for AtomicI8 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -103,12 +123,14 @@ impl Encode for AtomicI8 {
     }
 }
 
+#[cfg(target_has_atomic = "8")]
 impl Decode for AtomicI8 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicI8::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "16")]
 impl Encode for AtomicI16 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -118,12 +140,14 @@ impl Encode for AtomicI16 {
     }
 }
 
+#[cfg(target_has_atomic = "16")]
 impl Decode for AtomicI16 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicI16::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "32")]
 impl Encode for AtomicI32 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -133,12 +157,14 @@ impl Encode for AtomicI32 {
     }
 }
 
+#[cfg(target_has_atomic = "32")]
 impl Decode for AtomicI32 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicI32::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Encode for AtomicI64 {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -148,12 +174,14 @@ impl Encode for AtomicI64 {
     }
 }
 
+#[cfg(target_has_atomic = "64")]
 impl Decode for AtomicI64 {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicI64::new(Decode::decode(decoder)?))
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl Encode for AtomicIsize {
     fn encode<E: crate::enc::Encoder>(
         &self,
@@ -163,6 +191,7 @@ impl Encode for AtomicIsize {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl Decode for AtomicIsize {
     fn decode<D: crate::de::Decoder>(decoder: &mut D) -> Result<Self, crate::error::DecodeError> {
         Ok(AtomicIsize::new(Decode::decode(decoder)?))
@@ -1,10 +1,14 @@
 use crate::{
-    de::{Decode, Decoder},
-    enc::{self, Encode, Encoder},
-    error::{DecodeError, EncodeError},
+    config::{InternalMapOrderingConfig, InternalStringLenConfig},
+    de::{
+        read::{BorrowReader, Reader, SliceReader},
+        BorrowDecode, BorrowDecoder, Decode, Decoder, DecoderImpl,
+    },
+    enc::{self, write::Writer, Encode, Encoder, EncoderImpl},
+    error::{DecodeError, EncodeError, TranscodeError},
     Config,
 };
-#[cfg(feature = "atomic")]
+#[cfg(all(feature = "atomic", target_has_atomic = "ptr"))]
 use alloc::sync::Arc;
 use alloc::{
     borrow::{Cow, ToOwned},
@@ -21,6 +25,18 @@ pub(crate) struct VecWriter {
 }
 
 impl VecWriter {
+    /// Create a writer that can hold `capacity` bytes before it reallocates
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The amount of bytes that have been written so far
+    pub(crate) fn bytes_written(&self) -> usize {
+        self.inner.len()
+    }
+
     // May not be used in all feature combinations
     #[allow(dead_code)]
     pub(crate) fn collect(self) -> Vec<u8> {
@@ -33,6 +49,11 @@ impl enc::write::Writer for VecWriter {
         self.inner.extend_from_slice(bytes);
         Ok(())
     }
+
+    fn write_repeated(&mut self, byte: u8, count: usize) -> Result<(), EncodeError> {
+        self.inner.resize(self.inner.len() + count, byte);
+        Ok(())
+    }
 }
 
 /// Encode the given value into a `Vec<u8>` with the given `Config`. See the [config] module for more information.
@@ -40,10 +61,88 @@ impl enc::write::Writer for VecWriter {
 /// [config]: config/index.html
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn encode_to_vec<E: enc::Encode, C: Config>(val: E, config: C) -> Result<Vec<u8>, EncodeError> {
-    let writer = VecWriter::default();
+    let writer = VecWriter::with_capacity(val.size_hint::<C>().unwrap_or(0));
     let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
     val.encode(&mut encoder)?;
-    Ok(encoder.into_writer().inner)
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.inner)
+}
+
+/// Decode a `T` from `input` with `from_config`, and encode it again with `to_config`.
+///
+/// This is useful to migrate data to a different configuration. Any bytes in `input` after the encoded `T` are ignored.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # fn main() {
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let old_config = bincode::config::legacy().with_big_endian();
+/// let new_config = bincode::config::standard();
+///
+/// let old = bincode::encode_to_vec(Point { x: 1, y: 300 }, old_config).unwrap();
+/// assert_eq!(old, [0, 0, 0, 1, 0, 0, 1, 44]);
+///
+/// let new = bincode::transcode::<Point, _, _>(&old, old_config, new_config).unwrap();
+/// assert_eq!(new, [1, 251, 44, 1]);
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn transcode<T, C1, C2>(
+    input: &[u8],
+    from_config: C1,
+    to_config: C2,
+) -> Result<Vec<u8>, TranscodeError>
+where
+    T: Decode + Encode,
+    C1: Config,
+    C2: Config,
+{
+    let (value, _): (T, usize) =
+        crate::decode_from_slice(input, from_config).map_err(TranscodeError::Decode)?;
+    encode_to_vec(value, to_config).map_err(TranscodeError::Encode)
+}
+
+/// Decode a `Vec<T>` from the given [Reader] into `scratch`, reusing the allocation of `scratch`.
+///
+/// `scratch` is cleared and then filled with the decoded items, see [Decode::decode_in_place]. When decoding a sequence of messages in a loop, passing the same `scratch` every time avoids allocating a new `Vec` for every message. If decoding fails, `scratch` keeps its allocation, but its items are unspecified.
+///
+/// ```
+/// use bincode::de::read::SliceReader;
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = bincode::encode_to_vec(vec![1u32, 2, 3], config).unwrap();
+/// bytes.extend(bincode::encode_to_vec(vec![4u32, 5], config).unwrap());
+///
+/// let mut reader = SliceReader::new(&bytes);
+/// let mut scratch: Vec<u32> = Vec::new();
+/// bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+/// assert_eq!(scratch, [1, 2, 3]);
+/// bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+/// assert_eq!(scratch, [4, 5]);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn decode_from_reader_into<T: Decode, R: Reader, C: Config>(
+    scratch: &mut Vec<T>,
+    reader: R,
+    config: C,
+) -> Result<(), DecodeError> {
+    let mut decoder = crate::de::DecoderImpl::<_, C>::new(reader, config);
+    Vec::<T>::decode_in_place(&mut decoder, scratch)?;
+    decoder.debug_assert_claims_balanced();
+    Ok(())
 }
 
 impl<T> Decode for BinaryHeap<T>
@@ -51,17 +150,21 @@ where
     T: Decode + Ord,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
         let mut map = BinaryHeap::with_capacity(len);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.push(key);
-        }
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                let key = T::decode(decoder)?;
+                map.push(key);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
@@ -77,6 +180,10 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        enc::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
+    }
 }
 
 impl<K, V> Decode for BTreeMap<K, V>
@@ -85,18 +192,29 @@ where
     V: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<(K, V)>(len)?;
 
         let mut map = BTreeMap::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let key = K::decode(decoder)?;
-            let value = V::decode(decoder)?;
-            map.insert(key, value);
-        }
+        decoder.decode_nested_with(|decoder| {
+            for index in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
+
+                let key = K::decode(decoder)?;
+                if D::C::STRICT_MAP_ORDERING {
+                    if let Some((last, _)) = map.last_key_value() {
+                        if key <= *last {
+                            return Err(DecodeError::InvalidMapOrdering { index });
+                        }
+                    }
+                }
+                let value = V::decode(decoder)?;
+                map.insert(key, value);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
@@ -114,6 +232,14 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        enc::collection_size_hint::<C>(
+            self.len(),
+            self.iter()
+                .map(|(key, val)| key.size_hint::<C>()?.checked_add(val.size_hint::<C>()?)),
+        )
+    }
 }
 
 impl<T> Decode for BTreeSet<T>
@@ -121,17 +247,21 @@ where
     T: Decode + Ord,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
         let mut map = BTreeSet::new();
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.insert(key);
-        }
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                let key = T::decode(decoder)?;
+                map.insert(key);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
@@ -147,6 +277,10 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        enc::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
+    }
 }
 
 impl<T> Decode for VecDeque<T>
@@ -154,17 +288,23 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
-        let mut map = VecDeque::with_capacity(len);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.push_back(key);
-        }
+        let mut map = VecDeque::new();
+        map.try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                let key = T::decode(decoder)?;
+                map.push_back(key);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
@@ -180,6 +320,10 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        enc::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
+    }
 }
 
 impl<T> Decode for Vec<T>
@@ -187,18 +331,100 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let len = crate::de::decode_slice_len(decoder)?;
-        decoder.claim_container_read::<T>(len)?;
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let mut vec = Vec::new();
+        Self::decode_in_place(decoder, &mut vec)?;
+        Ok(vec)
+    }
+
+    /// `place` is cleared and filled with the decoded items, reusing its allocation. If the configuration does not allow allocation, this only fails if `place` does not have the capacity for the items.
+    fn decode_in_place<D: Decoder>(decoder: &mut D, place: &mut Self) -> Result<(), DecodeError> {
+        decoder.decode_nested_with(|decoder| decode_vec_items_into(decoder, place))
+    }
+}
 
-        let mut vec = Vec::with_capacity(len);
+/// Read `bytes` from the reader of `decoder` at once. If the input ends early, this fails the same as decoding the bytes one at a time, which stops at the first missing byte.
+fn read_bytes<D: Decoder>(decoder: &mut D, bytes: &mut [u8]) -> Result<(), DecodeError> {
+    decoder.reader().read(bytes).map_err(|e| match e {
+        DecodeError::UnexpectedEnd { .. } => DecodeError::UnexpectedEnd { additional: 1 },
+        e => e,
+    })
+}
+
+fn decode_vec_items_into<T: Decode, D: Decoder>(
+    decoder: &mut D,
+    vec: &mut Vec<T>,
+) -> Result<(), DecodeError> {
+    let len = crate::de::decode_slice_len(decoder)?;
+    if vec.capacity() < len {
+        crate::de::check_allocation::<Vec<T>, _>(decoder)?;
+    }
+    vec.clear();
+
+    // Optimize for `Vec<u8>`, which is encoded as its raw bytes
+    if crate::utils::is_same_type::<T, u8>() {
+        decoder.claim_bytes_read(len)?;
+        vec.try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        vec.resize_with(len, || {
+            // Safety: T is u8, for which all bit patterns are valid
+            unsafe { core::mem::zeroed() }
+        });
+        // Safety: T is u8, so the vec contains exactly `len` bytes
+        let bytes = unsafe { core::slice::from_raw_parts_mut(vec.as_mut_ptr() as *mut u8, len) };
+        if let Err(e) = read_bytes(decoder, bytes) {
+            vec.clear();
+            return Err(e);
+        }
+        return Ok(());
+    }
+
+    decoder.claim_container_read::<T>(len)?;
+
+    vec.try_reserve(len)
+        .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+    for _ in 0..len {
+        // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+        decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+        vec.push(T::decode(decoder)?);
+    }
+    Ok(())
+}
+
+impl<'a, 'de: 'a> BorrowDecode<'de> for Vec<&'a str> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        borrow_decode_vec(decoder)
+    }
+}
+
+impl<'a, 'de: 'a> BorrowDecode<'de> for Vec<&'a [u8]> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        borrow_decode_vec(decoder)
+    }
+}
+
+/// Decode a `Vec<T>` of items that borrow from the input, e.g. `Vec<&str>`. Only the `Vec` itself is allocated.
+fn borrow_decode_vec<'de, T: BorrowDecode<'de>, D: BorrowDecoder<'de>>(
+    decoder: &mut D,
+) -> Result<Vec<T>, DecodeError> {
+    crate::de::check_allocation::<Vec<T>, _>(decoder)?;
+    let len = crate::de::decode_slice_len(decoder)?;
+    decoder.claim_container_read::<T>(len)?;
+
+    let mut vec = Vec::new();
+    vec.try_reserve(len)
+        .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+    decoder.decode_nested_with(|decoder| {
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<T>());
 
-            vec.push(T::decode(decoder)?);
+            vec.push(T::borrow_decode(decoder)?);
         }
-        Ok(vec)
-    }
+        Ok(())
+    })?;
+    Ok(vec)
 }
 
 impl<T> Encode for Vec<T>
@@ -206,24 +432,65 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for item in self.iter() {
-            item.encode(encoder)?;
-        }
-        Ok(())
+        self.as_slice().encode(encoder)
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.as_slice().size_hint::<C>()
     }
 }
 
 impl Decode for String {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let bytes = Vec::<u8>::decode(decoder)?;
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        if D::C::STRING_LEN_IN_CHARS {
+            let len = crate::de::decode_slice_len(decoder)?;
+            decoder.claim_container_read::<u8>(len)?;
+
+            let mut string = String::new();
+            string
+                .try_reserve(len)
+                .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+            for _ in 0..len {
+                // `char::decode` claims the bytes it reads itself
+                decoder.unclaim_bytes_read(1);
+
+                string.push(char::decode(decoder)?);
+            }
+            return Ok(string);
+        }
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_bytes_read(len)?;
+
+        // If the reader has the bytes in a buffer, validate them there and copy them into the string only once
+        if let Some(bytes) = decoder.reader().peek_read(len) {
+            let str = core::str::from_utf8(bytes).map_err(DecodeError::Utf8)?;
+            let mut string = String::new();
+            string
+                .try_reserve(len)
+                .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+            string.push_str(str);
+            decoder.reader().consume(len);
+            return Ok(string);
+        }
+
+        let mut bytes = Vec::new();
+        bytes
+            .try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        bytes.resize(len, 0);
+        read_bytes(decoder, &mut bytes)?;
         String::from_utf8(bytes).map_err(|e| DecodeError::Utf8(e.utf8_error()))
     }
 }
 
 impl Encode for String {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        self.as_bytes().encode(encoder)
+        self.as_str().encode(encoder)
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.as_str().size_hint::<C>()
     }
 }
 
@@ -232,7 +499,8 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let t = decoder.decode_nested_with(T::decode)?;
         Ok(Box::new(t))
     }
 }
@@ -244,6 +512,10 @@ where
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         T::encode(self, encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        T::size_hint::<C>(self)
+    }
 }
 
 impl<T> Decode for Box<[T]>
@@ -251,6 +523,7 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let vec = Vec::decode(decoder)?;
         Ok(vec.into_boxed_slice())
     }
@@ -261,11 +534,11 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        crate::enc::encode_slice_len(encoder, self.len())?;
-        for item in self.iter() {
-            item.encode(encoder)?;
-        }
-        Ok(())
+        <[T]>::encode(self, encoder)
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        <[T]>::size_hint::<C>(self)
     }
 }
 
@@ -283,6 +556,9 @@ where
 //     }
 // }
 
+/// A `Cow<T>` is decoded as `T::Owned`, e.g. a `Cow<str>` is decoded as a `String`, and always returns `Cow::Owned`.
+///
+/// This also applies when decoding from a slice: a `Cow` never borrows from the input, because it can not implement both [Decode] and its own [BorrowDecode]. Decode a `&str` or `&[u8]` instead to borrow from the input.
 impl<'cow, T> Decode for Cow<'cow, T>
 where
     T: ToOwned + ?Sized,
@@ -294,6 +570,7 @@ where
     }
 }
 
+/// A `Cow<T>` is encoded the same as `T`, whether it is borrowed or owned. This works for sized types, and for `str`, `[T]`, `Path`, `OsStr` and `CStr`.
 impl<'cow, T> Encode for Cow<'cow, T>
 where
     T: ToOwned + ?Sized,
@@ -302,6 +579,10 @@ where
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.as_ref().encode(encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.as_ref().size_hint::<C>()
+    }
 }
 
 impl<T> Decode for Rc<T>
@@ -309,7 +590,8 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let t = decoder.decode_nested_with(T::decode)?;
         Ok(Rc::new(t))
     }
 }
@@ -321,20 +603,25 @@ where
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         T::encode(self, encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        T::size_hint::<C>(self)
+    }
 }
 
-#[cfg(feature = "atomic")]
+#[cfg(all(feature = "atomic", target_has_atomic = "ptr"))]
 impl<T> Decode for Arc<T>
 where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let t = T::decode(decoder)?;
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let t = decoder.decode_nested_with(T::decode)?;
         Ok(Arc::new(t))
     }
 }
 
-#[cfg(feature = "atomic")]
+#[cfg(all(feature = "atomic", target_has_atomic = "ptr"))]
 impl<T> Encode for Arc<T>
 where
     T: Encode,
@@ -342,4 +629,245 @@ where
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         T::encode(self, encoder)
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        T::size_hint::<C>(self)
+    }
+}
+
+/// A wrapper around [`BinaryHeap`] that always encodes its items in the same order.
+///
+/// A `BinaryHeap` is encoded in its internal heap order, which depends on the order in which the items were pushed. `SortedHeap` encodes the items in ascending order instead, so logically equal heaps always produce identical output. This collects and sorts references to all items, which takes `O(n log n)` time and an allocation of `n` pointers on every encode.
+///
+/// The encoded format is the same as that of `BinaryHeap`, so the data can be decoded as either type. Decoding rebuilds the heap from the items.
+///
+/// ```
+/// use bincode::SortedHeap;
+/// use std::collections::BinaryHeap;
+///
+/// let first = BinaryHeap::from(vec![3u8, 1, 2]);
+/// let second = BinaryHeap::from(vec![2u8, 3, 1]);
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(SortedHeap(first), config).unwrap();
+/// assert_eq!(bytes, [3, 1, 2, 3]);
+/// assert_eq!(bytes, bincode::encode_to_vec(SortedHeap(second), config).unwrap());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default)]
+pub struct SortedHeap<T: Ord>(pub BinaryHeap<T>);
+
+impl<T> Encode for SortedHeap<T>
+where
+    T: Encode + Ord,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let mut items: Vec<&T> = self.0.iter().collect();
+        items.sort_unstable();
+
+        crate::enc::encode_slice_len(encoder, items.len())?;
+        for item in items {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        self.0.size_hint::<C>()
+    }
+}
+
+impl<T> Decode for SortedHeap<T>
+where
+    T: Decode + Ord,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        BinaryHeap::decode(decoder).map(Self)
+    }
+}
+
+/// A wrapper around [`BTreeMap`] that is encoded together with an index, so a single value can be looked up in the encoded bytes without decoding the rest of the map.
+///
+/// An `IndexedMap` is encoded as the byte length of the map, followed by the map in the same format as a `BTreeMap`, followed by the index. The index is a collection of `(key_hash, byte_offset)` tuples of `u64`s, sorted by hash. `key_hash` is the 64-bit FNV-1a hash of the encoded key, and `byte_offset` is the position of the entry relative to the start of the map.
+///
+/// [IndexedMap::lookup] uses the index to find a key, and only decodes the matching entries:
+///
+/// ```
+/// use bincode::IndexedMap;
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert(String::from("apple"), 3u32);
+/// map.insert(String::from("pear"), 5u32);
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(IndexedMap(map), config).unwrap();
+///
+/// let pears: Option<u32> = IndexedMap::lookup(&bytes, &String::from("pear"), config).unwrap();
+/// assert_eq!(pears, Some(5));
+/// let plums: Option<u32> = IndexedMap::lookup(&bytes, &String::from("plum"), config).unwrap();
+/// assert_eq!(plums, None);
+/// ```
+///
+/// Encoding writes the map into a buffer first, to calculate its length and the offsets of the entries. Decoding an `IndexedMap` decodes the whole map and skips the index.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K, V> IndexedMap<K, V> {
+    /// Looks up `key` in the bytes of an encoded `IndexedMap`, and decodes only its value. Returns `None` if the map does not contain `key`.
+    ///
+    /// `src` must start with the `IndexedMap`, and `config` must be the config that it was encoded with.
+    pub fn lookup<C: Config>(src: &[u8], key: &K, config: C) -> Result<Option<V>, DecodeError>
+    where
+        K: Encode + Decode + PartialEq,
+        V: Decode,
+    {
+        let mut decoder = DecoderImpl::new(SliceReader::new(src), config);
+        let map_len = crate::de::len_from_u64(u64::decode(&mut decoder)?)?;
+        let map = decoder.borrow_reader().take_bytes(map_len)?;
+        let index = Vec::<(u64, u64)>::decode(&mut decoder)?;
+
+        let mut key_writer = HashWriter::default();
+        key.encode(&mut EncoderImpl::new(&mut key_writer, config))
+            .map_err(|_| {
+                DecodeError::Other("The key of an IndexedMap lookup could not be encoded")
+            })?;
+
+        // Different keys can have the same hash, so every entry with this hash has to be checked
+        let start = index.partition_point(|(hash, _)| *hash < key_writer.hash);
+        for (_, offset) in index[start..]
+            .iter()
+            .take_while(|(hash, _)| *hash == key_writer.hash)
+        {
+            let entry = crate::de::usize_from_u64(*offset)
+                .ok()
+                .and_then(|offset| map.get(offset..))
+                .ok_or(DecodeError::Other(
+                    "The index of an IndexedMap points outside of the map",
+                ))?;
+            let mut decoder = DecoderImpl::new(SliceReader::new(entry), config);
+            if K::decode(&mut decoder)? == *key {
+                return V::decode(&mut decoder).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<K, V> Encode for IndexedMap<K, V>
+where
+    K: Encode + Ord,
+    V: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        // The map is encoded into the buffer of the length prefix, so the offsets and the hashes of the keys are taken from the encoded bytes
+        let mut index = Vec::with_capacity(self.0.len());
+        encoder.encode_length_prefixed_with(|encoder| {
+            crate::enc::encode_slice_len(encoder, self.0.len())?;
+            for (key, value) in &self.0 {
+                let offset = encoder.writer().bytes().len();
+                key.encode(encoder)?;
+                let hash = fnv1a(FNV_OFFSET_BASIS, &encoder.writer().bytes()[offset..]);
+                index.push((hash, offset as u64));
+                value.encode(encoder)?;
+            }
+            Ok(())
+        })?;
+        index.sort_unstable();
+        index.encode(encoder)
+    }
+}
+
+impl<K, V> Decode for IndexedMap<K, V>
+where
+    K: Decode + Ord,
+    V: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let map = decoder.decode_length_prefixed::<BTreeMap<K, V>>()?;
+        Vec::<(u64, u64)>::decode(decoder)?;
+        Ok(Self(map))
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continues the 64-bit FNV-1a `hash` with `bytes`
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A writer that calculates the 64-bit FNV-1a hash of the bytes that are written to it.
+struct HashWriter {
+    hash: u64,
+}
+
+impl Default for HashWriter {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Writer for HashWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.hash = fnv1a(self.hash, bytes);
+        Ok(())
+    }
+}
+
+/// A wrapper around `Vec<bool>` that packs 8 bools into every byte.
+///
+/// A `Vec<bool>` encodes every bool as a separate byte. `BitPacked` writes the amount of bools as the length, followed by the bools packed into `len.div_ceil(8)` bytes. The first bool is stored in the least significant bit of the first byte. Unused bits in the last byte are zero, decoding returns [DecodeError::InvalidBitPadding] if they are not.
+///
+/// ```
+/// use bincode::BitPacked;
+///
+/// let flags = BitPacked(vec![true, false, true, true, false, false, false, false, true]);
+/// let bytes = bincode::encode_to_vec(&flags, bincode::config::standard()).unwrap();
+/// assert_eq!(bytes, [9, 0b0000_1101, 0b0000_0001]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitPacked(pub Vec<bool>);
+
+impl Encode for BitPacked {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.0.len())?;
+        for chunk in self.0.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (idx, bit)| byte | ((*bit as u8) << idx));
+            byte.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for BitPacked {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let len = crate::de::decode_slice_len(decoder)?;
+        let byte_len = len.div_ceil(8);
+        decoder.claim_bytes_read(byte_len)?;
+
+        let mut bits = Vec::new();
+        bits.try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        for idx in 0..byte_len {
+            let mut byte = [0u8];
+            decoder.reader().read(&mut byte)?;
+            let bits_in_byte = core::cmp::min(8, len - idx * 8);
+            if bits_in_byte < 8 && byte[0] >> bits_in_byte != 0 {
+                return Err(DecodeError::InvalidBitPadding { byte: byte[0] });
+            }
+            bits.extend((0..bits_in_byte).map(|bit| byte[0] & (1 << bit) != 0));
+        }
+        Ok(Self(bits))
+    }
 }
@@ -0,0 +1,41 @@
+use crate::{
+    de::{read::Reader, Decode, Decoder},
+    enc::{write::Writer, Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::vec::Vec;
+use bitvec::{field::BitField, order::Lsb0, vec::BitVec};
+
+/// A `BitVec` is encoded as its length in bits, followed by the bits packed into bytes. The first bit is stored in the least significant bit of the first byte.
+///
+/// If the length is not a multiple of 8, the unused bits of the last byte are written as zero, and ignored when decoding.
+impl Encode for BitVec<u8, Lsb0> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        // Not `as_raw_slice`, because the unused bits of the last byte may hold stale values
+        for chunk in self.chunks(8) {
+            encoder.writer().write(&[chunk.load_le::<u8>()])?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for BitVec<u8, Lsb0> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let len = crate::de::decode_slice_len(decoder)?;
+        let byte_len = len / 8 + usize::from(len % 8 != 0);
+        decoder.claim_bytes_read(byte_len)?;
+
+        let mut bytes = Vec::new();
+        bytes
+            .try_reserve(byte_len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        bytes.resize(byte_len, 0);
+        decoder.reader().read(&mut bytes)?;
+
+        let mut vec = BitVec::from_vec(bytes);
+        vec.truncate(len);
+        Ok(vec)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    config::Config,
+    de::Decode,
+    enc::Encode,
+    error::{DecodeError, EncodeError},
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::vec::Vec;
+
+/// Encode the given value into a gzip-compressed `Vec<u8>`. The value is compressed while it is being encoded, so the uncompressed bytes are never stored.
+///
+/// The compressed bytes can be decoded with [decode_from_slice_gzip]. To write the compressed bytes to a file or socket instead, pass a `flate2::write::GzEncoder` to [encode_into_std_write](crate::encode_into_std_write).
+///
+/// ```
+/// let numbers = vec![7u32; 1000];
+/// let config = bincode::config::standard();
+///
+/// let compressed = bincode::encode_to_vec_gzip(&numbers, config).unwrap();
+/// assert!(compressed.len() < bincode::encode_to_vec(&numbers, config).unwrap().len());
+///
+/// let decoded: Vec<u32> = bincode::decode_from_slice_gzip(&compressed, config).unwrap();
+/// assert_eq!(decoded, numbers);
+/// ```
+///
+/// See the [config](crate::config) module for more information on configurations.
+#[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+pub fn encode_to_vec_gzip<E: Encode, C: Config>(val: E, config: C) -> Result<Vec<u8>, EncodeError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let bytes_written = crate::encode_into_std_write(val, &mut encoder, config)?;
+    encoder.finish().map_err(|error| EncodeError::Io {
+        error,
+        index: bytes_written,
+    })
+}
+
+/// Decode type `D` from gzip-compressed bytes, like the bytes returned by [encode_to_vec_gzip]. The bytes are decompressed while the value is being decoded.
+///
+/// Because the decoded value can not borrow from the decompressed bytes, only types that implement [Decode] are supported. Bytes that are left over after the value are ignored.
+///
+/// **Note:** if the compressed bytes are corrupted, the error is reported as [DecodeError::UnexpectedEnd], like any other reader that fails with a `std::io::Error`.
+///
+/// See the [config](crate::config) module for more information on configurations.
+#[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+pub fn decode_from_slice_gzip<D: Decode, C: Config>(
+    src: &[u8],
+    config: C,
+) -> Result<D, DecodeError> {
+    crate::decode_from_std_read(&mut GzDecoder::new(src), config)
+}
@@ -0,0 +1,41 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use generic_array::{ArrayLength, GenericArray};
+
+/// A `GenericArray<T, N>` is encoded as its `N` elements. Because `N` is known at compile time, no length is written.
+impl<T, N> Encode for GenericArray<T, N>
+where
+    T: Encode,
+    N: ArrayLength,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, N> Decode for GenericArray<T, N>
+where
+    T: Decode,
+    N: ArrayLength,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decoder.claim_container_read::<T>(N::USIZE)?;
+
+        // `try_from_fallible_iter` drops the elements that are already decoded if an error occurs
+        let result = GenericArray::try_from_fallible_iter((0..N::USIZE).map(|_| {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+            T::decode(decoder)
+        }));
+
+        // The length is only wrong if `N::USIZE` does not match the amount of items in `(0..N::USIZE)`, which it always should
+        // So this unwrap should never occur
+        result.unwrap()
+    }
+}
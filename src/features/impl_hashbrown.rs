@@ -0,0 +1,113 @@
+use crate::{
+    config::Config,
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use core::hash::{BuildHasher, Hash};
+use hashbrown::{HashMap, HashSet};
+
+/// A `hashbrown::HashMap` is encoded the same as a `std::collections::HashMap`, with a length followed by its entries.
+impl<K, V, S> Encode for HashMap<K, V, S>
+where
+    K: Encode,
+    V: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for (k, v) in self.iter() {
+            Encode::encode(k, encoder)?;
+            Encode::encode(v, encoder)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        crate::enc::collection_size_hint::<C>(
+            self.len(),
+            self.iter()
+                .map(|(k, v)| k.size_hint::<C>()?.checked_add(v.size_hint::<C>()?)),
+        )
+    }
+}
+
+impl<K, V, S> Decode for HashMap<K, V, S>
+where
+    K: Decode + Eq + Hash,
+    V: Decode,
+    S: BuildHasher + Default,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_container_read::<(K, V)>(len)?;
+
+        // Every key and value that is not zero-sized is encoded as at least 1 byte
+        let min_entry_size = usize::from(core::mem::size_of::<K>() != 0)
+            + usize::from(core::mem::size_of::<V>() != 0);
+        let hash_builder: S = Default::default();
+        let mut map = HashMap::with_hasher(hash_builder);
+        map.try_reserve(crate::de::reserve_hint(decoder, len, min_entry_size))
+            .map_err(|inner| DecodeError::HashbrownOutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
+
+                let k = K::decode(decoder)?;
+                let v = V::decode(decoder)?;
+                map.insert(k, v);
+            }
+            Ok(())
+        })?;
+        Ok(map)
+    }
+}
+
+/// A `hashbrown::HashSet` is encoded the same as a `std::collections::HashSet`, with a length followed by its items.
+impl<T, S> Encode for HashSet<T, S>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        crate::enc::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
+    }
+}
+
+impl<T, S> Decode for HashSet<T, S>
+where
+    T: Decode + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_container_read::<T>(len)?;
+
+        // Every item that is not zero-sized is encoded as at least 1 byte
+        let min_item_size = usize::from(core::mem::size_of::<T>() != 0);
+        let hash_builder: S = Default::default();
+        let mut set = HashSet::with_hasher(hash_builder);
+        set.try_reserve(crate::de::reserve_hint(decoder, len, min_item_size))
+            .map_err(|inner| DecodeError::HashbrownOutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                let item = T::decode(decoder)?;
+                set.insert(item);
+            }
+            Ok(())
+        })?;
+        Ok(set)
+    }
+}
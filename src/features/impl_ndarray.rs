@@ -0,0 +1,73 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::vec::Vec;
+use ndarray::{Array, ArrayBase, Data, Dimension};
+
+/// An `ArrayBase` is encoded as its shape, the same as a `Vec<usize>`, followed by its items in standard (row-major) order.
+///
+/// The items are always written in this logical order, so arrays with a different memory layout, like transposed views, are encoded the same as a contiguous copy of them.
+impl<A, S, D> Encode for ArrayBase<S, D>
+where
+    A: Encode,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.shape().len().encode(encoder)?;
+        for len in self.shape() {
+            len.encode(encoder)?;
+        }
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decoding an array with a fixed dimension, like `Array2<T>`, fails with [DecodeError::ArrayLengthMismatch] if the encoded shape has a different number of dimensions.
+impl<A, D> Decode for Array<A, D>
+where
+    A: Decode,
+    D: Dimension,
+{
+    fn decode<D2: Decoder>(decoder: &mut D2) -> Result<Self, DecodeError> {
+        let shape = Vec::<usize>::decode(decoder)?;
+        if let Some(ndim) = D::NDIM {
+            if shape.len() != ndim {
+                return Err(DecodeError::ArrayLengthMismatch {
+                    required: ndim,
+                    found: shape.len(),
+                });
+            }
+        }
+        let len = shape
+            .iter()
+            .try_fold(1usize, |len, dim| len.checked_mul(*dim))
+            .ok_or(DecodeError::Other(
+                "The shape of the ndarray overflows a usize",
+            ))?;
+        decoder.claim_container_read::<A>(len)?;
+
+        let mut items = Vec::new();
+        items
+            .try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<A>());
+
+                items.push(A::decode(decoder)?);
+            }
+            Ok(())
+        })?;
+
+        let mut dim = D::zeros(shape.len());
+        dim.slice_mut().copy_from_slice(&shape);
+        Array::from_shape_vec(dim, items)
+            .map_err(|_| DecodeError::Other("The shape of the ndarray is too large"))
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::vec::Vec;
+use smallvec::SmallVec;
+
+/// Decode a `Vec<T>` into a `SmallVec<[T; N]>`. Up to `N` items are stored inline, so small collections are decoded without allocating. Larger collections are stored on the heap.
+///
+/// This can decode anything that was encoded as a `Vec<T>`, a slice or a `SmallVec`.
+///
+/// ```
+/// use bincode::de::{read::SliceReader, DecoderImpl};
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(vec![1u32, 2, 3], config).unwrap();
+///
+/// let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+/// let items = bincode::decode_smallvec::<u32, 4>(&mut decoder).unwrap();
+/// assert_eq!(items.as_slice(), [1, 2, 3]);
+/// assert!(!items.spilled());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+pub fn decode_smallvec<T: Decode, const N: usize>(
+    decoder: &mut impl Decoder,
+) -> Result<SmallVec<[T; N]>, DecodeError> {
+    let len = crate::de::decode_slice_len(decoder)?;
+    decoder.claim_container_read::<T>(len)?;
+
+    if len <= N {
+        let mut vec = SmallVec::new();
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                vec.push(T::decode(decoder)?);
+            }
+            Ok(())
+        })?;
+        return Ok(vec);
+    }
+
+    // Only a spilled `SmallVec` allocates
+    crate::de::check_allocation::<SmallVec<[T; N]>, _>(decoder)?;
+    let mut vec = Vec::new();
+    vec.try_reserve(len)
+        .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+    decoder.decode_nested_with(|decoder| {
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+            vec.push(T::decode(decoder)?);
+        }
+        Ok(())
+    })?;
+    // More than `N` items, so this keeps the heap allocation of `vec`
+    Ok(SmallVec::from_vec(vec))
+}
+
+/// A `SmallVec` is encoded the same as a `Vec`, regardless of whether its items are stored inline or on the heap.
+impl<T: Encode, const N: usize> Encode for SmallVec<[T; N]> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_slice().encode(encoder)
+    }
+}
+
+/// A `SmallVec` is decoded with [decode_smallvec].
+impl<T: Decode, const N: usize> Decode for SmallVec<[T; N]> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        decode_smallvec(decoder)
+    }
+}
@@ -7,12 +7,12 @@ use crate::{
 use core::time::Duration;
 use std::{
     collections::{HashMap, HashSet},
-    ffi::{CStr, CString},
+    ffi::{CStr, CString, OsStr, OsString},
     hash::Hash,
     io::Read,
-    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketAddrV4, SocketAddrV6},
     path::{Path, PathBuf},
-    sync::{Mutex, RwLock},
+    sync::{Mutex, OnceLock, RwLock},
     time::SystemTime,
 };
 
@@ -28,7 +28,10 @@ pub fn decode_from_std_read<D: Decode, C: Config, R: std::io::Read>(
 ) -> Result<D, DecodeError> {
     let reader = IoReader::new(src);
     let mut decoder = DecoderImpl::<_, C>::new(reader, config);
-    D::decode(&mut decoder)
+    let result = D::decode(&mut decoder)?;
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    decoder.debug_assert_claims_balanced();
+    Ok(result)
 }
 
 pub(crate) struct IoReader<R> {
@@ -93,7 +96,10 @@ pub fn encode_into_std_write<E: Encode, C: Config, W: std::io::Write>(
     let writer = IoWriter::new(dst);
     let mut encoder = EncoderImpl::<_, C>::new(writer, config);
     val.encode(&mut encoder)?;
-    Ok(encoder.into_writer().bytes_written())
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    crate::enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.bytes_written())
 }
 
 pub(crate) struct IoWriter<'a, W: std::io::Write> {
@@ -128,7 +134,7 @@ impl<'storage, W: std::io::Write> Writer for IoWriter<'storage, W> {
     }
 }
 
-impl<'a> Encode for &'a CStr {
+impl Encode for &CStr {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.to_bytes().encode(encoder)
     }
@@ -147,6 +153,113 @@ impl Decode for CString {
     }
 }
 
+#[cfg(unix)]
+impl Encode for &'_ OsStr {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        use std::os::unix::ffi::OsStrExt;
+        self.as_bytes().encode(encoder)
+    }
+}
+
+/// Borrows the `OsStr` directly from the input.
+///
+/// This is only available on unix, where an `OsStr` is an arbitrary byte sequence. On other platforms, decode an owned path instead.
+#[cfg(unix)]
+impl<'de> BorrowDecode<'de> for &'de OsStr {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        use std::os::unix::ffi::OsStrExt;
+        let bytes = <&'de [u8]>::borrow_decode(decoder)?;
+        Ok(OsStr::from_bytes(bytes))
+    }
+}
+
+#[cfg(unix)]
+impl Encode for OsString {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_os_str().encode(encoder)
+    }
+}
+
+#[cfg(unix)]
+impl Decode for OsString {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        use std::os::unix::ffi::OsStringExt;
+        let vec = std::vec::Vec::decode(decoder)?;
+        Ok(OsString::from_vec(vec))
+    }
+}
+
+/// On windows an `OsStr` is encoded as [WTF-8](https://simonsapin.github.io/wtf-8/), which is UTF-8 that also allows unpaired surrogates. Paths that are valid unicode are therefore encoded the same as on unix, and can be read on either platform.
+///
+/// Paths with unpaired surrogates round-trip on windows, but the representations are not interchangeable: on unix, such a path decodes to its WTF-8 bytes, and on windows, bytes from unix that are not valid WTF-8 are rejected with [DecodeError::InvalidWtf8].
+#[cfg(windows)]
+impl Encode for &'_ OsStr {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        use std::os::windows::ffi::OsStrExt;
+        let mut bytes = std::vec::Vec::with_capacity(self.len());
+        for c in char::decode_utf16(self.encode_wide()) {
+            match c {
+                Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                Err(e) => {
+                    // Surrogates are encoded like any other code point in the range 0x800..=0xFFFF
+                    let surrogate = e.unpaired_surrogate();
+                    bytes.extend_from_slice(&[
+                        0xE0 | (surrogate >> 12) as u8,
+                        0x80 | ((surrogate >> 6) & 0x3F) as u8,
+                        0x80 | (surrogate & 0x3F) as u8,
+                    ]);
+                }
+            }
+        }
+        bytes.encode(encoder)
+    }
+}
+
+#[cfg(windows)]
+impl Encode for OsString {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_os_str().encode(encoder)
+    }
+}
+
+#[cfg(windows)]
+impl Decode for OsString {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        use std::os::windows::ffi::OsStringExt;
+        let bytes = std::vec::Vec::<u8>::decode(decoder)?;
+        let mut wide = std::vec::Vec::with_capacity(bytes.len());
+        let mut rest = bytes.as_slice();
+        loop {
+            let (valid, invalid) = match core::str::from_utf8(rest) {
+                Ok(valid) => (valid, &[][..]),
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+                    // Safety: `valid_up_to` is the length of the valid UTF-8 prefix
+                    (unsafe { core::str::from_utf8_unchecked(valid) }, invalid)
+                }
+            };
+            wide.extend(valid.encode_utf16());
+            // The only sequences that are valid WTF-8 but not UTF-8 are encoded surrogates. A lead surrogate
+            // followed by a trail surrogate is not allowed, that pair has to be encoded as a single code point.
+            match *invalid {
+                [] => break,
+                [0xED, b1 @ 0xA0..=0xBF, b2 @ 0x80..=0xBF, ..]
+                    if !(b1 >= 0xB0 && matches!(wide.last(), Some(0xD800..=0xDBFF))) =>
+                {
+                    wide.push(0xD000 | ((b1 as u16 & 0x3F) << 6) | (b2 as u16 & 0x3F));
+                    rest = &invalid[3..];
+                }
+                _ => {
+                    return Err(DecodeError::InvalidWtf8 {
+                        index: bytes.len() - invalid.len(),
+                    })
+                }
+            }
+        }
+        Ok(OsString::from_wide(&wide))
+    }
+}
+
 impl<T> Encode for Mutex<T>
 where
     T: Encode,
@@ -191,28 +304,144 @@ where
     }
 }
 
+/// A `OnceLock` is encoded like an `Option<T>`, which is `None` if the lock is not initialized.
+impl<T> Encode for OnceLock<T>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.get().encode(encoder)
+    }
+}
+
+impl<T> Decode for OnceLock<T>
+where
+    T: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match Option::<T>::decode(decoder)? {
+            Some(t) => Ok(OnceLock::from(t)),
+            None => Ok(OnceLock::new()),
+        }
+    }
+}
+
+/// A `SystemTime` is encoded as a sign byte, followed by the `Duration` between the time and `UNIX_EPOCH`.
+///
+/// The sign byte is `0` if the time is at or after `UNIX_EPOCH`, and `1` if the time is before it. This allows times before 1970 to be encoded.
 impl Encode for SystemTime {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        let duration = self.duration_since(SystemTime::UNIX_EPOCH).map_err(|e| {
-            EncodeError::InvalidSystemTime {
-                inner: e,
-                time: *self,
+        match self.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(duration) => {
+                0u8.encode(encoder)?;
+                duration.encode(encoder)
             }
-        })?;
-        duration.encode(encoder)
+            Err(e) => {
+                1u8.encode(encoder)?;
+                e.duration().encode(encoder)
+            }
+        }
     }
 }
 
 impl Decode for SystemTime {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let duration = Duration::decode(decoder)?;
-        match SystemTime::UNIX_EPOCH.checked_add(duration) {
-            Some(t) => Ok(t),
-            None => Err(DecodeError::InvalidSystemTime { duration }),
+        match u8::decode(decoder)? {
+            0 => {
+                let duration = Duration::decode(decoder)?;
+                SystemTime::UNIX_EPOCH
+                    .checked_add(duration)
+                    .ok_or(DecodeError::InvalidSystemTime { duration })
+            }
+            1 => {
+                let duration = Duration::decode(decoder)?;
+                SystemTime::UNIX_EPOCH
+                    .checked_sub(duration)
+                    .ok_or(DecodeError::InvalidSystemTime { duration })
+            }
+            found => Err(DecodeError::UnexpectedVariant {
+                allowed: crate::error::AllowedEnumVariants::Range { min: 0, max: 1 },
+                found: found.into(),
+                type_name: core::any::type_name::<SystemTime>(),
+            }),
+        }
+    }
+}
+
+/// A wrapper around a `SystemTime` that is encoded as the signed amount of nanoseconds since `UNIX_EPOCH`, as a single `i128`.
+///
+/// A `SystemTime` is normally encoded as a sign byte followed by a `Duration`, which splits the time into seconds and nanoseconds. This wrapper keeps the full nanosecond precision in a single integer instead, which is negative for times before `UNIX_EPOCH`.
+///
+/// ```
+/// use bincode::SystemTimeNanos;
+/// use std::time::{Duration, SystemTime};
+///
+/// let config = bincode::config::legacy();
+/// let time = SystemTime::UNIX_EPOCH - Duration::from_nanos(1_500);
+/// let bytes = bincode::encode_to_vec(SystemTimeNanos(time), config).unwrap();
+/// assert_eq!(bytes, (-1_500i128).to_le_bytes());
+///
+/// let (decoded, _): (SystemTimeNanos, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(decoded.0, time);
+/// ```
+///
+/// When decoding, an amount of nanoseconds that does not fit in a `SystemTime` returns [DecodeError::InvalidSystemTimeNanos].
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemTimeNanos(pub SystemTime);
+
+impl SystemTimeNanos {
+    /// Returns the signed amount of nanoseconds since `UNIX_EPOCH` that is encoded.
+    pub fn to_nanos(&self) -> i128 {
+        // A `Duration` is at most `u64::MAX` seconds, so its nanoseconds always fit in an `i128`
+        match self.0.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(duration) => duration.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
         }
     }
+
+    /// Creates the time that is `nanos` nanoseconds after `UNIX_EPOCH`, or before it if `nanos` is negative. Returns `None` if the time can not be represented by a `SystemTime`.
+    pub fn from_nanos(nanos: i128) -> Option<Self> {
+        let abs = nanos.unsigned_abs();
+        let secs = u64::try_from(abs / 1_000_000_000).ok()?;
+        let duration = Duration::new(secs, (abs % 1_000_000_000) as u32);
+        let time = if nanos < 0 {
+            SystemTime::UNIX_EPOCH.checked_sub(duration)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_add(duration)
+        };
+        time.map(Self)
+    }
 }
 
+impl Encode for SystemTimeNanos {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.to_nanos().encode(encoder)
+    }
+}
+
+impl Decode for SystemTimeNanos {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let nanos = i128::decode(decoder)?;
+        Self::from_nanos(nanos).ok_or(DecodeError::InvalidSystemTimeNanos { nanos })
+    }
+}
+
+impl From<SystemTime> for SystemTimeNanos {
+    fn from(time: SystemTime) -> Self {
+        Self(time)
+    }
+}
+
+/// A path is encoded as its `OsStr`, so paths that are not valid unicode can be encoded without allocating a `PathBuf`.
+#[cfg(any(unix, windows))]
+impl Encode for &'_ Path {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_os_str().encode(encoder)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
 impl Encode for &'_ Path {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         match self.to_str() {
@@ -222,6 +451,15 @@ impl Encode for &'_ Path {
     }
 }
 
+#[cfg(unix)]
+impl<'de> BorrowDecode<'de> for &'de Path {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let os_str = <&'de OsStr>::borrow_decode(decoder)?;
+        Ok(Path::new(os_str))
+    }
+}
+
+#[cfg(not(unix))]
 impl<'de> BorrowDecode<'de> for &'de Path {
     fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
         let str = <&'de str>::borrow_decode(decoder)?;
@@ -235,6 +473,15 @@ impl Encode for PathBuf {
     }
 }
 
+#[cfg(any(unix, windows))]
+impl Decode for PathBuf {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let os_string = OsString::decode(decoder)?;
+        Ok(os_string.into())
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
 impl Decode for PathBuf {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let string = std::string::String::decode(decoder)?;
@@ -264,7 +511,7 @@ impl Decode for IpAddr {
             1 => Ok(IpAddr::V6(Ipv6Addr::decode(decoder)?)),
             found => Err(DecodeError::UnexpectedVariant {
                 allowed: crate::error::AllowedEnumVariants::Range { min: 0, max: 1 },
-                found,
+                found: found.into(),
                 type_name: core::any::type_name::<IpAddr>(),
             }),
         }
@@ -321,7 +568,7 @@ impl Decode for SocketAddr {
             1 => Ok(SocketAddr::V6(SocketAddrV6::decode(decoder)?)),
             found => Err(DecodeError::UnexpectedVariant {
                 allowed: crate::error::AllowedEnumVariants::Range { min: 0, max: 1 },
-                found,
+                found: found.into(),
                 type_name: core::any::type_name::<SocketAddr>(),
             }),
         }
@@ -358,8 +605,35 @@ impl Decode for SocketAddrV6 {
     }
 }
 
+impl Encode for Shutdown {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let variant: u8 = match self {
+            Shutdown::Read => 0,
+            Shutdown::Write => 1,
+            Shutdown::Both => 2,
+        };
+        variant.encode(encoder)
+    }
+}
+
+impl Decode for Shutdown {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(Shutdown::Read),
+            1 => Ok(Shutdown::Write),
+            2 => Ok(Shutdown::Both),
+            found => Err(DecodeError::UnexpectedVariant {
+                allowed: crate::error::AllowedEnumVariants::Range { min: 0, max: 2 },
+                found: found.into(),
+                type_name: core::any::type_name::<Shutdown>(),
+            }),
+        }
+    }
+}
+
 impl std::error::Error for EncodeError {}
 impl std::error::Error for DecodeError {}
+impl std::error::Error for crate::error::TranscodeError {}
 
 impl<K, V, S> Encode for HashMap<K, V, S>
 where
@@ -374,6 +648,14 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        crate::enc::collection_size_hint::<C>(
+            self.len(),
+            self.iter()
+                .map(|(k, v)| k.size_hint::<C>()?.checked_add(v.size_hint::<C>()?)),
+        )
+    }
 }
 
 impl<K, V, S> Decode for HashMap<K, V, S>
@@ -383,41 +665,112 @@ where
     S: std::hash::BuildHasher + Default,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<(K, V)>(len)?;
 
+        // Every key and value that is not zero-sized is encoded as at least 1 byte
+        let min_entry_size = usize::from(core::mem::size_of::<K>() != 0)
+            + usize::from(core::mem::size_of::<V>() != 0);
         let hash_builder: S = Default::default();
-        let mut map = HashMap::with_capacity_and_hasher(len, hash_builder);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
-
-            let k = K::decode(decoder)?;
-            let v = V::decode(decoder)?;
-            map.insert(k, v);
-        }
+        let mut map = HashMap::with_hasher(hash_builder);
+        map.try_reserve(crate::de::reserve_hint(decoder, len, min_entry_size))
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
+
+                let k = K::decode(decoder)?;
+                let v = V::decode(decoder)?;
+                map.insert(k, v);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
 
+/// A wrapper around [`HashMap`] that always encodes its entries in the same order.
+///
+/// The iteration order of a `HashMap` depends on its hasher, so two maps with the same content may encode to different bytes. `DeterministicMap` sorts the entries by their keys, using the `Ord` implementation of `K`, before writing them. Logically equal maps always produce identical output. This collects and sorts references to all entries, which takes `O(n log n)` time and an allocation of `n` pairs of pointers on every encode.
+///
+/// The encoded format is the same as that of `HashMap`, so the data can be decoded as either type. Because the entries are in the same order as those of a `BTreeMap` with the same keys, it can also be decoded as a `BTreeMap` when [strict map ordering] is enabled.
+///
+/// [strict map ordering]: crate::config::Configuration::with_strict_map_ordering
+///
+/// ```
+/// # use std::collections::HashMap;
+/// use bincode::DeterministicMap;
+///
+/// let mut map = HashMap::new();
+/// map.insert(1u32, "one");
+/// map.insert(2u32, "two");
+///
+/// let bytes = bincode::encode_to_vec(DeterministicMap(map), bincode::config::standard()).unwrap();
+/// assert_eq!(bytes, [2, 1, 3, b'o', b'n', b'e', 2, 3, b't', b'w', b'o']);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Default)]
+pub struct DeterministicMap<K, V, S = std::collections::hash_map::RandomState>(
+    pub HashMap<K, V, S>,
+);
+
+impl<K, V, S> Encode for DeterministicMap<K, V, S>
+where
+    K: Encode + Ord,
+    V: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let mut entries: std::vec::Vec<(&K, &V)> = self.0.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        crate::enc::encode_slice_len(encoder, entries.len())?;
+        for (k, v) in entries {
+            Encode::encode(k, encoder)?;
+            Encode::encode(v, encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, V, S> Decode for DeterministicMap<K, V, S>
+where
+    K: Decode + Eq + std::hash::Hash,
+    V: Decode,
+    S: std::hash::BuildHasher + Default,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        HashMap::decode(decoder).map(Self)
+    }
+}
+
 impl<T, S> Decode for HashSet<T, S>
 where
     T: Decode + Eq + Hash,
     S: std::hash::BuildHasher + Default,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        crate::de::check_allocation::<Self, _>(decoder)?;
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
+        // Every item that is not zero-sized is encoded as at least 1 byte
+        let min_item_size = usize::from(core::mem::size_of::<T>() != 0);
         let hash_builder: S = Default::default();
-        let mut map: HashSet<T, S> = HashSet::with_capacity_and_hasher(len, hash_builder);
-        for _ in 0..len {
-            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
-            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
-
-            let key = T::decode(decoder)?;
-            map.insert(key);
-        }
+        let mut map: HashSet<T, S> = HashSet::with_hasher(hash_builder);
+        map.try_reserve(crate::de::reserve_hint(decoder, len, min_item_size))
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+                let key = T::decode(decoder)?;
+                map.insert(key);
+            }
+            Ok(())
+        })?;
         Ok(map)
     }
 }
@@ -433,4 +786,8 @@ where
         }
         Ok(())
     }
+
+    fn size_hint<C: Config>(&self) -> Option<usize> {
+        crate::enc::collection_size_hint::<C>(self.len(), self.iter().map(T::size_hint::<C>))
+    }
 }
@@ -0,0 +1,109 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use tinyvec::{Array, ArrayVec};
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, tinyvec::TinyVec};
+
+/// An `ArrayVec` is encoded the same as a `Vec`, with a length followed by its items.
+impl<A> Encode for ArrayVec<A>
+where
+    A: Array,
+    A::Item: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decoding an `ArrayVec` fails with [DecodeError::CollectionTooLong] if the encoded length is larger than its capacity.
+impl<A> Decode for ArrayVec<A>
+where
+    A: Array,
+    A::Item: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        if len > A::CAPACITY {
+            return Err(DecodeError::CollectionTooLong {
+                len,
+                max: A::CAPACITY,
+            });
+        }
+        decode_array_vec(decoder, len)
+    }
+}
+
+fn decode_array_vec<A, D>(decoder: &mut D, len: usize) -> Result<ArrayVec<A>, DecodeError>
+where
+    A: Array,
+    A::Item: Decode,
+    D: Decoder,
+{
+    decoder.claim_container_read::<A::Item>(len)?;
+
+    let mut vec = ArrayVec::new();
+    decoder.decode_nested_with(|decoder| {
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<A::Item>());
+
+            vec.push(A::Item::decode(decoder)?);
+        }
+        Ok(())
+    })?;
+    Ok(vec)
+}
+
+/// A `TinyVec` is encoded the same as a `Vec`, regardless of whether its items are stored inline or on the heap.
+#[cfg(feature = "alloc")]
+impl<A> Encode for TinyVec<A>
+where
+    A: Array,
+    A::Item: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `TinyVec` is decoded inline if the encoded length fits in its capacity, and on the heap otherwise.
+#[cfg(feature = "alloc")]
+impl<A> Decode for TinyVec<A>
+where
+    A: Array,
+    A::Item: Decode,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        if len <= A::CAPACITY {
+            return decode_array_vec(decoder, len).map(TinyVec::Inline);
+        }
+        crate::de::check_allocation::<Self, _>(decoder)?;
+        decoder.claim_container_read::<A::Item>(len)?;
+
+        let mut vec = Vec::new();
+        vec.try_reserve(len)
+            .map_err(|inner| DecodeError::OutOfMemory { inner })?;
+        decoder.decode_nested_with(|decoder| {
+            for _ in 0..len {
+                // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                decoder.unclaim_bytes_read(core::mem::size_of::<A::Item>());
+
+                vec.push(A::Item::decode(decoder)?);
+            }
+            Ok(())
+        })?;
+        Ok(TinyVec::Heap(vec))
+    }
+}
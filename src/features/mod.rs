@@ -1,7 +1,14 @@
-#[cfg(feature = "atomic")]
+#[cfg(all(
+    feature = "atomic",
+    any(
+        target_has_atomic = "8",
+        target_has_atomic = "16",
+        target_has_atomic = "32",
+        target_has_atomic = "64",
+        target_has_atomic = "ptr"
+    )
+))]
 mod atomic;
-#[cfg(feature = "atomic")]
-pub use self::atomic::*;
 
 #[cfg(feature = "alloc")]
 mod impl_alloc;
@@ -18,6 +25,31 @@ mod derive;
 #[cfg(feature = "derive")]
 pub use self::derive::*;
 
+#[cfg(feature = "generic-array")]
+mod impl_generic_array;
+
+#[cfg(feature = "tinyvec")]
+mod impl_tinyvec;
+
+#[cfg(feature = "ndarray")]
+mod impl_ndarray;
+
+#[cfg(feature = "hashbrown")]
+mod impl_hashbrown;
+
+#[cfg(feature = "bitvec")]
+mod impl_bitvec;
+
+#[cfg(feature = "smallvec")]
+mod impl_smallvec;
+#[cfg(feature = "smallvec")]
+pub use self::impl_smallvec::*;
+
+#[cfg(feature = "flate2")]
+mod impl_flate2;
+#[cfg(feature = "flate2")]
+pub use self::impl_flate2::*;
+
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod serde;
@@ -22,7 +22,9 @@ where
         de: &mut decoder,
         pd: PhantomData,
     };
-    T::deserialize(serde_decoder)
+    let result = T::deserialize(serde_decoder)?;
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    Ok(result)
 }
 
 pub(super) struct SerdeDecoder<'a, 'de, DE: BorrowDecoder<'de>> {
@@ -75,13 +77,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
         visitor.visit_i64(Decode::decode(&mut self.de)?)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde_incl::de::Visitor<'de>,
-        {
-            visitor.visit_i128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde_incl::de::Visitor<'de>,
+    {
+        visitor.visit_i128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -112,13 +112,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
         visitor.visit_u64(Decode::decode(&mut self.de)?)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde_incl::de::Visitor<'de>,
-        {
-            visitor.visit_u128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde_incl::de::Visitor<'de>,
+    {
+        visitor.visit_u128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -235,7 +233,7 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
         self.deserialize_tuple(len, visitor)
     }
 
@@ -343,7 +341,7 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
         visitor.visit_map(Access {
             deserializer: &mut self,
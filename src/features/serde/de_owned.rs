@@ -23,7 +23,8 @@ where
     let mut decoder = crate::de::DecoderImpl::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
     let result = T::deserialize(serde_decoder)?;
-    let bytes_read = slice.len() - decoder.reader().slice.len();
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    let bytes_read = slice.len() - decoder.reader().reader.slice.len();
     Ok((result, bytes_read))
 }
 
@@ -40,7 +41,9 @@ pub fn decode_from_std_read<D: DeserializeOwned, C: Config, R: std::io::Read>(
     let reader = crate::IoReader::new(src);
     let mut decoder = crate::de::DecoderImpl::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
-    D::deserialize(serde_decoder)
+    let result = D::deserialize(serde_decoder)?;
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    Ok(result)
 }
 
 /// Attempt to decode a given type `D` from the given [Reader].
@@ -57,7 +60,9 @@ pub fn decode_from_reader<D: DeserializeOwned, R: Reader, C: Config>(
     }
     let mut decoder = crate::de::DecoderImpl::<_, C>::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
-    D::deserialize(serde_decoder)
+    let result = D::deserialize(serde_decoder)?;
+    crate::de::decode_alignment_padding(&mut decoder)?;
+    Ok(result)
 }
 
 pub(crate) struct SerdeDecoder<'a, DE: Decoder> {
@@ -109,13 +114,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
         visitor.visit_i64(Decode::decode(&mut self.de)?)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde_incl::de::Visitor<'de>,
-        {
-            visitor.visit_i128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde_incl::de::Visitor<'de>,
+    {
+        visitor.visit_i128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -146,13 +149,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
         visitor.visit_u64(Decode::decode(&mut self.de)?)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-        where
-            V: serde_incl::de::Visitor<'de>,
-        {
-            visitor.visit_u128(Decode::decode(&mut self.de)?)
-        }
+    fn deserialize_u128<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde_incl::de::Visitor<'de>,
+    {
+        visitor.visit_u128(Decode::decode(&mut self.de)?)
     }
 
     fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -284,7 +285,7 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
         self.deserialize_tuple(len, visitor)
     }
 
@@ -389,7 +390,7 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
         visitor.visit_map(Access {
             deserializer: &mut self,
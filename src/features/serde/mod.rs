@@ -185,8 +185,9 @@ impl serde_incl::ser::Error for crate::error::EncodeError {
 
 /// Wrapper struct that implements [Decode] and [Encode] on any type that implements serde's [DeserializeOwned] and [Serialize] respectively.
 ///
-/// This works for most types, but if you're dealing with borrowed data consider using [BorrowCompat] instead.
+/// This works for most types, but if you're dealing with borrowed data consider using [BorrowCompat] instead. `Compat` can not borrow from the input, because every [Decode] type already implements [BorrowDecode] for any lifetime.
 ///
+/// [BorrowDecode]: ../de/trait.BorrowDecode.html
 /// [Decode]: ../de/trait.Decode.html
 /// [Encode]: ../enc/trait.Encode.html
 /// [DeserializeOwned]: https://docs.rs/serde/1/serde/de/trait.DeserializeOwned.html
@@ -219,6 +220,8 @@ where
 
 /// Wrapper struct that implements [BorrowDecode] and [Encode] on any type that implements serde's [Deserialize] and [Serialize] respectively. This is mostly used on `&[u8]` and `&str`, for other types consider using [Compat] instead.
 ///
+/// The value is deserialized with a serde `Deserializer` that borrows from the input, so fields marked with `#[serde(borrow)]` point into the decoded slice without being copied.
+///
 /// [BorrowDecode]: ../de/trait.BorrowDecode.html
 /// [Encode]: ../enc/trait.Encode.html
 /// [Deserialize]: https://docs.rs/serde/1/serde/de/trait.Deserialize.html
@@ -1,6 +1,6 @@
 use super::EncodeError as SerdeEncodeError;
 use crate::{
-    config::Config,
+    config::{Config, InternalAlignmentConfig},
     enc::{write::Writer, Encode, Encoder},
     error::EncodeError,
 };
@@ -22,7 +22,10 @@ where
     let mut encoder = crate::enc::EncoderImpl::new(crate::VecWriter::default(), config);
     let serializer = SerdeEncoder { enc: &mut encoder };
     t.serialize(serializer)?;
-    Ok(encoder.into_writer().collect())
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    crate::enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.collect())
 }
 
 /// Encode a `serde` `Serialize` type into a given byte slice with the bincode algorithm
@@ -38,7 +41,10 @@ where
         crate::enc::EncoderImpl::new(crate::enc::write::SliceWriter::new(slice), config);
     let serializer = SerdeEncoder { enc: &mut encoder };
     t.serialize(serializer)?;
-    Ok(encoder.into_writer().bytes_written())
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    crate::enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.bytes_written())
 }
 
 /// Encode the given value into a custom [Writer].
@@ -54,10 +60,19 @@ pub fn encode_into_writer<E: Serialize, W: Writer, C: Config>(
     if C::SKIP_FIXED_ARRAY_LENGTH {
         return Err(SerdeEncodeError::SkipFixedArrayLengthNotSupported.into());
     }
+    if <C as InternalAlignmentConfig>::ALIGNMENT.is_none() {
+        let mut encoder = crate::enc::EncoderImpl::<_, C>::new(writer, config);
+        let serializer = SerdeEncoder { enc: &mut encoder };
+        return val.serialize(serializer);
+    }
+    // Count the bytes of the value, so the padding can be calculated
+    let writer =
+        crate::enc::write::TeeWriter::new(writer, crate::enc::write::SizeWriter::default());
     let mut encoder = crate::enc::EncoderImpl::<_, C>::new(writer, config);
     let serializer = SerdeEncoder { enc: &mut encoder };
     val.serialize(serializer)?;
-    Ok(())
+    let (mut writer, size) = encoder.into_writer().into_inner();
+    crate::enc::write_alignment_padding::<C, _>(&mut writer, size.bytes_written)
 }
 
 /// Encode the given value into any type that implements `std::io::Write`, e.g. `std::fs::File`, with the given `Config`.
@@ -78,7 +93,10 @@ pub fn encode_into_std_write<E: Serialize, C: Config, W: std::io::Write>(
     let mut encoder = crate::enc::EncoderImpl::<_, C>::new(writer, config);
     let serializer = SerdeEncoder { enc: &mut encoder };
     val.serialize(serializer)?;
-    Ok(encoder.into_writer().bytes_written())
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    crate::enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.bytes_written())
 }
 
 pub(super) struct SerdeEncoder<'a, ENC: Encoder> {
@@ -121,10 +139,8 @@ where
         v.encode(self.enc)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-            v.encode(self.enc)
-        }
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        v.encode(self.enc)
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
@@ -143,10 +159,8 @@ where
         v.encode(self.enc)
     }
 
-    serde_incl::serde_if_integer128! {
-        fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-            v.encode(self.enc)
-        }
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        v.encode(self.enc)
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
@@ -173,9 +187,9 @@ where
         0u8.encode(self.enc)
     }
 
-    fn serialize_some<T: ?Sized>(mut self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(mut self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         1u8.encode(&mut self.enc)?;
         value.serialize(self)
@@ -198,18 +212,18 @@ where
         variant_index.encode(self.enc)
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(
+    fn serialize_newtype_struct<T>(
         self,
         _name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(self)
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         mut self,
         _name: &'static str,
         variant_index: u32,
@@ -217,7 +231,7 @@ where
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         variant_index.encode(&mut self.enc)?;
         value.serialize(self)
@@ -297,9 +311,9 @@ impl<'a, ENC: Encoder> SerializeSeq for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -313,9 +327,9 @@ impl<'a, ENC: Encoder> SerializeTuple for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -329,9 +343,9 @@ impl<'a, ENC: Encoder> SerializeTupleStruct for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -345,9 +359,9 @@ impl<'a, ENC: Encoder> SerializeTupleVariant for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -361,16 +375,16 @@ impl<'a, ENC: Encoder> SerializeMap for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         key.serialize(SerdeEncoder { enc: self.enc })
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -384,13 +398,9 @@ impl<'a, ENC: Encoder> SerializeStruct for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_field<T: ?Sized>(
-        &mut self,
-        _key: &'static str,
-        value: &T,
-    ) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -404,13 +414,9 @@ impl<'a, ENC: Encoder> SerializeStructVariant for Compound<'a, ENC> {
     type Ok = ();
     type Error = EncodeError;
 
-    fn serialize_field<T: ?Sized>(
-        &mut self,
-        _key: &'static str,
-        value: &T,
-    ) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(SerdeEncoder { enc: self.enc })
     }
@@ -0,0 +1,74 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+
+/// An `f64` that is encoded as an `i64` of `1 / SCALE` units, so the encoding does not depend on the floating point behavior of the platform.
+///
+/// The value is rounded to the nearest unit, with halfway cases rounded away from zero. `SCALE` can not be `0`. Encoding returns [EncodeError::FixedPointOverflow] for values that are not finite, or that do not fit in an `i64` after scaling.
+///
+/// ```
+/// use bincode::FixedPoint;
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = [0u8; 16];
+/// let len = bincode::encode_into_slice(FixedPoint::<1000>(1.2345), &mut bytes, config).unwrap();
+/// // Encoded as `1235i64`
+/// assert_eq!(&bytes[..len], [251, 166, 9]);
+///
+/// let (decoded, _): (FixedPoint<1000>, usize) = bincode::decode_from_slice(&bytes[..len], config).unwrap();
+/// assert_eq!(decoded.0, 1.235);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct FixedPoint<const SCALE: u32>(pub f64);
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+    const NON_ZERO_SCALE: () = assert!(SCALE != 0, "The scale of a FixedPoint can not be 0");
+
+    /// Returns the scaled and rounded value that is encoded, or `None` if it does not fit in an `i64`.
+    pub fn to_fixed(&self) -> Option<i64> {
+        let () = Self::NON_ZERO_SCALE;
+
+        let scaled = self.0 * SCALE as f64;
+        // `i64::MAX as f64` is rounded up to 2^63, so this range is exactly what fits in an `i64`. This also rejects NaN.
+        if !(scaled >= i64::MIN as f64 && scaled < i64::MAX as f64) {
+            return None;
+        }
+        // `f64::round` is not available in `core`. In this range truncating is exact, and so is the fraction.
+        let truncated = scaled as i64;
+        let fraction = scaled - truncated as f64;
+        Some(if fraction >= 0.5 {
+            truncated + 1
+        } else if fraction <= -0.5 {
+            truncated - 1
+        } else {
+            truncated
+        })
+    }
+
+    /// Creates a value from its scaled representation, as returned by [FixedPoint::to_fixed].
+    pub fn from_fixed(value: i64) -> Self {
+        let () = Self::NON_ZERO_SCALE;
+
+        Self(value as f64 / SCALE as f64)
+    }
+}
+
+impl<const SCALE: u32> Encode for FixedPoint<SCALE> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self.to_fixed() {
+            Some(value) => value.encode(encoder),
+            None => Err(EncodeError::FixedPointOverflow {
+                value: self.0,
+                scale: SCALE,
+            }),
+        }
+    }
+}
+
+impl<const SCALE: u32> Decode for FixedPoint<SCALE> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self::from_fixed(i64::decode(decoder)?))
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+
+/// An opaque handle that is encoded as a `usize`, e.g. the address of a `*mut core::ffi::c_void` in an FFI registry.
+///
+/// Raw pointers do not implement `Encode` and `Decode`. Convert them with [Handle::from_ptr] and [Handle::to_ptr] instead.
+///
+/// **Pointer provenance is not preserved.** Only the address is encoded, so the pointer returned by [Handle::to_ptr] may only be dereferenced while the original pointer is still valid, in the same process. The rules of [`core::ptr`] for casting integers to pointers apply.
+///
+/// ```
+/// use bincode::Handle;
+///
+/// let value = 5u32;
+/// let handle = Handle::from_ptr(&value as *const u32);
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = [0u8; 16];
+/// let len = bincode::encode_into_slice(handle, &mut bytes, config).unwrap();
+/// let (decoded, _): (Handle, usize) = bincode::decode_from_slice(&bytes[..len], config).unwrap();
+/// assert_eq!(decoded, handle);
+///
+/// // Safety: `value` is still alive, and the handle was created in this process
+/// assert_eq!(unsafe { *decoded.to_ptr::<u32>() }, 5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle(pub usize);
+
+impl Handle {
+    /// Creates a handle from the address of `ptr`.
+    pub fn from_ptr<T>(ptr: *const T) -> Self {
+        Self(ptr as usize)
+    }
+
+    /// Converts the handle back into a pointer. See the documentation of [Handle] for when this pointer can be dereferenced.
+    pub fn to_ptr<T>(self) -> *const T {
+        self.0 as *const T
+    }
+}
+
+impl Encode for Handle {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+impl Decode for Handle {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self(usize::decode(decoder)?))
+    }
+}
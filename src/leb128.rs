@@ -0,0 +1,93 @@
+//! Helpers to read and write bare [LEB128](https://en.wikipedia.org/wiki/LEB128) integers.
+//!
+//! Bincode itself does not use LEB128; see [the spec](../spec/index.html) for the varint format used by [Configuration::with_variable_int_encoding]. These functions are meant for interop with formats that do use LEB128, like WebAssembly and DWARF, and can be used from custom [Encode] and [Decode] implementations.
+//!
+//! ```
+//! # use bincode::{de::read::SliceReader, enc::write::SliceWriter};
+//! let mut buffer = [0u8; 10];
+//! let mut writer = SliceWriter::new(&mut buffer);
+//! bincode::leb128::encode_u64(&mut writer, 624_485).unwrap();
+//! assert_eq!(writer.bytes_written(), 3);
+//! assert_eq!(buffer[..3], [0xE5, 0x8E, 0x26]);
+//!
+//! let value = bincode::leb128::decode_u64(&mut SliceReader::new(&buffer)).unwrap();
+//! assert_eq!(value, 624_485);
+//! ```
+//!
+//! [Configuration::with_variable_int_encoding]: crate::config::Configuration::with_variable_int_encoding
+//! [Encode]: crate::Encode
+//! [Decode]: crate::Decode
+
+use crate::{de::read::Reader, enc::write::Writer, error::DecodeError, error::EncodeError};
+
+/// The maximum amount of bytes a LEB128-encoded `u64` can take.
+pub const MAX_U64_LEN: usize = 10;
+
+/// Write `val` as an unsigned LEB128 integer.
+pub fn encode_u64<W: Writer>(writer: &mut W, mut val: u64) -> Result<(), EncodeError> {
+    let mut bytes = [0u8; MAX_U64_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (val & 0x7F) as u8;
+        val >>= 7;
+        if val == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write(&bytes[..len])
+}
+
+/// Read an unsigned LEB128 integer.
+///
+/// Returns [DecodeError::Leb128Overflow] if the encoded value does not fit in a `u64`.
+pub fn decode_u64<R: Reader>(reader: &mut R) -> Result<u64, DecodeError> {
+    let mut result = 0u64;
+    for index in 0..MAX_U64_LEN {
+        let mut byte = [0u8; 1];
+        reader.read(&mut byte)?;
+        let byte = byte[0];
+        let shift = index * 7;
+
+        // The 10th byte may only contain the highest bit of the u64
+        if index == MAX_U64_LEN - 1 && byte > 1 {
+            return Err(DecodeError::Leb128Overflow);
+        }
+
+        result |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err(DecodeError::Leb128Overflow)
+}
+
+/// Write `val` as a zigzag-encoded unsigned LEB128 integer.
+///
+/// Zigzag encoding maps signed integers to unsigned integers, so that numbers close to zero use few bytes: `0 => 0`, `-1 => 1`, `1 => 2`, `-2 => 3`, etc. This is the same mapping bincode uses for signed varints.
+///
+/// **Note:** this is not the same as signed LEB128 (`SLEB128`), which sign-extends the value instead.
+pub fn encode_i64<W: Writer>(writer: &mut W, val: i64) -> Result<(), EncodeError> {
+    encode_u64(
+        writer,
+        if val < 0 {
+            // !n * 2 + 1 = 2(-n - 1) + 1 = -2n - 1, this avoids overflowing on i64::MIN
+            !(val as u64) * 2 + 1
+        } else {
+            (val as u64) * 2
+        },
+    )
+}
+
+/// Read a zigzag-encoded unsigned LEB128 integer, as written by [encode_i64].
+pub fn decode_i64<R: Reader>(reader: &mut R) -> Result<i64, DecodeError> {
+    let n = decode_u64(reader)?;
+    Ok(if n % 2 == 0 {
+        (n / 2) as i64
+    } else {
+        !(n / 2) as i64
+    })
+}
@@ -18,11 +18,32 @@
 //! |Name  |Default?|Supported types for Encode/Decode|Enabled methods                                                  |Other|
 //! |------|--------|-----------------------------------------|-----------------------------------------------------------------|-----|
 //! |std   | Yes    |`HashMap` and `HashSet`|`decode_from_std_read` and `encode_into_std_write`|
-//! |alloc | Yes    |All common containers in alloc, like `Vec`, `String`, `Box`|`encode_to_vec`|
-//! |atomic| Yes    |All `Atomic*` integer types, e.g. `AtomicUsize`, and `AtomicBool`||
+//! |alloc | Yes    |All common containers in alloc, like `Vec`, `String`, `Box`, `SparseVec`, `SortedHeap` and `IndexedMap`|`encode_to_vec`, `decode_from_reader_into` and `transcode`|
+//! |atomic| Yes    |All `Atomic*` integer types, e.g. `AtomicUsize`, and `AtomicBool`. See [Atomics](#atomics) for the supported types per target||
 //! |derive| Yes    |||Enables the `BorrowDecode`, `Decode` and `Encode` derive macros|
+//! |generic-array| No |`GenericArray<T, N>` from the [generic-array](https://docs.rs/generic-array) crate, encoded without a length||
+//! |tinyvec| No |`ArrayVec` and, with the `alloc` feature, `TinyVec` from the [tinyvec](https://docs.rs/tinyvec) crate||
+//! |ndarray| No |`Array<T, D>` from the [ndarray](https://docs.rs/ndarray) crate, encoded as its shape followed by its items in row-major order. Any `ArrayBase`, like an `ArrayView`, can be encoded||Enables `alloc`|
+//! |hashbrown| No |`HashMap` and `HashSet` from the [hashbrown](https://docs.rs/hashbrown) crate, encoded the same as the `std` collections. Useful on `no_std` targets with an allocator||Enables `alloc`|
+//! |bitvec| No |`BitVec<u8, Lsb0>` from the [bitvec](https://docs.rs/bitvec) crate, encoded as its length in bits followed by the packed bytes||Enables `alloc`|
+//! |smallvec| No |`SmallVec<[T; N]>` from the [smallvec](https://docs.rs/smallvec) crate, encoded the same as a `Vec`|`decode_smallvec`, which decodes up to `N` items without allocating|Enables `alloc`|
+//! |flate2| No ||`encode_to_vec_gzip` and `decode_from_slice_gzip`, which compress and decompress with the [flate2](https://docs.rs/flate2) crate|Enables `std`|
 //! |serde | No     |`Compat` and `BorrowCompat`, which will work for all types that implement serde's traits|serde-specific encode/decode functions in the [serde] module|Note: There are several [known issues](serde/index.html#known-issues) when using serde and bincode|
 //!
+//! # Atomics
+//!
+//! Not every target supports every atomic type. With the `atomic` feature enabled, the `Atomic*` types only implement `Encode` and `Decode` when the target has atomic support of the matching size, as reported by `cfg(target_has_atomic)`:
+//!
+//! |Type|Required `target_has_atomic`|
+//! |----|----------------------------|
+//! |`AtomicBool`, `AtomicU8`, `AtomicI8`|`"8"`|
+//! |`AtomicU16`, `AtomicI16`|`"16"`|
+//! |`AtomicU32`, `AtomicI32`|`"32"`|
+//! |`AtomicU64`, `AtomicI64`|`"64"`|
+//! |`AtomicUsize`, `AtomicIsize`, and `Arc<T>` with the `alloc` feature|`"ptr"`|
+//!
+//! For example, `thumbv7m-none-eabi` has no 64-bit atomics, so `AtomicU64` is not supported there. On targets without any atomics, like `thumbv6m-none-eabi`, the `atomic` feature has no effect.
+//!
 //! # Which functions to use
 //!
 //! Bincode has a couple of pairs of functions that are used in different situations.
@@ -33,6 +54,7 @@
 //! |you're working with in-memory buffers|[`encode_to_vec`]|[`decode_from_slice`]|
 //! |You want to use a custom [Reader](de::read::Reader) and [writer](enc::write::Writer)|[`encode_into_writer`]|[`decode_from_reader`]|
 //! |You're working with pre-allocated buffers or on embedded targets|[`encode_into_slice`]|[`decode_from_slice`]|
+//! |You want to stream the encoded bytes to a callback in chunks|[`encode_streaming`]|[`decode_from_reader`]|
 //!
 //! **Note:** If you're using `serde`, use `bincode::serde::...` instead of `bincode::...`
 //!
@@ -80,18 +102,36 @@ extern crate alloc;
 #[cfg(any(feature = "std", test))]
 extern crate std;
 
+mod duration_millis;
 mod features;
+mod fixed_point;
+mod handle;
+mod niche;
+mod partial;
+#[cfg(feature = "alloc")]
+mod sparse_vec;
 pub(crate) mod utils;
-pub(crate) mod varint;
 
 use de::{read::Reader, Decoder};
+pub use duration_millis::DurationMillis;
 use enc::write::Writer;
 pub use features::*;
+pub use fixed_point::FixedPoint;
+pub use handle::Handle;
+pub use niche::{NonZeroInteger, NonZeroOption};
+pub use partial::{PartialEncode, PartialStruct};
+#[cfg(feature = "alloc")]
+pub use sparse_vec::SparseVec;
 
+pub mod checksum;
 pub mod config;
 pub mod de;
 pub mod enc;
 pub mod error;
+pub mod leb128;
+#[cfg(feature = "alloc")]
+pub mod registry;
+pub mod varint;
 
 pub use de::{BorrowDecode, Decode};
 pub use enc::Encode;
@@ -100,9 +140,12 @@ use config::Config;
 
 /// Encode the given value into the given slice. Returns the amount of bytes that have been written.
 ///
+/// If `dst` is too small, [EncodeError::BufferFull] is returned.
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [config]: config/index.html
+/// [EncodeError::BufferFull]: error::EncodeError::BufferFull
 pub fn encode_into_slice<E: enc::Encode, C: Config>(
     val: E,
     dst: &mut [u8],
@@ -111,11 +154,35 @@ pub fn encode_into_slice<E: enc::Encode, C: Config>(
     let writer = enc::write::SliceWriter::new(dst);
     let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
     val.encode(&mut encoder)?;
-    Ok(encoder.into_writer().bytes_written())
+    let mut writer = encoder.into_writer();
+    let len = writer.bytes_written();
+    enc::write_alignment_padding::<C, _>(&mut writer, len)?;
+    Ok(writer.bytes_written())
 }
 
 /// Encode the given value into a custom [Writer].
 ///
+/// ```
+/// use bincode::{enc::write::Writer, error::EncodeError};
+///
+/// // A writer that only keeps the last byte that was written
+/// #[derive(Default)]
+/// struct LastByteWriter {
+///     last: Option<u8>,
+/// }
+///
+/// impl Writer for LastByteWriter {
+///     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+///         self.last = bytes.last().copied().or(self.last);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut writer = LastByteWriter::default();
+/// bincode::encode_into_writer((1u8, 2u8, 3u8), &mut writer, bincode::config::standard()).unwrap();
+/// assert_eq!(writer.last, Some(3));
+/// ```
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [config]: config/index.html
@@ -124,9 +191,44 @@ pub fn encode_into_writer<E: enc::Encode, W: Writer, C: Config>(
     writer: W,
     config: C,
 ) -> Result<(), error::EncodeError> {
+    if <C as config::InternalAlignmentConfig>::ALIGNMENT.is_none() {
+        let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
+        return val.encode(&mut encoder);
+    }
+    // Count the bytes of the value, so the padding can be calculated
+    let writer = enc::write::TeeWriter::new(writer, enc::write::SizeWriter::default());
     let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
     val.encode(&mut encoder)?;
-    Ok(())
+    let (mut writer, size) = encoder.into_writer().into_inner();
+    enc::write_alignment_padding::<C, _>(&mut writer, size.bytes_written)
+}
+
+/// Encode the given value, passing the encoded bytes to `callback` in chunks as they are produced. This is useful to stream a value to a destination that is not a [Writer], without encoding it into a buffer first.
+///
+/// The chunks are passed on as they are written by the encoder, so they can be as small as a single byte. If the callback returns an error, encoding stops and the error is returned.
+///
+/// ```
+/// let mut bytes = [0u8; 8];
+/// let mut len = 0;
+/// bincode::encode_streaming((1u8, 1000u32), bincode::config::standard(), |chunk: &[u8]| {
+///     bytes[len..len + chunk.len()].copy_from_slice(chunk);
+///     len += chunk.len();
+///     Ok(())
+/// })
+/// .unwrap();
+/// assert_eq!(bytes[..len], [1, 251, 232, 3]);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+pub fn encode_streaming<E, C, F>(val: E, config: C, callback: F) -> Result<(), error::EncodeError>
+where
+    E: enc::Encode,
+    C: Config,
+    F: FnMut(&[u8]) -> Result<(), error::EncodeError>,
+{
+    encode_into_writer(val, enc::write::CallbackWriter::new(callback), config)
 }
 
 /// Attempt to decode a given type `D` from the given slice.
@@ -141,12 +243,57 @@ pub fn decode_from_slice<'a, D: de::BorrowDecode<'a>, C: Config>(
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
     let result = D::borrow_decode(&mut decoder)?;
-    let bytes_read = src.len() - decoder.reader().slice.len();
+    de::decode_alignment_padding(&mut decoder)?;
+    decoder.debug_assert_claims_balanced();
+    let bytes_read = src.len() - decoder.reader().reader.slice.len();
+    Ok((result, bytes_read))
+}
+
+/// Decode a value from the given slice with a [DecodeSeed], which can hold state that is needed to decode the value.
+///
+/// See the [config] module for more information on configurations.
+///
+/// [DecodeSeed]: de::DecodeSeed
+/// [config]: config/index.html
+pub fn decode_seed_from_slice<S: de::DecodeSeed, C: Config>(
+    seed: S,
+    src: &[u8],
+    config: C,
+) -> Result<(S::Value, usize), error::DecodeError> {
+    let reader = de::read::SliceReader::new(src);
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    let result = seed.decode(&mut decoder)?;
+    de::decode_alignment_padding(&mut decoder)?;
+    decoder.debug_assert_claims_balanced();
+    let bytes_read = src.len() - decoder.reader().reader.slice.len();
     Ok((result, bytes_read))
 }
 
 /// Attempt to decode a given type `D` from the given [Reader].
 ///
+/// ```
+/// use bincode::{de::read::Reader, error::DecodeError};
+///
+/// // A reader that reads from an iterator of bytes
+/// struct IterReader<I>(I);
+///
+/// impl<I: Iterator<Item = u8>> Reader for IterReader<I> {
+///     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+///         let len = bytes.len();
+///         for (i, byte) in bytes.iter_mut().enumerate() {
+///             *byte = self.0.next().ok_or(DecodeError::UnexpectedEnd {
+///                 additional: len - i,
+///             })?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let reader = IterReader([1u8, 2, 3].into_iter());
+/// let decoded: (u8, u8, u8) = bincode::decode_from_reader(reader, bincode::config::standard()).unwrap();
+/// assert_eq!(decoded, (1, 2, 3));
+/// ```
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [config]: config/index.html
@@ -155,7 +302,123 @@ pub fn decode_from_reader<D: de::Decode, R: Reader, C: Config>(
     config: C,
 ) -> Result<D, error::DecodeError> {
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
-    D::decode(&mut decoder)
+    let result = D::decode(&mut decoder)?;
+    de::decode_alignment_padding(&mut decoder)?;
+    decoder.debug_assert_claims_balanced();
+    Ok(result)
+}
+
+/// Decode the entries of a map, like a `BTreeMap<K, V>` or `HashMap<K, V>`, from the given [Reader] one at a time.
+///
+/// Instead of building the map, `callback` is called for every entry. This allows large maps to be processed without holding the entire map in memory. Decoding stops at the first error returned by `callback`. Returns the amount of entries that were decoded.
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::collections::BTreeMap;
+/// let mut map = BTreeMap::new();
+/// map.insert(1u32, "one".to_string());
+/// map.insert(2u32, "two".to_string());
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(&map, config).unwrap();
+///
+/// let mut total_len = 0;
+/// let reader = bincode::de::read::SliceReader::new(&bytes);
+/// let count = bincode::decode_map_entries(reader, config, |_key: u32, value: String| {
+///     total_len += value.len();
+///     Ok(())
+/// })
+/// .unwrap();
+/// assert_eq!(count, 2);
+/// assert_eq!(total_len, 6);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+pub fn decode_map_entries<K, V, R, C, F>(
+    reader: R,
+    config: C,
+    mut callback: F,
+) -> Result<usize, error::DecodeError>
+where
+    K: de::Decode,
+    V: de::Decode,
+    R: Reader,
+    C: Config,
+    F: FnMut(K, V) -> Result<(), error::DecodeError>,
+{
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    let len = de::decode_slice_len(&mut decoder)?;
+    for _ in 0..len {
+        let key = K::decode(&mut decoder)?;
+        let value = V::decode(&mut decoder)?;
+        decoder.debug_assert_claims_balanced();
+        callback(key, value)?;
+    }
+    Ok(len)
+}
+
+/// Lazily decode the items of an encoded sequence, e.g. a `Vec<T>`, from the given [Reader].
+///
+/// The length of the sequence is read when the first item is requested, after which the iterator yields that many items. Each item is only decoded when it is requested, so the sequence is never held in memory as a whole. The iterator stops after the first error.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// // `vec![1u32, 2, 3]`: the length, followed by the items
+/// let bytes = [3, 1, 2, 3];
+///
+/// let reader = bincode::de::read::SliceReader::new(&bytes);
+/// let sum = bincode::decode_seq::<u32, _, _>(reader, config)
+///     .sum::<Result<u32, _>>()
+///     .unwrap();
+/// assert_eq!(sum, 6);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+pub fn decode_seq<T, R, C>(
+    reader: R,
+    config: C,
+) -> impl Iterator<Item = Result<T, error::DecodeError>>
+where
+    T: de::Decode,
+    R: Reader,
+    C: Config,
+{
+    let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
+    // `None` until the length has been decoded
+    let mut remaining = None;
+    core::iter::from_fn(move || {
+        let remaining = match remaining {
+            Some(ref mut remaining) => remaining,
+            None => match de::decode_slice_len(&mut decoder) {
+                Ok(len) => remaining.insert(len),
+                Err(e) => {
+                    remaining = Some(0);
+                    return Some(Err(e));
+                }
+            },
+        };
+        if *remaining == 0 {
+            return None;
+        }
+        match T::decode(&mut decoder) {
+            Ok(item) => {
+                decoder.debug_assert_claims_balanced();
+                *remaining -= 1;
+                Some(Ok(item))
+            }
+            Err(e) => {
+                *remaining = 0;
+                Some(Err(e))
+            }
+        }
+    })
 }
 
 // TODO: Currently our doctests fail when trying to include the specs because the specs depend on `derive` and `alloc`.
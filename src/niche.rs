@@ -0,0 +1,238 @@
+//! Niche-optimized encoding of optional `NonZero*` integers.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+    utils::Sealed,
+};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+/// A wrapper around an `Option` of any of the `NonZero*` integer types that uses `0` to encode `None`.
+///
+/// A regular `Option<NonZeroU32>` writes a byte for the variant, followed by the value if it is `Some`. Because a `NonZero*` integer can never be `0`, `NonZeroOption` encodes `None` as a `0` of the inner integer type, and `Some(n)` as `n`. This saves the variant byte, and makes `None` and `Some` the same size with fixed int encoding.
+///
+/// ```
+/// # use core::num::NonZeroU32;
+/// use bincode::NonZeroOption;
+///
+/// let config = bincode::config::standard().with_fixed_int_encoding();
+/// let mut slice = [0u8; 4];
+///
+/// bincode::encode_into_slice(NonZeroOption(NonZeroU32::new(5)), &mut slice, config).unwrap();
+/// assert_eq!(slice, [5, 0, 0, 0]);
+///
+/// bincode::encode_into_slice(NonZeroOption::<NonZeroU32>(None), &mut slice, config).unwrap();
+/// assert_eq!(slice, [0, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonZeroOption<T: NonZeroInteger>(pub Option<T>);
+
+/// The `NonZero*` integer types that can be used in a [NonZeroOption].
+///
+/// This trait is sealed and can not be implemented outside of bincode.
+pub trait NonZeroInteger: Sealed + Copy {
+    /// The integer type that this type wraps, e.g. `u32` for `NonZeroU32`
+    type Integer: Encode + Decode;
+
+    #[doc(hidden)]
+    fn from_integer(value: Self::Integer) -> Option<Self>;
+
+    #[doc(hidden)]
+    fn into_integer(value: Option<Self>) -> Self::Integer;
+}
+
+impl<T: NonZeroInteger> Encode for NonZeroOption<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        T::into_integer(self.0).encode(encoder)
+    }
+}
+
+impl<T: NonZeroInteger> Decode for NonZeroOption<T> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self(T::from_integer(T::Integer::decode(decoder)?)))
+    }
+}
+
+impl<T: NonZeroInteger> Default for NonZeroOption<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: NonZeroInteger> From<Option<T>> for NonZeroOption<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl Sealed for NonZeroU8 {}
+
+impl NonZeroInteger for NonZeroU8 {
+    type Integer = u8;
+
+    fn from_integer(value: u8) -> Option<Self> {
+        NonZeroU8::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> u8 {
+        value.map_or(0, NonZeroU8::get)
+    }
+}
+
+impl Sealed for NonZeroU16 {}
+
+impl NonZeroInteger for NonZeroU16 {
+    type Integer = u16;
+
+    fn from_integer(value: u16) -> Option<Self> {
+        NonZeroU16::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> u16 {
+        value.map_or(0, NonZeroU16::get)
+    }
+}
+
+impl Sealed for NonZeroU32 {}
+
+impl NonZeroInteger for NonZeroU32 {
+    type Integer = u32;
+
+    fn from_integer(value: u32) -> Option<Self> {
+        NonZeroU32::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> u32 {
+        value.map_or(0, NonZeroU32::get)
+    }
+}
+
+impl Sealed for NonZeroU64 {}
+
+impl NonZeroInteger for NonZeroU64 {
+    type Integer = u64;
+
+    fn from_integer(value: u64) -> Option<Self> {
+        NonZeroU64::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> u64 {
+        value.map_or(0, NonZeroU64::get)
+    }
+}
+
+impl Sealed for NonZeroU128 {}
+
+impl NonZeroInteger for NonZeroU128 {
+    type Integer = u128;
+
+    fn from_integer(value: u128) -> Option<Self> {
+        NonZeroU128::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> u128 {
+        value.map_or(0, NonZeroU128::get)
+    }
+}
+
+impl Sealed for NonZeroUsize {}
+
+impl NonZeroInteger for NonZeroUsize {
+    type Integer = usize;
+
+    fn from_integer(value: usize) -> Option<Self> {
+        NonZeroUsize::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> usize {
+        value.map_or(0, NonZeroUsize::get)
+    }
+}
+
+impl Sealed for NonZeroI8 {}
+
+impl NonZeroInteger for NonZeroI8 {
+    type Integer = i8;
+
+    fn from_integer(value: i8) -> Option<Self> {
+        NonZeroI8::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> i8 {
+        value.map_or(0, NonZeroI8::get)
+    }
+}
+
+impl Sealed for NonZeroI16 {}
+
+impl NonZeroInteger for NonZeroI16 {
+    type Integer = i16;
+
+    fn from_integer(value: i16) -> Option<Self> {
+        NonZeroI16::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> i16 {
+        value.map_or(0, NonZeroI16::get)
+    }
+}
+
+impl Sealed for NonZeroI32 {}
+
+impl NonZeroInteger for NonZeroI32 {
+    type Integer = i32;
+
+    fn from_integer(value: i32) -> Option<Self> {
+        NonZeroI32::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> i32 {
+        value.map_or(0, NonZeroI32::get)
+    }
+}
+
+impl Sealed for NonZeroI64 {}
+
+impl NonZeroInteger for NonZeroI64 {
+    type Integer = i64;
+
+    fn from_integer(value: i64) -> Option<Self> {
+        NonZeroI64::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> i64 {
+        value.map_or(0, NonZeroI64::get)
+    }
+}
+
+impl Sealed for NonZeroI128 {}
+
+impl NonZeroInteger for NonZeroI128 {
+    type Integer = i128;
+
+    fn from_integer(value: i128) -> Option<Self> {
+        NonZeroI128::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> i128 {
+        value.map_or(0, NonZeroI128::get)
+    }
+}
+
+impl Sealed for NonZeroIsize {}
+
+impl NonZeroInteger for NonZeroIsize {
+    type Integer = isize;
+
+    fn from_integer(value: isize) -> Option<Self> {
+        NonZeroIsize::new(value)
+    }
+
+    fn into_integer(value: Option<Self>) -> isize {
+        value.map_or(0, NonZeroIsize::get)
+    }
+}
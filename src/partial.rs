@@ -0,0 +1,123 @@
+//! Encode a struct with only some of its `#[bincode(optional)]` fields, e.g. for partial updates.
+
+use crate::{
+    enc::{Encode, Encoder},
+    error::EncodeError,
+};
+
+/// A struct with `#[bincode(optional)]` fields, implemented by `#[derive(Encode)]`.
+///
+/// Such a struct is encoded as a presence mask, followed by its fields. The mask is the smallest unsigned integer type that has a bit for every optional field, e.g. a `u8` for up to 8 optional fields. Bit `i` of the mask is set if the `i`-th optional field is present, and absent fields are not written at all. Fields without the attribute are always written. When decoding, absent fields are set to their `Default` value.
+///
+/// Encoding the struct itself writes every field. Use [PartialStruct] to only write some of the optional fields.
+pub trait PartialEncode: Encode {
+    /// The names of the `#[bincode(optional)]` fields, in the order of their bits in the presence mask. The fields of a tuple struct are named by their index, e.g. `"1"`.
+    fn optional_fields() -> &'static [&'static str];
+
+    /// The presence mask with the bits of every optional field set.
+    fn all_present() -> u64;
+
+    /// Encodes the struct with only the optional fields whose bit is set in `present`.
+    fn encode_partial<E: Encoder>(&self, present: u64, encoder: &mut E) -> Result<(), EncodeError>;
+}
+
+/// Encodes a struct with only the selected `#[bincode(optional)]` fields. See [PartialEncode] for the format.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # fn main() {
+/// use bincode::{Decode, Encode, PartialStruct};
+///
+/// #[derive(Encode, Decode, PartialEq, Debug)]
+/// struct Settings {
+///     id: u32,
+///     #[bincode(optional)]
+///     volume: u8,
+///     #[bincode(optional)]
+///     brightness: u8,
+/// }
+///
+/// let settings = Settings { id: 1, volume: 30, brightness: 70 };
+/// let config = bincode::config::standard();
+///
+/// let mut bytes = [0u8; 16];
+/// let partial = PartialStruct::new(&settings).with_field("brightness");
+/// let len = bincode::encode_into_slice(partial, &mut bytes, config).unwrap();
+/// assert_eq!(bytes[..len], [0b10, 1, 70]);
+///
+/// let (decoded, _): (Settings, usize) = bincode::decode_from_slice(&bytes[..len], config).unwrap();
+/// assert_eq!(decoded, Settings { id: 1, volume: 0, brightness: 70 });
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct PartialStruct<'a, T: ?Sized> {
+    value: &'a T,
+    present: u64,
+}
+
+impl<'a, T: PartialEncode + ?Sized> PartialStruct<'a, T> {
+    /// Encodes `value` without any of its optional fields.
+    pub fn new(value: &'a T) -> Self {
+        Self { value, present: 0 }
+    }
+
+    /// Encodes `value` with all of its optional fields. This is the same as encoding `value` itself.
+    pub fn all(value: &'a T) -> Self {
+        Self {
+            value,
+            present: T::all_present(),
+        }
+    }
+
+    /// Marks the optional field `name` as present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` does not have an optional field called `name`. See [PartialStruct::try_with_field] for a version that returns an error instead.
+    pub fn with_field(self, name: &str) -> Self {
+        match self.try_with_field(name) {
+            Ok(partial) => partial,
+            Err(_) => panic!(
+                "{} does not have an optional field called {}",
+                core::any::type_name::<T>(),
+                name
+            ),
+        }
+    }
+
+    /// Marks the optional field `name` as present.
+    ///
+    /// Returns [EncodeError::UnknownOptionalField] if `T` does not have an optional field called `name`.
+    pub fn try_with_field(mut self, name: &str) -> Result<Self, EncodeError> {
+        match T::optional_fields().iter().position(|field| *field == name) {
+            Some(index) => {
+                self.present |= 1 << index;
+                Ok(self)
+            }
+            None => Err(EncodeError::UnknownOptionalField {
+                type_name: core::any::type_name::<T>(),
+            }),
+        }
+    }
+
+    /// The presence mask that is written, with bit `i` set if the `i`-th optional field is present.
+    pub fn present(&self) -> u64 {
+        self.present
+    }
+}
+
+impl<T: PartialEncode + ?Sized> Encode for PartialStruct<'_, T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.value.encode_partial(self.present, encoder)
+    }
+}
+
+impl<T: ?Sized> Clone for PartialStruct<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for PartialStruct<'_, T> {}
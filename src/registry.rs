@@ -0,0 +1,260 @@
+//! Encode trait objects, like `Box<dyn Trait>`, by tagging every value with the concrete type it was created from.
+//!
+//! A [TypeRegistry] maps every concrete type to a `u32` tag. A registered value is encoded as its tag, followed by the value itself in the same format as [Encoder::encode_length_prefixed]. When decoding, the tag is used to look up the type to decode, and a constructor turns it back into a `Box<dyn Trait>`.
+//!
+//! The concrete type of a trait object is found with [Any::type_id], so the trait must have [Any] as a supertrait. Values of types that were not registered fail to encode with [EncodeError::UnregisteredType], and unknown tags fail to decode with [DecodeError::UnregisteredTag].
+//!
+//! `Box<dyn Trait>` can not implement `Encode` and `Decode` by itself, because it does not know the registry. The registry is passed as the context of the encoder and decoder instead, where [encode_registered] and [decode_registered] will find it:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # fn main() {
+//! use bincode::{
+//!     de::{read::SliceReader, Decoder, DecoderImpl},
+//!     enc::{write::SliceWriter, Encoder, EncoderImpl},
+//!     error::{DecodeError, EncodeError},
+//!     registry::{decode_registered, encode_registered, TypeRegistry},
+//!     Decode, Encode,
+//! };
+//! use core::any::Any;
+//!
+//! trait Shape: Any {
+//!     fn area(&self) -> f64;
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Circle {
+//!     radius: f64,
+//! }
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f64 {
+//!         3.0 * self.radius * self.radius
+//!     }
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Square {
+//!     side: f64,
+//! }
+//!
+//! impl Shape for Square {
+//!     fn area(&self) -> f64 {
+//!         self.side * self.side
+//!     }
+//! }
+//!
+//! struct Shapes(Vec<Box<dyn Shape>>);
+//!
+//! impl Encode for Shapes {
+//!     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+//!         encoder.encode_len(self.0.len())?;
+//!         for shape in &self.0 {
+//!             encode_registered::<dyn Shape, _>(&**shape, encoder)?;
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! impl Decode for Shapes {
+//!     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+//!         let len = decoder.decode_len()?;
+//!         let mut shapes = Vec::new();
+//!         for _ in 0..len {
+//!             shapes.push(decode_registered::<dyn Shape, _>(decoder)?);
+//!         }
+//!         Ok(Shapes(shapes))
+//!     }
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let mut registry = TypeRegistry::<dyn Shape, _>::new(config);
+//! registry
+//!     .register::<Circle>(1, |circle| Box::new(circle))
+//!     .register::<Square>(2, |square| Box::new(square));
+//!
+//! let shapes = Shapes(vec![
+//!     Box::new(Circle { radius: 1.0 }),
+//!     Box::new(Square { side: 2.0 }),
+//! ]);
+//!
+//! let mut buffer = [0u8; 64];
+//! let mut encoder = EncoderImpl::with_context(SliceWriter::new(&mut buffer), config, registry);
+//! shapes.encode(&mut encoder).unwrap();
+//! let registry = encoder.into_context();
+//!
+//! let mut decoder = DecoderImpl::with_context(SliceReader::new(&buffer), config, registry);
+//! let decoded = Shapes::decode(&mut decoder).unwrap();
+//! let areas: Vec<f64> = decoded.0.iter().map(|shape| shape.area()).collect();
+//! assert_eq!(areas, [3.0, 4.0]);
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+//!
+//! [Encoder::encode_length_prefixed]: crate::enc::Encoder::encode_length_prefixed
+
+use crate::{
+    config::{Config, InternalOwnedConfig},
+    de::{read::SliceReader, Decode, Decoder, DecoderImpl},
+    enc::{write::Writer, Encode, Encoder, EncoderImpl},
+    error::{DecodeError, EncodeError},
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+/// A registry of the concrete types that can be stored in a trait object `T`, e.g. `dyn Shape`. See the [module documentation](self) for an example.
+///
+/// The registry is tied to the config `C` that the values are encoded with.
+pub struct TypeRegistry<T: ?Sized + 'static, C> {
+    config: C,
+    entries: Vec<Registration<T, C>>,
+}
+
+struct Registration<T: ?Sized + 'static, C> {
+    tag: u32,
+    type_id: TypeId,
+    /// Only call this with values whose concrete type has `type_id`
+    encode: unsafe fn(&T, C) -> Result<Vec<u8>, EncodeError>,
+    decode: DecodeFn<T, C>,
+}
+
+type DecodeFn<T, C> = Box<dyn Fn(&[u8], C) -> Result<Box<T>, DecodeError>>;
+
+impl<T: ?Sized + Any, C: Config> TypeRegistry<T, C> {
+    /// Creates an empty registry for values that are encoded with `config`.
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers the type `U` with the given `tag`. The `into_box` constructor turns a decoded `U` into a `Box<T>`, and is usually `|value| Box::new(value)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` or `U` is already registered.
+    pub fn register<U>(&mut self, tag: u32, into_box: fn(U) -> Box<T>) -> &mut Self
+    where
+        U: Encode + Decode + 'static,
+    {
+        let type_id = TypeId::of::<U>();
+        assert!(
+            self.entries.iter().all(|entry| entry.tag != tag),
+            "tag {} is already registered",
+            tag
+        );
+        assert!(
+            self.entries.iter().all(|entry| entry.type_id != type_id),
+            "{} is already registered",
+            core::any::type_name::<U>()
+        );
+        self.entries.push(Registration {
+            tag,
+            type_id,
+            encode: encode_as::<T, U, C>,
+            decode: Box::new(move |bytes, config| {
+                // Not `decode_from_slice`, which would read the padding of an aligned config
+                let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config);
+                U::decode(&mut decoder).map(into_box)
+            }),
+        });
+        self
+    }
+
+    /// Encodes `value` as its tag, followed by the length prefixed value.
+    pub fn encode<E: Encoder<C = C>>(&self, value: &T, encoder: &mut E) -> Result<(), EncodeError> {
+        let (tag, bytes) = self.encode_to_bytes(value)?;
+        write_tagged(encoder, tag, &bytes)
+    }
+
+    /// Decodes a value that was encoded with [TypeRegistry::encode].
+    pub fn decode<D: Decoder<C = C>>(&self, decoder: &mut D) -> Result<Box<T>, DecodeError> {
+        let (tag, bytes) = read_tagged(decoder)?;
+        self.decode_from_bytes(tag, &bytes)
+    }
+}
+
+impl<T: ?Sized + Any, C: Copy> TypeRegistry<T, C> {
+    fn encode_to_bytes(&self, value: &T) -> Result<(u32, Vec<u8>), EncodeError> {
+        // `T` is a trait object with `Any` as a supertrait, so this returns the id of the concrete type
+        let type_id = Any::type_id(value);
+        match self.entries.iter().find(|entry| entry.type_id == type_id) {
+            // Safety: the concrete type of `value` is the type of this registration
+            Some(entry) => Ok((entry.tag, unsafe { (entry.encode)(value, self.config) }?)),
+            None => Err(EncodeError::UnregisteredType {
+                type_name: core::any::type_name::<T>(),
+            }),
+        }
+    }
+
+    fn decode_from_bytes(&self, tag: u32, bytes: &[u8]) -> Result<Box<T>, DecodeError> {
+        match self.entries.iter().find(|entry| entry.tag == tag) {
+            Some(entry) => (entry.decode)(bytes, self.config),
+            None => Err(DecodeError::UnregisteredTag { tag }),
+        }
+    }
+}
+
+/// Encodes `value` with [TypeRegistry::encode]. The registry must be the [context](Encoder::context) of the encoder, with the same config as the encoder.
+pub fn encode_registered<T, E>(value: &T, encoder: &mut E) -> Result<(), EncodeError>
+where
+    T: ?Sized + Any,
+    E: Encoder,
+{
+    let registry = encoder
+        .context()
+        .and_then(|context| {
+            context.downcast_ref::<TypeRegistry<T, <E::C as InternalOwnedConfig>::Owned>>()
+        })
+        .ok_or(EncodeError::Other(
+            "The context of the encoder is not a TypeRegistry for this type and config",
+        ))?;
+    let (tag, bytes) = registry.encode_to_bytes(value)?;
+    write_tagged(encoder, tag, &bytes)
+}
+
+/// Decodes a value with [TypeRegistry::decode]. The registry must be the [context](Decoder::context) of the decoder, with the same config as the decoder.
+pub fn decode_registered<T, D>(decoder: &mut D) -> Result<Box<T>, DecodeError>
+where
+    T: ?Sized + Any,
+    D: Decoder,
+{
+    let (tag, bytes) = read_tagged(decoder)?;
+    let registry = decoder
+        .context()
+        .and_then(|context| {
+            context.downcast_ref::<TypeRegistry<T, <D::C as InternalOwnedConfig>::Owned>>()
+        })
+        .ok_or(DecodeError::Other(
+            "The context of the decoder is not a TypeRegistry for this type and config",
+        ))?;
+    registry.decode_from_bytes(tag, &bytes)
+}
+
+/// # Safety
+///
+/// The concrete type of `value` must be `U`.
+unsafe fn encode_as<T: ?Sized, U: Encode, C: Config>(
+    value: &T,
+    config: C,
+) -> Result<Vec<u8>, EncodeError> {
+    let value = &*(value as *const T as *const U);
+    // Not `encode_to_vec`, which would add the padding of an aligned config
+    let mut encoder = EncoderImpl::new(crate::VecWriter::default(), config);
+    value.encode(&mut encoder)?;
+    Ok(encoder.into_writer().collect())
+}
+
+fn write_tagged<E: Encoder>(encoder: &mut E, tag: u32, bytes: &[u8]) -> Result<(), EncodeError> {
+    tag.encode(encoder)?;
+    encoder.encode_len(bytes.len())?;
+    encoder.writer().write(bytes)
+}
+
+fn read_tagged<D: Decoder>(decoder: &mut D) -> Result<(u32, Vec<u8>), DecodeError> {
+    let tag = u32::decode(decoder)?;
+    let bytes = Vec::<u8>::decode(decoder)?;
+    Ok((tag, bytes))
+}
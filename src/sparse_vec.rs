@@ -0,0 +1,152 @@
+//! Sparse vectors that only store their non-default entries.
+
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+use alloc::vec::Vec;
+
+/// A vector of `len` items where only some of the indices hold a value.
+///
+/// The entries are kept sorted by their index. A `SparseVec` is encoded as its `len`, followed by the entries as a collection of `(index, value)` tuples:
+///
+/// ```
+/// use bincode::SparseVec;
+///
+/// let mut vec = SparseVec::new(1000);
+/// vec.set(3, 1.5f64);
+/// vec.set(998, -2.0f64);
+///
+/// let config = bincode::config::standard();
+/// let bytes = bincode::encode_to_vec(&vec, config).unwrap();
+/// assert_eq!(bytes.len(), 3 + 1 + (1 + 8) + (3 + 8));
+///
+/// let (decoded, _): (SparseVec<f64>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(decoded, vec);
+/// assert_eq!(decoded.get(998), Some(&-2.0));
+/// assert_eq!(decoded.get(4), None);
+/// ```
+///
+/// When decoding, the indices must be strictly increasing and less than `len`, otherwise [DecodeError::InvalidSparseIndex] is returned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseVec<T> {
+    len: usize,
+    entries: Vec<(usize, T)>,
+}
+
+impl<T> SparseVec<T> {
+    /// Creates a sparse vector of `len` items without any entries.
+    pub const fn new(len: usize) -> Self {
+        Self {
+            len,
+            entries: Vec::new(),
+        }
+    }
+
+    /// The length of the dense vector that this sparse vector represents.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the length of this vector is 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The entries of this vector, sorted by their index.
+    pub fn entries(&self) -> &[(usize, T)] {
+        &self.entries
+    }
+
+    /// Returns the value at `index`, or `None` if there is no entry for that index.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.entries
+            .binary_search_by_key(&index, |(i, _)| *i)
+            .ok()
+            .map(|position| &self.entries[position].1)
+    }
+
+    /// Sets the value at `index`, and returns the previous value. If `index` is out of bounds, the vector grows to `index + 1` items.
+    pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+        if index >= self.len {
+            self.len = index + 1;
+        }
+        match self.entries.binary_search_by_key(&index, |(i, _)| *i) {
+            Ok(position) => Some(core::mem::replace(&mut self.entries[position].1, value)),
+            Err(position) => {
+                self.entries.insert(position, (index, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the entry at `index`, and returns its value. The length of the vector is not changed.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        self.entries
+            .binary_search_by_key(&index, |(i, _)| *i)
+            .ok()
+            .map(|position| self.entries.remove(position).1)
+    }
+
+    /// Iterates over the entries of this vector, sorted by their index.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.entries.iter().map(|(index, value)| (*index, value))
+    }
+
+    /// Expands this vector into a dense `Vec`, filling the indices without an entry with `T::default()`.
+    pub fn to_dense(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        let mut dense = Vec::new();
+        dense.resize(self.len, T::default());
+        for (index, value) in &self.entries {
+            dense[*index] = value.clone();
+        }
+        dense
+    }
+
+    /// Creates a sparse vector from a dense slice, storing only the items that are not equal to `T::default()`.
+    pub fn from_dense(dense: &[T]) -> Self
+    where
+        T: Clone + Default + PartialEq,
+    {
+        let default = T::default();
+        Self {
+            len: dense.len(),
+            entries: dense
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| **value != default)
+                .map(|(index, value)| (index, value.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Encode> Encode for SparseVec<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.len.encode(encoder)?;
+        self.entries.encode(encoder)
+    }
+}
+
+impl<T: Decode> Decode for SparseVec<T> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = usize::decode(decoder)?;
+        let entries = Vec::<(usize, T)>::decode(decoder)?;
+
+        let mut min_index = 0;
+        for (position, (index, _)) in entries.iter().enumerate() {
+            if *index < min_index || *index >= len {
+                return Err(DecodeError::InvalidSparseIndex {
+                    position,
+                    index: *index,
+                });
+            }
+            min_index = index + 1;
+        }
+        Ok(Self { len, entries })
+    }
+}
@@ -1,3 +1,34 @@
+use crate::config::{IntEncoding, InternalIntEncodingConfig};
+
 pub trait Sealed {}
 
-impl<'a, T> Sealed for &'a mut T where T: Sealed {}
+impl<T> Sealed for &mut T where T: Sealed {}
+
+/// Returns `true` if `T` and `U` are the same type, where `U` is a primitive type like `u8`.
+///
+/// Like [raw_int_size], this compares the [core::any::type_name]s, so it can be used in impls like `impl<T: Decode> Decode for Vec<T>`
+/// where `T` is not `'static`. References are named with their `&`, so `&'a u8` is never equal to `u8`.
+pub(crate) fn is_same_type<T, U>() -> bool {
+    core::any::type_name::<T>() == core::any::type_name::<U>()
+        && core::mem::size_of::<T>() == core::mem::size_of::<U>()
+}
+
+/// If `T` is an integer type that is encoded as its raw bytes with config `C`, return the size of `T`.
+///
+/// `u8` and `i8` are always encoded as a single byte, the other integer types only with [IntEncoding::Fixed].
+///
+/// The type is recognized by its [core::any::type_name] instead of its [core::any::TypeId], so `T` does not have to be `'static`.
+/// Any other type with the same name would be named by its path, e.g. `my_crate::u32`.
+pub(crate) fn raw_int_size<T, C: InternalIntEncodingConfig>() -> Option<usize> {
+    let name = core::any::type_name::<T>();
+    let size = match name {
+        "u8" | "i8" => 1,
+        _ if C::INT_ENCODING != IntEncoding::Fixed => return None,
+        "u16" | "i16" => 2,
+        "u32" | "i32" => 4,
+        "u64" | "i64" => 8,
+        "u128" | "i128" => 16,
+        _ => return None,
+    };
+    (core::mem::size_of::<T>() == size).then_some(size)
+}
@@ -1,9 +1,9 @@
-use core::{convert::TryInto, u32};
+use core::convert::TryInto;
 
 use super::{SINGLE_BYTE_MAX, U128_BYTE, U16_BYTE, U32_BYTE, U64_BYTE};
 use crate::{
     config::Endian,
-    de::read::Reader,
+    de::{read::Reader, usize_from_u64},
     error::{DecodeError, IntegerType},
 };
 
@@ -20,10 +20,11 @@ where
         U16_BYTE => {
             let mut bytes = [0u8; 2];
             read.read(&mut bytes)?;
-            Ok(match endian {
+            let val = match endian {
                 Endian::Big => u16::from_be_bytes(bytes),
                 Endian::Little => u16::from_le_bytes(bytes),
-            })
+            };
+            Ok(canonical(val, SINGLE_BYTE_MAX as u16 + 1)?)
         }
         U32_BYTE => invalid_varint_discriminant(IntegerType::U16, IntegerType::U32),
         U64_BYTE => invalid_varint_discriminant(IntegerType::U16, IntegerType::U64),
@@ -45,18 +46,20 @@ where
         U16_BYTE => {
             let mut bytes = [0u8; 2];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u16::from_be_bytes(bytes) as u32,
-                Endian::Little => u16::from_le_bytes(bytes) as u32,
-            })
+            let val = match endian {
+                Endian::Big => u16::from_be_bytes(bytes),
+                Endian::Little => u16::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u32)
         }
         U32_BYTE => {
             let mut bytes = [0u8; 4];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u32::from_be_bytes(bytes) as u32,
-                Endian::Little => u32::from_le_bytes(bytes) as u32,
-            })
+            let val = match endian {
+                Endian::Big => u32::from_be_bytes(bytes),
+                Endian::Little => u32::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, u16::MAX as u32 + 1)?)
         }
         U64_BYTE => invalid_varint_discriminant(IntegerType::U32, IntegerType::U64),
         U128_BYTE => invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
@@ -77,26 +80,29 @@ where
         U16_BYTE => {
             let mut bytes = [0u8; 2];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u16::from_be_bytes(bytes) as u64,
-                Endian::Little => u16::from_le_bytes(bytes) as u64,
-            })
+            let val = match endian {
+                Endian::Big => u16::from_be_bytes(bytes),
+                Endian::Little => u16::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u64)
         }
         U32_BYTE => {
             let mut bytes = [0u8; 4];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u32::from_be_bytes(bytes) as u64,
-                Endian::Little => u32::from_le_bytes(bytes) as u64,
-            })
+            let val = match endian {
+                Endian::Big => u32::from_be_bytes(bytes),
+                Endian::Little => u32::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, u16::MAX as u32 + 1)? as u64)
         }
         U64_BYTE => {
             let mut bytes = [0u8; 8];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u64::from_be_bytes(bytes) as u64,
-                Endian::Little => u64::from_le_bytes(bytes) as u64,
-            })
+            let val = match endian {
+                Endian::Big => u64::from_be_bytes(bytes),
+                Endian::Little => u64::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, u32::MAX as u64 + 1)?)
         }
         U128_BYTE => invalid_varint_discriminant(IntegerType::U64, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::U64, IntegerType::Reserved),
@@ -116,26 +122,29 @@ where
         U16_BYTE => {
             let mut bytes = [0u8; 2];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u16::from_be_bytes(bytes) as usize,
-                Endian::Little => u16::from_le_bytes(bytes) as usize,
-            })
+            let val = match endian {
+                Endian::Big => u16::from_be_bytes(bytes),
+                Endian::Little => u16::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as usize)
         }
         U32_BYTE => {
             let mut bytes = [0u8; 4];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u32::from_be_bytes(bytes) as usize,
-                Endian::Little => u32::from_le_bytes(bytes) as usize,
-            })
+            let val = match endian {
+                Endian::Big => u32::from_be_bytes(bytes),
+                Endian::Little => u32::from_le_bytes(bytes),
+            };
+            usize_from_u64(canonical(val, u16::MAX as u32 + 1)? as u64)
         }
         U64_BYTE => {
             let mut bytes = [0u8; 8];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u64::from_be_bytes(bytes) as usize,
-                Endian::Little => u64::from_le_bytes(bytes) as usize,
-            })
+            let val = match endian {
+                Endian::Big => u64::from_be_bytes(bytes),
+                Endian::Little => u64::from_le_bytes(bytes),
+            };
+            usize_from_u64(canonical(val, u32::MAX as u64 + 1)?)
         }
         U128_BYTE => invalid_varint_discriminant(IntegerType::Usize, IntegerType::U128),
         _ => invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
@@ -155,39 +164,53 @@ where
         U16_BYTE => {
             let mut bytes = [0u8; 2];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u16::from_be_bytes(bytes) as u128,
-                Endian::Little => u16::from_le_bytes(bytes) as u128,
-            })
+            let val = match endian {
+                Endian::Big => u16::from_be_bytes(bytes),
+                Endian::Little => u16::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u128)
         }
         U32_BYTE => {
             let mut bytes = [0u8; 4];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u32::from_be_bytes(bytes) as u128,
-                Endian::Little => u32::from_le_bytes(bytes) as u128,
-            })
+            let val = match endian {
+                Endian::Big => u32::from_be_bytes(bytes),
+                Endian::Little => u32::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, u16::MAX as u32 + 1)? as u128)
         }
         U64_BYTE => {
             let mut bytes = [0u8; 8];
             read.read(&mut bytes)?;
-            Ok(match endian {
-                Endian::Big => u64::from_be_bytes(bytes) as u128,
-                Endian::Little => u64::from_le_bytes(bytes) as u128,
-            })
+            let val = match endian {
+                Endian::Big => u64::from_be_bytes(bytes),
+                Endian::Little => u64::from_le_bytes(bytes),
+            };
+            Ok(canonical(val, u32::MAX as u64 + 1)? as u128)
         }
         U128_BYTE => {
             let mut bytes = [0u8; 16];
             read.read(&mut bytes)?;
-            Ok(match endian {
+            let val = match endian {
                 Endian::Big => u128::from_be_bytes(bytes),
                 Endian::Little => u128::from_le_bytes(bytes),
-            })
+            };
+            Ok(canonical(val, u64::MAX as u128 + 1)?)
         }
         _ => invalid_varint_discriminant(IntegerType::U128, IntegerType::Reserved),
     }
 }
 
+/// Rejects `value` if it is smaller than `min`, the smallest value that needs the varint encoding it was read from.
+#[inline]
+fn canonical<T: PartialOrd>(value: T, min: T) -> Result<T, DecodeError> {
+    if value >= min {
+        Ok(value)
+    } else {
+        Err(DecodeError::NonCanonicalVarint)
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn invalid_varint_discriminant<T>(
@@ -208,7 +231,7 @@ pub fn varint_decode_u16<R: Reader>(read: &mut R, endian: Endian) -> Result<u16,
                     Endian::Little => u16::from_le_bytes(bytes[..2].try_into().unwrap()),
                 };
 
-                (val, 3)
+                (canonical(val, SINGLE_BYTE_MAX as u16 + 1)?, 3)
             }
             U32_BYTE => return invalid_varint_discriminant(IntegerType::U16, IntegerType::U32),
             U64_BYTE => return invalid_varint_discriminant(IntegerType::U16, IntegerType::U64),
@@ -234,7 +257,7 @@ pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endian) -> Result<u32,
                     Endian::Little => u16::from_le_bytes(bytes[..2].try_into().unwrap()),
                 };
 
-                (val as u32, 3)
+                (canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u32, 3)
             }
             U32_BYTE => {
                 let val = match endian {
@@ -242,7 +265,7 @@ pub fn varint_decode_u32<R: Reader>(read: &mut R, endian: Endian) -> Result<u32,
                     Endian::Little => u32::from_le_bytes(bytes[..4].try_into().unwrap()),
                 };
 
-                (val as u32, 5)
+                (canonical(val, u16::MAX as u32 + 1)?, 5)
             }
             U64_BYTE => return invalid_varint_discriminant(IntegerType::U32, IntegerType::U64),
             U128_BYTE => return invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
@@ -267,7 +290,7 @@ pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endian) -> Result<u64,
                     Endian::Little => u16::from_le_bytes(bytes[..2].try_into().unwrap()),
                 };
 
-                (val as u64, 3)
+                (canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u64, 3)
             }
             U32_BYTE => {
                 let val = match endian {
@@ -275,7 +298,7 @@ pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endian) -> Result<u64,
                     Endian::Little => u32::from_le_bytes(bytes[..4].try_into().unwrap()),
                 };
 
-                (val as u64, 5)
+                (canonical(val, u16::MAX as u32 + 1)? as u64, 5)
             }
             U64_BYTE => {
                 let val = match endian {
@@ -283,10 +306,10 @@ pub fn varint_decode_u64<R: Reader>(read: &mut R, endian: Endian) -> Result<u64,
                     Endian::Little => u64::from_le_bytes(bytes[..8].try_into().unwrap()),
                 };
 
-                (val as u64, 9)
+                (canonical(val, u32::MAX as u64 + 1)?, 9)
             }
-            U128_BYTE => return invalid_varint_discriminant(IntegerType::U32, IntegerType::U128),
-            _ => return invalid_varint_discriminant(IntegerType::U32, IntegerType::Reserved),
+            U128_BYTE => return invalid_varint_discriminant(IntegerType::U64, IntegerType::U128),
+            _ => return invalid_varint_discriminant(IntegerType::U64, IntegerType::Reserved),
         };
 
         read.consume(used);
@@ -307,7 +330,7 @@ pub fn varint_decode_usize<R: Reader>(read: &mut R, endian: Endian) -> Result<us
                     Endian::Little => u16::from_le_bytes(bytes[..2].try_into().unwrap()),
                 };
 
-                (val as usize, 3)
+                (canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as usize, 3)
             }
             U32_BYTE => {
                 let val = match endian {
@@ -315,7 +338,10 @@ pub fn varint_decode_usize<R: Reader>(read: &mut R, endian: Endian) -> Result<us
                     Endian::Little => u32::from_le_bytes(bytes[..4].try_into().unwrap()),
                 };
 
-                (val as usize, 5)
+                (
+                    usize_from_u64(canonical(val, u16::MAX as u32 + 1)? as u64)?,
+                    5,
+                )
             }
             U64_BYTE => {
                 let val = match endian {
@@ -323,7 +349,7 @@ pub fn varint_decode_usize<R: Reader>(read: &mut R, endian: Endian) -> Result<us
                     Endian::Little => u64::from_le_bytes(bytes[..8].try_into().unwrap()),
                 };
 
-                (val as usize, 9)
+                (usize_from_u64(canonical(val, u32::MAX as u64 + 1)?)?, 9)
             }
             U128_BYTE => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::U128),
             _ => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
@@ -347,7 +373,7 @@ pub fn varint_decode_u128<R: Reader>(read: &mut R, endian: Endian) -> Result<u12
                     Endian::Little => u16::from_le_bytes(bytes[..2].try_into().unwrap()),
                 };
 
-                (val as u128, 3)
+                (canonical(val, SINGLE_BYTE_MAX as u16 + 1)? as u128, 3)
             }
             U32_BYTE => {
                 let val = match endian {
@@ -355,7 +381,7 @@ pub fn varint_decode_u128<R: Reader>(read: &mut R, endian: Endian) -> Result<u12
                     Endian::Little => u32::from_le_bytes(bytes[..4].try_into().unwrap()),
                 };
 
-                (val as u128, 5)
+                (canonical(val, u16::MAX as u32 + 1)? as u128, 5)
             }
             U64_BYTE => {
                 let val = match endian {
@@ -363,7 +389,7 @@ pub fn varint_decode_u128<R: Reader>(read: &mut R, endian: Endian) -> Result<u12
                     Endian::Little => u64::from_le_bytes(bytes[..8].try_into().unwrap()),
                 };
 
-                (val as u128, 9)
+                (canonical(val, u32::MAX as u64 + 1)? as u128, 9)
             }
             U128_BYTE => {
                 let val = match endian {
@@ -371,9 +397,9 @@ pub fn varint_decode_u128<R: Reader>(read: &mut R, endian: Endian) -> Result<u12
                     Endian::Little => u128::from_le_bytes(bytes[..16].try_into().unwrap()),
                 };
 
-                (val as u128, 17)
+                (canonical(val, u64::MAX as u128 + 1)?, 17)
             }
-            _ => return invalid_varint_discriminant(IntegerType::Usize, IntegerType::Reserved),
+            _ => return invalid_varint_discriminant(IntegerType::U128, IntegerType::Reserved),
         };
 
         read.consume(used);
@@ -388,7 +414,7 @@ fn test_decode_u16() {
     let cases: &[(&[u8], u16, u16)] = &[
         (&[0], 0, 0),
         (&[10], 10, 10),
-        (&[U16_BYTE, 0, 10], 2560, 10),
+        (&[U16_BYTE, 1, 10], 2_561, 266),
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
@@ -401,6 +427,7 @@ fn test_decode_u16() {
     }
 
     let errors: &[(&[u8], DecodeError)] = &[
+        (&[U16_BYTE, 250, 0], DecodeError::NonCanonicalVarint),
         (
             &[U32_BYTE],
             DecodeError::InvalidIntegerType {
@@ -438,8 +465,8 @@ fn test_decode_u32() {
     let cases: &[(&[u8], u32, u32)] = &[
         (&[0], 0, 0),
         (&[10], 10, 10),
-        (&[U16_BYTE, 0, 10], 2560, 10),
-        (&[U32_BYTE, 0, 0, 0, 10], 167_772_160, 10),
+        (&[U16_BYTE, 1, 10], 2_561, 266),
+        (&[U32_BYTE, 1, 0, 0, 10], 167_772_161, 16_777_226),
     ];
     for &(slice, expected_le, expected_be) in cases {
         let mut reader = crate::de::read::SliceReader::new(slice);
@@ -452,6 +479,12 @@ fn test_decode_u32() {
     }
 
     let errors: &[(&[u8], DecodeError)] = &[
+        (&[U16_BYTE, 250, 0], DecodeError::NonCanonicalVarint),
+        (&[U16_BYTE, 250, 0, 0, 0], DecodeError::NonCanonicalVarint),
+        (
+            &[U32_BYTE, 0xFF, 0xFF, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
         (
             &[U64_BYTE],
             DecodeError::InvalidIntegerType {
@@ -492,12 +525,12 @@ fn test_decode_u64() {
     let cases: &[(&[u8], u64, u64)] = &[
         (&[0], 0, 0),
         (&[10], 10, 10),
-        (&[U16_BYTE, 0, 10], 2560, 10),
-        (&[U32_BYTE, 0, 0, 0, 10], 167_772_160, 10),
+        (&[U16_BYTE, 1, 10], 2_561, 266),
+        (&[U32_BYTE, 1, 0, 0, 10], 167_772_161, 16_777_226),
         (
-            &[U64_BYTE, 0, 0, 0, 0, 0, 0, 0, 10],
-            72_057_594_037_9279_360,
-            10,
+            &[U64_BYTE, 1, 0, 0, 0, 0, 0, 0, 10],
+            720_575_940_379_279_361,
+            72_057_594_037_927_946,
         ),
     ];
     for &(slice, expected_le, expected_be) in cases {
@@ -511,6 +544,23 @@ fn test_decode_u64() {
     }
 
     let errors: &[(&[u8], DecodeError)] = &[
+        (&[U16_BYTE, 250, 0], DecodeError::NonCanonicalVarint),
+        (
+            &[U16_BYTE, 250, 0, 0, 0, 0, 0, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[U32_BYTE, 0xFF, 0xFF, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[U32_BYTE, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[U64_BYTE, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
         (
             &[U128_BYTE],
             DecodeError::InvalidIntegerType {
@@ -518,6 +568,13 @@ fn test_decode_u64() {
                 found: IntegerType::U128,
             },
         ),
+        (
+            &[U128_BYTE, 0, 0, 0, 0, 0, 0, 0, 0],
+            DecodeError::InvalidIntegerType {
+                expected: IntegerType::U64,
+                found: IntegerType::U128,
+            },
+        ),
         (&[U16_BYTE], DecodeError::UnexpectedEnd { additional: 2 }),
         (&[U16_BYTE, 0], DecodeError::UnexpectedEnd { additional: 1 }),
         (&[U32_BYTE], DecodeError::UnexpectedEnd { additional: 4 }),
@@ -570,17 +627,17 @@ fn test_decode_u128() {
     let cases: &[(&[u8], u128, u128)] = &[
         (&[0], 0, 0),
         (&[10], 10, 10),
-        (&[U16_BYTE, 0, 10], 2560, 10),
-        (&[U32_BYTE, 0, 0, 0, 10], 167_772_160, 10),
+        (&[U16_BYTE, 1, 10], 2_561, 266),
+        (&[U32_BYTE, 1, 0, 0, 10], 167_772_161, 16_777_226),
         (
-            &[U64_BYTE, 0, 0, 0, 0, 0, 0, 0, 10],
-            72_057_594_037_9279_360,
-            10,
+            &[U64_BYTE, 1, 0, 0, 0, 0, 0, 0, 10],
+            720_575_940_379_279_361,
+            72_057_594_037_927_946,
         ),
         (
-            &[U128_BYTE, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10],
-            13_292_279_957_849_158_729_038_070_602_803_445_760,
-            10,
+            &[U128_BYTE, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10],
+            13_292_279_957_849_158_729_038_070_602_803_445_761,
+            1_329_227_995_784_915_872_903_807_060_280_344_586,
         ),
     ];
     for &(slice, expected_le, expected_be) in cases {
@@ -594,6 +651,37 @@ fn test_decode_u128() {
     }
 
     let errors: &[(&[u8], DecodeError)] = &[
+        (&[U16_BYTE, 250, 0], DecodeError::NonCanonicalVarint),
+        (
+            &[U16_BYTE, 250, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[U32_BYTE, 0xFF, 0xFF, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[
+                U32_BYTE, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[U64_BYTE, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[
+                U64_BYTE, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+            DecodeError::NonCanonicalVarint,
+        ),
+        (
+            &[
+                U128_BYTE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+            DecodeError::NonCanonicalVarint,
+        ),
         (&[U16_BYTE], DecodeError::UnexpectedEnd { additional: 2 }),
         (&[U16_BYTE, 0], DecodeError::UnexpectedEnd { additional: 1 }),
         (&[U32_BYTE], DecodeError::UnexpectedEnd { additional: 4 }),
@@ -693,6 +781,20 @@ fn test_decode_u128() {
             &[U128_BYTE, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
             DecodeError::UnexpectedEnd { additional: 1 },
         ),
+        (
+            &[255],
+            DecodeError::InvalidIntegerType {
+                expected: IntegerType::U128,
+                found: IntegerType::Reserved,
+            },
+        ),
+        (
+            &[255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            DecodeError::InvalidIntegerType {
+                expected: IntegerType::U128,
+                found: IntegerType::Reserved,
+            },
+        ),
     ];
 
     for (slice, expected) in errors {
@@ -1,9 +1,26 @@
+//! Helpers for the varint format that is used by [Configuration::with_variable_int_encoding].
+//!
+//! An unsigned integer is encoded as a single byte if it is at most 250, or as a marker byte followed by a `u16`, `u32`, `u64` or `u128`. Signed integers are first converted with zigzag encoding. See [Configuration::with_variable_int_encoding] for the details.
+//!
+//! The functions in this module compute the amount of bytes a value takes up, e.g. to calculate the size of a frame before writing it:
+//!
+//! ```
+//! use bincode::varint::{encoded_len_i64, encoded_len_u64};
+//!
+//! assert_eq!(encoded_len_u64(250), 1);
+//! assert_eq!(encoded_len_u64(251), 3);
+//! // -1 is zigzag encoded as 1
+//! assert_eq!(encoded_len_i64(-1), 1);
+//! ```
+//!
+//! [Configuration::with_variable_int_encoding]: crate::config::Configuration::with_variable_int_encoding
+
 mod decode_signed;
 mod decode_unsigned;
 mod encode_signed;
 mod encode_unsigned;
 
-pub use self::{
+pub(crate) use self::{
     decode_signed::{
         varint_decode_i128, varint_decode_i16, varint_decode_i32, varint_decode_i64,
         varint_decode_isize,
@@ -22,8 +39,53 @@ pub use self::{
     },
 };
 
-pub(self) const SINGLE_BYTE_MAX: u8 = 250;
-pub(self) const U16_BYTE: u8 = 251;
-pub(self) const U32_BYTE: u8 = 252;
-pub(self) const U64_BYTE: u8 = 253;
-pub(self) const U128_BYTE: u8 = 254;
+const SINGLE_BYTE_MAX: u8 = 250;
+const U16_BYTE: u8 = 251;
+const U32_BYTE: u8 = 252;
+const U64_BYTE: u8 = 253;
+const U128_BYTE: u8 = 254;
+
+/// The amount of bytes that `val` is encoded in as an unsigned varint, regardless of its original type
+pub(crate) fn varint_size(val: u128) -> usize {
+    if val <= SINGLE_BYTE_MAX as u128 {
+        1
+    } else if val <= u16::MAX as u128 {
+        3
+    } else if val <= u32::MAX as u128 {
+        5
+    } else if val <= u64::MAX as u128 {
+        9
+    } else {
+        17
+    }
+}
+
+/// The amount of bytes that `val` is encoded in as a signed varint, regardless of its original type
+pub(crate) fn varint_size_signed(val: i128) -> usize {
+    // The same zigzag encoding as `varint_encode_i128`
+    varint_size(if val < 0 {
+        !(val as u128) * 2 + 1
+    } else {
+        (val as u128) * 2
+    })
+}
+
+/// The amount of bytes that `value` takes up as a varint: 1, 3, 5 or 9.
+pub fn encoded_len_u64(value: u64) -> usize {
+    varint_size(value.into())
+}
+
+/// The amount of bytes that `value` takes up as a zigzag encoded varint: 1, 3, 5 or 9.
+pub fn encoded_len_i64(value: i64) -> usize {
+    varint_size_signed(value.into())
+}
+
+/// The amount of bytes that `value` takes up as a varint: 1, 3, 5, 9 or 17.
+pub fn encoded_len_u128(value: u128) -> usize {
+    varint_size(value)
+}
+
+/// The amount of bytes that `value` takes up as a zigzag encoded varint: 1, 3, 5, 9 or 17.
+pub fn encoded_len_i128(value: i128) -> usize {
+    varint_size_signed(value)
+}
@@ -10,7 +10,7 @@ use alloc::collections::*;
 use alloc::rc::Rc;
 #[cfg(all(feature = "atomic", not(feature = "serde")))]
 use alloc::sync::Arc;
-use utils::{the_same, the_same_with_comparer};
+use utils::{assert_roundtrip, the_same, the_same_with_comparer};
 
 struct Foo {
     pub a: u32,
@@ -75,7 +75,7 @@ fn test_alloc_commons() {
             map.push(5);
             map
         },
-        |a, b| a.into_iter().collect::<Vec<_>>() == b.into_iter().collect::<Vec<_>>(),
+        |a, b| a.iter().collect::<Vec<_>>() == b.iter().collect::<Vec<_>>(),
     );
     the_same({
         let mut map = BTreeMap::<u32, i32>::new();
@@ -95,6 +95,43 @@ fn test_alloc_commons() {
     });
 }
 
+#[test]
+fn test_vec_deque_wrapped() {
+    let config = bincode::config::standard();
+
+    // Make the items wrap around the end of the internal ring buffer
+    let mut deque = VecDeque::with_capacity(8);
+    deque.extend(0u16..6);
+    deque.drain(..4);
+    deque.extend(6u16..12);
+    deque.rotate_left(3);
+    let (front, back) = deque.as_slices();
+    assert!(!front.is_empty() && !back.is_empty());
+
+    let logical: Vec<u16> = deque.iter().copied().collect();
+    assert_eq!(logical, [7, 8, 9, 10, 11, 4, 5, 6]);
+
+    // Encoded in logical order, the same as a `Vec`
+    let bytes = bincode::encode_to_vec(&deque, config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(&logical, config).unwrap());
+
+    let (decoded, len): (VecDeque<u16>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, deque);
+    assert_eq!(decoded.iter().copied().collect::<Vec<_>>(), logical);
+    assert_eq!(len, bytes.len());
+
+    // Every rotation encodes its own logical order
+    for rotation in 0..deque.len() {
+        let mut rotated = deque.clone();
+        rotated.rotate_right(rotation);
+        let bytes = bincode::encode_to_vec(&rotated, config).unwrap();
+        let (decoded, _): (VecDeque<u16>, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, rotated);
+    }
+}
+
 #[test]
 fn test_container_limits() {
     use bincode::{error::DecodeError, Decode};
@@ -104,7 +141,7 @@ fn test_container_limits() {
     // for this test we'll create a malformed package of a lot of bytes
     let test_cases = &[
         // u64::max_value(), should overflow
-        bincode::encode_to_vec(u64::max_value(), bincode::config::standard()).unwrap(),
+        bincode::encode_to_vec(u64::MAX, bincode::config::standard()).unwrap(),
         // A high value which doesn't overflow, but exceeds the decode limit
         bincode::encode_to_vec(DECODE_LIMIT as u64, bincode::config::standard()).unwrap(),
     ];
@@ -133,3 +170,1590 @@ fn test_container_limits() {
         }
     }
 }
+
+#[test]
+fn test_write_repeated() {
+    use bincode::enc::write::Writer;
+
+    struct Padding(usize);
+
+    impl bincode::Encode for Padding {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            encoder.writer().write_repeated(0, self.0)
+        }
+    }
+
+    let vec = bincode::encode_to_vec(Padding(1024), bincode::config::standard()).unwrap();
+    assert_eq!(vec, [0u8; 1024]);
+
+    // The Vec is resized in one go. Writing the bytes in chunks would double its capacity up to 1024
+    let vec = bincode::encode_to_vec(Padding(1000), bincode::config::standard()).unwrap();
+    assert_eq!(vec, [0u8; 1000]);
+    assert_eq!(vec.capacity(), 1000);
+
+    // A writer that relies on the default implementation
+    #[derive(Default)]
+    struct CountingWriter {
+        bytes: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Writer for CountingWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+            self.calls += 1;
+            self.bytes.extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    let mut writer = CountingWriter::default();
+    bincode::encode_into_writer(Padding(1024), &mut writer, bincode::config::standard()).unwrap();
+    assert_eq!(writer.bytes, [0u8; 1024]);
+    assert!(writer.calls < 1024);
+
+    let mut slice = [1u8; 1024];
+    let len =
+        bincode::encode_into_slice(Padding(1024), &mut slice, bincode::config::standard()).unwrap();
+    assert_eq!(len, 1024);
+    assert_eq!(slice, [0u8; 1024]);
+}
+
+#[test]
+fn test_length_prefixed_shares_state() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        enc::{write::SliceWriter, Encoder, EncoderImpl},
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Prefixed<T>(T);
+
+    impl<T: Encode> Encode for Prefixed<T> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encoder.encode_length_prefixed(&self.0)
+        }
+    }
+
+    impl<T: Decode> Decode for Prefixed<T> {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            decoder.decode_length_prefixed().map(Prefixed)
+        }
+    }
+
+    /// Encodes how many values have been encoded so far, which is counted in the context
+    #[derive(Debug, PartialEq)]
+    struct Counted;
+
+    impl Encode for Counted {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            let counter = encoder
+                .context()
+                .and_then(|ctx| ctx.downcast_mut::<u32>())
+                .ok_or(EncodeError::Other("Missing counter"))?;
+            *counter += 1;
+            let count = *counter;
+            count.encode(encoder)
+        }
+    }
+
+    impl Decode for Counted {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let count = u32::decode(decoder)?;
+            let counter = decoder
+                .context()
+                .and_then(|ctx| ctx.downcast_mut::<u32>())
+                .ok_or(DecodeError::Other("Missing counter"))?;
+            *counter += 1;
+            if count != *counter {
+                return Err(DecodeError::Other("Unexpected count"));
+            }
+            Ok(Counted)
+        }
+    }
+
+    // The value is encoded once, with the context of the outer encoder
+    let config = bincode::config::standard();
+    let counted = Prefixed(vec![Counted, Counted, Counted]);
+    let mut buffer = [0u8; 16];
+    let mut encoder = EncoderImpl::with_context(SliceWriter::new(&mut buffer), config, 0u32);
+    counted.encode(&mut encoder).unwrap();
+    assert_eq!(encoder.into_context(), 3);
+    // the length, the length of the vec, and the counts
+    assert_eq!(buffer[..5], [4, 3, 1, 2, 3]);
+
+    let mut decoder = DecoderImpl::with_context(SliceReader::new(&buffer[..5]), config, 0u32);
+    let decoded = Prefixed::<Vec<Counted>>::decode(&mut decoder).unwrap();
+    assert_eq!(decoded, counted);
+    assert_eq!(decoder.into_context(), 3);
+
+    // The value counts towards the nesting depth of the outer decoder
+    type Nested = Vec<Prefixed<Vec<Vec<u8>>>>;
+    let value: Nested = vec![Prefixed(vec![vec![1u8]])];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let result: Result<(Nested, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_max_depth::<2>());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::DepthLimitExceeded { max: 2 }
+    );
+    let (decoded, _): (Nested, usize) =
+        bincode::decode_from_slice(&bytes, config.with_max_depth::<3>()).unwrap();
+    assert_eq!(decoded, value);
+
+    // The value counts towards the limit of the outer decoder, the same as the length and the values around it
+    type WithTrailer = (Prefixed<Vec<u8>>, u8);
+    let value: WithTrailer = (Prefixed(vec![1u8, 2, 3]), 4u8);
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    assert_eq!(bytes, [4, 3, 1, 2, 3, 4]);
+    // 8 bytes for the length, 8 bytes for the length of the vec, 3 bytes for its items and 1 byte for the `u8`
+    let (decoded, _): (WithTrailer, usize) =
+        bincode::decode_from_slice(&bytes, config.with_limit::<20>()).unwrap();
+    assert_eq!(decoded, value);
+    let result: Result<(WithTrailer, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_limit::<19>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+}
+
+#[test]
+fn test_max_collection_len() {
+    use bincode::error::DecodeError;
+
+    const MAX_LEN: usize = 16;
+    let config = bincode::config::standard().with_max_collection_len::<MAX_LEN>();
+
+    let bytes = bincode::encode_to_vec(vec![0u8; MAX_LEN], config).unwrap();
+    let (decoded, _): (Vec<u8>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.len(), MAX_LEN);
+
+    let bytes = bincode::encode_to_vec(vec![0u8; MAX_LEN + 1], config).unwrap();
+    let result: Result<(Vec<u8>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong {
+            len: MAX_LEN + 1,
+            max: MAX_LEN
+        }
+    );
+
+    // Only the length prefix is needed to reject the collection
+    let result: Result<(String, usize), _> = bincode::decode_from_slice(&bytes[..1], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong {
+            len: MAX_LEN + 1,
+            max: MAX_LEN
+        }
+    );
+}
+
+#[test]
+fn test_unit() {
+    let config = bincode::config::standard();
+    assert!(bincode::encode_to_vec((), config).unwrap().is_empty());
+
+    let ((), len): ((), usize) = bincode::decode_from_slice(&[], config).unwrap();
+    assert_eq!(len, 0);
+
+    // `()` does not consume any of the input
+    let ((), len): ((), usize) = bincode::decode_from_slice(&[1, 2, 3], config).unwrap();
+    assert_eq!(len, 0);
+
+    let bytes = bincode::encode_to_vec(vec![(); 5], config).unwrap();
+    assert_eq!(bytes, [5]);
+    let (decoded, len): (Vec<()>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, vec![(); 5]);
+    assert_eq!(len, 1);
+}
+
+#[test]
+fn test_decode_map_entries() {
+    use bincode::{de::read::SliceReader, error::DecodeError};
+
+    let config = bincode::config::standard();
+    let map: BTreeMap<u32, u64> = (0..1000).map(|i| (i, i as u64 * 2)).collect();
+    let bytes = bincode::encode_to_vec(&map, config).unwrap();
+
+    let mut count = 0;
+    let mut sum = 0;
+    let decoded =
+        bincode::decode_map_entries(SliceReader::new(&bytes), config, |key: u32, value: u64| {
+            assert_eq!(value, key as u64 * 2);
+            count += 1;
+            sum += value;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(decoded, 1000);
+    assert_eq!(count, 1000);
+    assert_eq!(sum, map.values().sum());
+
+    // Errors of the callback stop decoding
+    let mut count = 0;
+    let result =
+        bincode::decode_map_entries(SliceReader::new(&bytes), config, |key: u32, _: u64| {
+            count += 1;
+            if key == 10 {
+                Err(DecodeError::Other("Stop"))
+            } else {
+                Ok(())
+            }
+        });
+    assert_eq!(result.unwrap_err(), DecodeError::Other("Stop"));
+    assert_eq!(count, 11);
+
+    // Truncated input
+    let result = bincode::decode_map_entries(
+        SliceReader::new(&bytes[..bytes.len() - 1]),
+        config,
+        |_: u32, _: u64| Ok(()),
+    );
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
+
+#[test]
+fn test_decode_seq() {
+    use bincode::{de::read::SliceReader, error::DecodeError};
+
+    let config = bincode::config::standard();
+    let vec: Vec<u64> = (0..100_000).map(|i| i * 3).collect();
+    let bytes = bincode::encode_to_vec(&vec, config).unwrap();
+
+    let mut count = 0;
+    let mut sum = 0;
+    for item in bincode::decode_seq::<u64, _, _>(SliceReader::new(&bytes), config) {
+        count += 1;
+        sum += item.unwrap();
+    }
+    assert_eq!(count, vec.len());
+    assert_eq!(sum, vec.iter().sum());
+
+    // Items are decoded on demand
+    let mut iter = bincode::decode_seq::<u64, _, _>(SliceReader::new(&bytes), config);
+    assert_eq!(iter.next(), Some(Ok(0)));
+    assert_eq!(iter.nth(9), Some(Ok(30)));
+
+    // An empty sequence
+    let bytes = bincode::encode_to_vec(Vec::<u64>::new(), config).unwrap();
+    let mut iter = bincode::decode_seq::<u64, _, _>(SliceReader::new(&bytes), config);
+    assert_eq!(iter.next(), None);
+
+    // Errors stop the iterator
+    let bytes = bincode::encode_to_vec(vec![1u8, 2, 3], config).unwrap();
+    let items: Vec<_> =
+        bincode::decode_seq::<u8, _, _>(SliceReader::new(&bytes[..3]), config).collect();
+    assert_eq!(
+        items,
+        [
+            Ok(1),
+            Ok(2),
+            Err(DecodeError::UnexpectedEnd { additional: 1 })
+        ]
+    );
+    let items: Vec<_> = bincode::decode_seq::<u8, _, _>(SliceReader::new(&[]), config).collect();
+    assert_eq!(items, [Err(DecodeError::UnexpectedEnd { additional: 1 })]);
+}
+
+#[test]
+fn test_bit_packed() {
+    use bincode::{error::DecodeError, BitPacked};
+
+    let config = bincode::config::standard();
+
+    for len in [0usize, 1, 7, 8, 9, 13, 16, 100] {
+        let bits = BitPacked((0..len).map(|i| i % 3 == 0).collect());
+        let bytes = bincode::encode_to_vec(&bits, config).unwrap();
+        // 1 byte for the length, and the bits rounded up to whole bytes
+        assert_eq!(bytes.len(), 1 + len.div_ceil(8));
+
+        let (decoded, decoded_len): (BitPacked, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, bits);
+        assert_eq!(decoded_len, bytes.len());
+    }
+
+    let bits = BitPacked(vec![true; 13]);
+    let bytes = bincode::encode_to_vec(&bits, config).unwrap();
+    assert_eq!(bytes, [13, 0xFF, 0b0001_1111]);
+    // Compared to one byte per bool
+    assert_eq!(bincode::encode_to_vec(&bits.0, config).unwrap().len(), 14);
+
+    let result: Result<(BitPacked, usize), _> = bincode::decode_from_slice(&bytes[..2], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    let result: Result<(BitPacked, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_limit::<1>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+
+    // The unused bits of the last byte must be zero
+    let result: Result<(BitPacked, usize), _> =
+        bincode::decode_from_slice(&[13, 0xFF, 0b0011_1111], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidBitPadding { byte: 0b0011_1111 }
+    );
+    let result: Result<(BitPacked, usize), _> = bincode::decode_from_slice(&[1, 0b10], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidBitPadding { byte: 0b10 }
+    );
+}
+
+#[test]
+fn test_roundtrip_collections() {
+    fn check_all<C: bincode::config::Config>(config: C) {
+        assert_roundtrip(vec![1u32, 2, 3], config);
+        assert_roundtrip(vec![String::from("a"), String::from("bc")], config);
+        assert_roundtrip(vec![vec![1u8], vec![], vec![2, 3]], config);
+        assert_roundtrip(VecDeque::from(vec![(1u8, 2u16), (3, 4)]), config);
+        assert_roundtrip(
+            [(1u8, 'a'), (2, 'b')]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+            config,
+        );
+        assert_roundtrip(
+            [1i64, -1, i64::MAX].into_iter().collect::<BTreeSet<_>>(),
+            config,
+        );
+        assert_roundtrip(Box::<[u32]>::from(vec![1, 2]), config);
+        assert_roundtrip(Cow::<str>::Owned(String::from("cow")), config);
+        assert_roundtrip(Some(vec![Some(1u8), None]), config);
+        assert_roundtrip(bincode::BitPacked(vec![true, false, true]), config);
+        #[cfg(feature = "std")]
+        assert_roundtrip(
+            [(String::from("key"), 5u64)]
+                .into_iter()
+                .collect::<std::collections::HashMap<_, _>>(),
+            config,
+        );
+    }
+
+    check_all(bincode::config::standard());
+    check_all(bincode::config::legacy());
+    check_all(bincode::config::standard().with_limit::<1024>());
+}
+
+#[test]
+fn test_string_len_in_chars() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard().with_string_len_in_chars();
+    // 4 chars, but 7 bytes
+    let input = "aé€b";
+    assert_eq!(input.chars().count(), 4);
+    assert_eq!(input.len(), 7);
+
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+    assert_eq!(bytes, [4, b'a', 0xC3, 0xA9, 0xE2, 0x82, 0xAC, b'b']);
+    assert_eq!(
+        bincode::encode_to_vec(String::from(input), config).unwrap(),
+        bytes
+    );
+    // The default is a byte count
+    assert_eq!(
+        bincode::encode_to_vec(input, bincode::config::standard()).unwrap()[0],
+        7
+    );
+
+    let (output, len): (String, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(len, 8);
+    let (output, len): (&str, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(len, 8);
+    assert_roundtrip(
+        (
+            String::from("ß"),
+            vec![String::from("日本語"), String::new()],
+        ),
+        config.with_fixed_int_encoding(),
+    );
+
+    // Invalid and truncated UTF-8 are detected
+    let invalid = [2, b'a', 0xFF];
+    let result: Result<(String, usize), _> = bincode::decode_from_slice(&invalid, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidCharEncoding([0xFF, 0, 0, 0])
+    );
+    let result: Result<(&str, usize), _> = bincode::decode_from_slice(&invalid, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidCharEncoding([0xFF, 0, 0, 0])
+    );
+    let result: Result<(String, usize), _> = bincode::decode_from_slice(&bytes[..7], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+    let result: Result<(&str, usize), _> = bincode::decode_from_slice(&bytes[..5], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+}
+
+#[test]
+fn test_zst_container_len() {
+    use bincode::{de::DEFAULT_MAX_ZST_CONTAINER_LEN, error::DecodeError};
+
+    // Without a limit or a max collection length, the length is bounded by the default maximum
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(usize::MAX, config).unwrap();
+    let result: Result<(Vec<()>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong {
+            len: usize::MAX,
+            max: DEFAULT_MAX_ZST_CONTAINER_LEN
+        }
+    );
+    let result: Result<(BTreeSet<()>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong {
+            len: usize::MAX,
+            max: DEFAULT_MAX_ZST_CONTAINER_LEN
+        }
+    );
+
+    assert_roundtrip(vec![(); DEFAULT_MAX_ZST_CONTAINER_LEN], config);
+    let bytes =
+        bincode::encode_to_vec(vec![(); DEFAULT_MAX_ZST_CONTAINER_LEN + 1], config).unwrap();
+    let result: Result<(Vec<()>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong { .. }
+    ));
+
+    // A configured max collection length replaces the default
+    let config = config.with_max_collection_len::<{ DEFAULT_MAX_ZST_CONTAINER_LEN * 2 }>();
+    assert_roundtrip(vec![(); DEFAULT_MAX_ZST_CONTAINER_LEN + 1], config);
+    let config = config.with_max_collection_len::<2>();
+    let bytes = bincode::encode_to_vec(vec![(); 3], config).unwrap();
+    let result: Result<(Vec<()>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong { len: 3, max: 2 }
+    );
+}
+
+#[test]
+fn test_strict_map_ordering() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard().with_strict_map_ordering();
+    // A `Vec` of tuples is encoded the same as a map
+    let unsorted = bincode::encode_to_vec(vec![(1u32, 'a'), (3, 'b'), (2, 'c')], config).unwrap();
+    let duplicated = bincode::encode_to_vec(vec![(1u32, 'a'), (1, 'b')], config).unwrap();
+
+    let result: Result<(BTreeMap<u32, char>, usize), _> =
+        bincode::decode_from_slice(&unsorted, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidMapOrdering { index: 2 }
+    );
+    let result: Result<(BTreeMap<u32, char>, usize), _> =
+        bincode::decode_from_slice(&duplicated, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidMapOrdering { index: 1 }
+    );
+
+    // Maps written by bincode are always sorted
+    let map: BTreeMap<u32, char> = [(5, 'a'), (1, 'b'), (3, 'c')].into_iter().collect();
+    assert_roundtrip(map, config);
+    assert_roundtrip(BTreeMap::<String, u8>::new(), config);
+
+    // Any order is accepted by default
+    let config = config.with_unchecked_map_ordering();
+    let (map, _): (BTreeMap<u32, char>, usize) =
+        bincode::decode_from_slice(&unsorted, config).unwrap();
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        [(1, 'a'), (2, 'c'), (3, 'b')]
+    );
+    let (map, _): (BTreeMap<u32, char>, usize) =
+        bincode::decode_from_slice(&duplicated, bincode::config::standard()).unwrap();
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, 'b')]);
+}
+
+#[test]
+fn test_reverse_map_keys() {
+    use core::cmp::Reverse;
+
+    let map: BTreeMap<Reverse<u64>, &str> =
+        [(Reverse(1), "a"), (Reverse(30), "b"), (Reverse(7), "c")]
+            .into_iter()
+            .collect();
+
+    // The keys are written in the order of the map, which is descending for `Reverse` keys
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(&map, config).unwrap();
+    assert_eq!(bytes[..3], [3, 30, 1]);
+
+    let expected = [(30, "b"), (7, "c"), (1, "a")];
+    let (decoded, _): (BTreeMap<Reverse<u64>, String>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    let decoded: Vec<_> = decoded
+        .iter()
+        .map(|(Reverse(k), v)| (*k, v.as_str()))
+        .collect();
+    assert_eq!(decoded, expected);
+
+    // The map ordering check compares the keys with `Ord`, so descending `Reverse` keys are in order
+    let (decoded, _): (BTreeMap<Reverse<u64>, String>, usize) =
+        bincode::decode_from_slice(&bytes, config.with_strict_map_ordering()).unwrap();
+    let decoded: Vec<_> = decoded
+        .iter()
+        .map(|(Reverse(k), v)| (*k, v.as_str()))
+        .collect();
+    assert_eq!(decoded, expected);
+}
+
+#[test]
+fn test_read_until() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        error::DecodeError,
+        Decode,
+    };
+
+    // Two NUL-terminated segments, followed by a regular value
+    let bytes = b"first\0\0second\0\x05";
+    let config = bincode::config::standard();
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config);
+    assert_eq!(decoder.read_until(0).unwrap(), b"first");
+    assert_eq!(decoder.read_until(0).unwrap(), b"");
+    assert_eq!(decoder.read_until(0).unwrap(), b"second");
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 5);
+
+    // The reader ends before the delimiter is found
+    let mut decoder = DecoderImpl::new(SliceReader::new(b"no end"), config);
+    assert_eq!(
+        decoder.read_until(0).unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    // The bytes and the delimiter count towards the limit
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config.with_limit::<6>());
+    assert_eq!(decoder.read_until(0).unwrap(), b"first");
+    assert_eq!(
+        decoder.read_until(0).unwrap_err(),
+        DecodeError::LimitExceeded
+    );
+
+    // The bytes are collected in a `Vec`, which is not allowed without allocation
+    let mut decoder = DecoderImpl::new(SliceReader::new(bytes), config.with_no_allocation());
+    assert!(matches!(
+        decoder.read_until(0),
+        Err(DecodeError::AllocationDisallowed { .. })
+    ));
+}
+
+#[test]
+fn test_tee_writer() {
+    use bincode::enc::write::{SliceWriter, TeeWriter, Writer};
+    use bincode::error::EncodeError;
+
+    struct VecWriter(Vec<u8>);
+
+    impl Writer for VecWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+            self.0.extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let value = (
+        String::from("Hello world"),
+        vec![1u32, 300, 70_000],
+        bincode::BitPacked(vec![true; 20]),
+    );
+    let mut writer = TeeWriter::new(VecWriter(Vec::new()), VecWriter(Vec::new()));
+    bincode::encode_into_writer(&value, &mut writer, config).unwrap();
+    writer.write_repeated(0xAB, 3).unwrap();
+
+    let (a, b) = writer.into_inner();
+    let mut expected = bincode::encode_to_vec(&value, config).unwrap();
+    expected.extend_from_slice(&[0xAB; 3]);
+    assert_eq!(a.0, expected);
+    assert_eq!(b.0, expected);
+
+    // The first error stops writing
+    let mut buffer = [0u8; 2];
+    let mut writer = TeeWriter::new(SliceWriter::new(&mut buffer), VecWriter(Vec::new()));
+    assert!(matches!(
+        writer.write(&[1, 2, 3]).unwrap_err(),
+        EncodeError::BufferFull {
+            required: 3,
+            capacity: 2
+        }
+    ));
+    let (_, b) = writer.into_inner();
+    assert!(b.0.is_empty());
+}
+
+#[test]
+fn test_custom_container_len() {
+    use bincode::{
+        de::{Decode, Decoder},
+        enc::{Encode, Encoder},
+        error::{DecodeError, EncodeError},
+    };
+
+    /// A stack that is encoded from the top down
+    #[derive(PartialEq, Debug)]
+    struct Stack<T>(Vec<T>);
+
+    impl<T: Encode> Encode for Stack<T> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encoder.encode_len(self.0.len())?;
+            for item in self.0.iter().rev() {
+                item.encode(encoder)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Decode> Decode for Stack<T> {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = decoder.decode_len()?;
+            decoder.claim_container_read::<T>(len)?;
+
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                items.push(T::decode(decoder)?);
+            }
+            items.reverse();
+            Ok(Stack(items))
+        }
+    }
+
+    fn check<C: bincode::config::Config>(config: C) {
+        let stack = Stack(vec![1u32, 2, 300, 70_000]);
+        // The length is encoded the same as the length of a `Vec`
+        let bytes = bincode::encode_to_vec(&stack, config).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::encode_to_vec(vec![70_000u32, 300, 2, 1], config).unwrap()
+        );
+        assert_roundtrip(stack, config);
+        assert_roundtrip(Stack::<String>(Vec::new()), config);
+    }
+    check(bincode::config::standard());
+    check(bincode::config::legacy());
+
+    // The configured max collection length applies
+    let config = bincode::config::standard().with_max_collection_len::<3>();
+    let bytes = bincode::encode_to_vec(Stack(vec![1u8, 2, 3, 4]), config).unwrap();
+    let result: Result<(Stack<u8>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong { len: 4, max: 3 }
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "never un-claimed with `unclaim_bytes_read`")]
+fn test_unbalanced_container_claim() {
+    use bincode::{
+        de::{Decode, Decoder},
+        error::DecodeError,
+    };
+
+    struct Container(#[allow(dead_code)] Vec<u32>);
+
+    impl Decode for Container {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = decoder.decode_len()?;
+            decoder.claim_container_read::<u32>(len)?;
+
+            let mut items = Vec::with_capacity(len);
+            // Forgets to un-claim the last item
+            for _ in 1..len {
+                decoder.unclaim_bytes_read(core::mem::size_of::<u32>());
+                items.push(u32::decode(decoder)?);
+            }
+            items.push(u32::decode(decoder)?);
+            Ok(Container(items))
+        }
+    }
+
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(vec![1u32, 2, 3], config).unwrap();
+    let _: (Container, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+}
+
+#[test]
+fn test_transcode() {
+    use bincode::error::{DecodeError, TranscodeError};
+
+    let old_config = bincode::config::legacy().with_big_endian();
+    let new_config = bincode::config::standard();
+
+    let old = bincode::encode_to_vec(Foo { a: 5, b: 70_000 }, old_config).unwrap();
+    assert_eq!(old, [0, 0, 0, 5, 0, 1, 0x11, 0x70]);
+
+    let new = bincode::transcode::<Foo, _, _>(&old, old_config, new_config).unwrap();
+    assert_eq!(
+        new,
+        bincode::encode_to_vec(Foo { a: 5, b: 70_000 }, new_config).unwrap()
+    );
+    let (foo, _): (Foo, usize) = bincode::decode_from_slice(&new, new_config).unwrap();
+    assert_eq!((foo.a, foo.b), (5, 70_000));
+
+    // And back again
+    let back = bincode::transcode::<Foo, _, _>(&new, new_config, old_config).unwrap();
+    assert_eq!(back, old);
+
+    // The input must be valid for the original config
+    let err = bincode::transcode::<Foo, _, _>(&old[..7], old_config, new_config).unwrap_err();
+    assert!(matches!(
+        err,
+        TranscodeError::Decode(DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+}
+
+#[test]
+fn test_vec_of_byte_vecs() {
+    use bincode::error::DecodeError;
+
+    // Many small blobs, of varying lengths
+    let blobs: Vec<Vec<u8>> = (0..1000u32)
+        .map(|i| (0..i % 17).map(|j| (i + j) as u8).collect())
+        .collect();
+
+    // `VecDeque<u8>` has the same encoding as `Vec<u8>`, but always encodes and decodes one byte at a time
+    let generic: VecDeque<VecDeque<u8>> =
+        blobs.iter().map(|b| b.iter().copied().collect()).collect();
+
+    fn check<C: bincode::config::Config>(
+        blobs: &Vec<Vec<u8>>,
+        generic: &VecDeque<VecDeque<u8>>,
+        config: C,
+    ) {
+        let bytes = bincode::encode_to_vec(blobs, config).unwrap();
+        assert_eq!(bytes, bincode::encode_to_vec(generic, config).unwrap());
+
+        let (decoded, len): (Vec<Vec<u8>>, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(&decoded, blobs);
+        assert_eq!(len, bytes.len());
+
+        let (decoded, _): (VecDeque<VecDeque<u8>>, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(&decoded, generic);
+
+        // Truncated input is still rejected
+        let result: Result<(Vec<Vec<u8>>, usize), _> =
+            bincode::decode_from_slice(&bytes[..bytes.len() - 1], config);
+        assert!(matches!(
+            result.unwrap_err(),
+            DecodeError::UnexpectedEnd { .. }
+        ));
+    }
+    check(&blobs, &generic, bincode::config::standard());
+    check(
+        &blobs,
+        &generic,
+        bincode::config::standard().with_fixed_int_encoding(),
+    );
+
+    // The bulk path counts towards the limit exactly like the generic path
+    fn check_limit<const N: usize>(bytes: &[u8]) -> bool {
+        let config = bincode::config::standard().with_limit::<N>();
+        let bulk = bincode::decode_from_slice::<Vec<Vec<u8>>, _>(bytes, config).map(|(_, len)| len);
+        let generic = bincode::decode_from_slice::<VecDeque<VecDeque<u8>>, _>(bytes, config)
+            .map(|(_, len)| len);
+        assert_eq!(bulk, generic);
+        bulk.is_ok()
+    }
+    let bytes =
+        bincode::encode_to_vec(vec![vec![0u8; 64]; 2], bincode::config::standard()).unwrap();
+    assert!(!check_limit::<100>(&bytes));
+    assert!(!check_limit::<131>(&bytes));
+    assert!(!check_limit::<150>(&bytes));
+    assert!(check_limit::<155>(&bytes));
+    assert!(check_limit::<200>(&bytes));
+}
+
+#[test]
+fn test_decode_from_reader_into() {
+    use bincode::{
+        de::{read::SliceReader, DecoderImpl},
+        Decode,
+    };
+
+    let config = bincode::config::standard();
+    let mut bytes = Vec::new();
+    for message in [vec![1u32, 2, 3, 4], vec![5, 6], vec![7, 8, 9]] {
+        bytes.extend(bincode::encode_to_vec(message, config).unwrap());
+    }
+
+    let mut reader = SliceReader::new(&bytes);
+    let mut scratch: Vec<u32> = Vec::with_capacity(8);
+    let ptr = scratch.as_ptr();
+    let capacity = scratch.capacity();
+
+    let expected: [&[u32]; 3] = [&[1, 2, 3, 4], &[5, 6], &[7, 8, 9]];
+    for expected in expected {
+        bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+        assert_eq!(scratch, expected);
+        // The allocation is reused
+        assert_eq!(scratch.as_ptr(), ptr);
+        assert_eq!(scratch.capacity(), capacity);
+    }
+    assert!(bincode::decode_from_reader_into(&mut scratch, &mut reader, config).is_err());
+
+    // Byte vecs are read in bulk, into the same allocation
+    let mut bytes = Vec::new();
+    for message in [vec![1u8; 16], vec![2; 4], vec![3; 10]] {
+        bytes.extend(bincode::encode_to_vec(message, config).unwrap());
+    }
+    let mut reader = SliceReader::new(&bytes);
+    let mut scratch: Vec<u8> = Vec::new();
+    bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+    assert_eq!(scratch, [1; 16]);
+    let ptr = scratch.as_ptr();
+    let capacity = scratch.capacity();
+    bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+    assert_eq!(scratch, [2; 4]);
+    bincode::decode_from_reader_into(&mut scratch, &mut reader, config).unwrap();
+    assert_eq!(scratch, [3; 10]);
+    assert_eq!(scratch.as_ptr(), ptr);
+    assert_eq!(scratch.capacity(), capacity);
+
+    // Any type can be decoded in place, a `Vec` reuses its allocation
+    let bytes = bincode::encode_to_vec((5u32, vec![1u16, 2]), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    let mut number = 0u32;
+    let mut items: Vec<u16> = Vec::with_capacity(4);
+    let ptr = items.as_ptr();
+    u32::decode_in_place(&mut decoder, &mut number).unwrap();
+    Vec::decode_in_place(&mut decoder, &mut items).unwrap();
+    assert_eq!(number, 5);
+    assert_eq!(items, [1, 2]);
+    assert_eq!(items.as_ptr(), ptr);
+}
+
+#[test]
+fn test_borrow_decode_vec_of_str() {
+    let config = bincode::config::standard();
+    let strings = vec!["first", "", "third one", "ünïcödé"];
+    let bytes = bincode::encode_to_vec(&strings, config).unwrap();
+
+    let (decoded, len): (Vec<&str>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, strings);
+    assert_eq!(len, bytes.len());
+
+    // Every string points into the input buffer, so no string was allocated
+    let mut offset = 1;
+    for s in &decoded {
+        offset += 1;
+        assert_eq!(s.as_ptr(), bytes[offset..].as_ptr());
+        offset += s.len();
+    }
+
+    // Encoded the same as a `Vec<String>`
+    let owned: Vec<String> = strings.iter().map(|s| s.to_string()).collect();
+    assert_eq!(bytes, bincode::encode_to_vec(&owned, config).unwrap());
+
+    let blobs: Vec<&[u8]> = vec![b"ab", b"", b"\xFF\x00"];
+    let bytes = bincode::encode_to_vec(&blobs, config).unwrap();
+    let (decoded, _): (Vec<&[u8]>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, blobs);
+    assert_eq!(decoded[2].as_ptr(), bytes[bytes.len() - 2..].as_ptr());
+
+    // Invalid UTF-8 is still rejected
+    let bytes = bincode::encode_to_vec(&blobs, config).unwrap();
+    let result: Result<(Vec<&str>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::Utf8(_)
+    ));
+}
+
+#[test]
+fn test_sparse_vec() {
+    use bincode::{error::DecodeError, SparseVec};
+
+    let config = bincode::config::standard();
+
+    let mut vec = SparseVec::new(100);
+    vec.set(0, 1.0f64);
+    vec.set(17, -3.5);
+    vec.set(5, 2.25);
+    vec.set(99, 8.0);
+    assert_eq!(vec.set(17, 4.5), Some(-3.5));
+    assert_eq!(
+        vec.iter().map(|(index, _)| index).collect::<Vec<_>>(),
+        [0, 5, 17, 99]
+    );
+
+    let bytes = bincode::encode_to_vec(&vec, config).unwrap();
+    let (decoded, len): (SparseVec<f64>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded, vec);
+    assert_eq!(decoded.len(), 100);
+    assert_eq!(decoded.get(5), Some(&2.25));
+    assert_eq!(decoded.get(6), None);
+
+    let dense = decoded.to_dense();
+    assert_eq!(dense.len(), 100);
+    assert_eq!(dense[17], 4.5);
+    assert_eq!(dense[18], 0.0);
+    assert_eq!(SparseVec::from_dense(&dense), vec);
+
+    // Setting an index past the end grows the vector
+    let mut grown = decoded;
+    grown.set(150, 1.0);
+    assert_eq!(grown.len(), 151);
+    assert_eq!(grown.remove(150), Some(1.0));
+    assert_eq!(grown.len(), 151);
+
+    // Indices that are out of bounds or out of order are rejected
+    let out_of_bounds = bincode::encode_to_vec((3usize, vec![(3usize, 1u8)]), config).unwrap();
+    let unsorted = bincode::encode_to_vec((5usize, vec![(2usize, 1u8), (2, 2)]), config).unwrap();
+    assert!(matches!(
+        bincode::decode_from_slice::<SparseVec<u8>, _>(&out_of_bounds, config),
+        Err(DecodeError::InvalidSparseIndex {
+            position: 0,
+            index: 3
+        })
+    ));
+    assert!(matches!(
+        bincode::decode_from_slice::<SparseVec<u8>, _>(&unsorted, config),
+        Err(DecodeError::InvalidSparseIndex {
+            position: 1,
+            index: 2
+        })
+    ));
+}
+
+#[test]
+fn test_type_registry() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        enc::{write::SliceWriter, Encoder, EncoderImpl},
+        error::{DecodeError, EncodeError},
+        registry::{decode_registered, encode_registered, TypeRegistry},
+        Decode, Encode,
+    };
+    use core::any::Any;
+
+    trait Animal: Any {
+        fn name(&self) -> String;
+    }
+
+    struct Dog {
+        age: u8,
+    }
+
+    impl Animal for Dog {
+        fn name(&self) -> String {
+            format!("dog of {}", self.age)
+        }
+    }
+
+    impl Encode for Dog {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.age.encode(encoder)
+        }
+    }
+
+    impl Decode for Dog {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Dog {
+                age: u8::decode(decoder)?,
+            })
+        }
+    }
+
+    struct Cat {
+        name: String,
+    }
+
+    impl Animal for Cat {
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+    }
+
+    impl Encode for Cat {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.name.encode(encoder)
+        }
+    }
+
+    impl Decode for Cat {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Cat {
+                name: String::decode(decoder)?,
+            })
+        }
+    }
+
+    struct Fish;
+
+    impl Animal for Fish {
+        fn name(&self) -> String {
+            String::from("fish")
+        }
+    }
+
+    struct Pet(Box<dyn Animal>);
+
+    impl Encode for Pet {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encode_registered::<dyn Animal, _>(&*self.0, encoder)
+        }
+    }
+
+    impl Decode for Pet {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            decode_registered::<dyn Animal, _>(decoder).map(Pet)
+        }
+    }
+
+    let config = bincode::config::standard();
+    let mut registry = TypeRegistry::<dyn Animal, _>::new(config);
+    registry
+        .register::<Dog>(7, |dog| Box::new(dog))
+        .register::<Cat>(300, |cat| Box::new(cat));
+
+    let pets = vec![
+        Pet(Box::new(Cat {
+            name: String::from("Tom"),
+        })),
+        Pet(Box::new(Dog { age: 3 })),
+    ];
+    let mut buffer = [0u8; 32];
+    let mut encoder = EncoderImpl::with_context(SliceWriter::new(&mut buffer), config, registry);
+    pets.encode(&mut encoder).unwrap();
+    let len = encoder.writer().bytes_written();
+    let registry = encoder.into_context();
+    assert_eq!(
+        &buffer[..len],
+        &[
+            2, // pets
+            251, 44, 1, 4, 3, b'T', b'o', b'm', // tag 300, length prefixed name
+            7, 1, 3, // tag 7, length prefixed age
+        ]
+    );
+
+    // The config of the decoder can be a reference to the config of the registry
+    let mut decoder =
+        DecoderImpl::with_context(SliceReader::new(&buffer[..len]), &config, registry);
+    let decoded = Vec::<Pet>::decode(&mut decoder).unwrap();
+    let names: Vec<String> = decoded.iter().map(|pet| pet.0.name()).collect();
+    assert_eq!(names, ["Tom", "dog of 3"]);
+    let registry = decoder.into_context();
+
+    // The methods of the registry can be used without a context
+    let mut decoder = DecoderImpl::new(SliceReader::new(&[8, 1, 3]), config);
+    assert_eq!(
+        registry.decode(&mut decoder).err(),
+        Some(DecodeError::UnregisteredTag { tag: 8 })
+    );
+    let mut encoder = EncoderImpl::new(SliceWriter::new(&mut buffer), config);
+    assert!(matches!(
+        registry.encode(&Fish, &mut encoder),
+        Err(EncodeError::UnregisteredType { .. })
+    ));
+
+    // Without a registry as the context
+    let result = bincode::encode_to_vec(Pet(Box::new(Dog { age: 1 })), config);
+    assert!(matches!(result, Err(EncodeError::Other(_))));
+    let result: Result<(Pet, usize), _> = bincode::decode_from_slice(&[7, 1, 3], config);
+    assert!(matches!(result, Err(DecodeError::Other(_))));
+}
+
+#[test]
+fn test_option_box_chain() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        value: u8,
+        next: Option<Box<Node>>,
+    }
+
+    impl Encode for Node {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.value.encode(encoder)?;
+            self.next.encode(encoder)
+        }
+    }
+
+    impl Decode for Node {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Node {
+                value: Decode::decode(decoder)?,
+                next: Decode::decode(decoder)?,
+            })
+        }
+    }
+
+    fn chain(len: u8) -> Node {
+        let mut node = Node {
+            value: 0,
+            next: None,
+        };
+        for value in 1..len {
+            node = Node {
+                value,
+                next: Some(Box::new(node)),
+            };
+        }
+        node
+    }
+
+    // `None` is a single byte
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(chain(1), config).unwrap();
+    assert_eq!(bytes, [0, 0]);
+    let bytes = bincode::encode_to_vec(chain(3), config).unwrap();
+    assert_eq!(bytes, [2, 1, 1, 1, 0, 0]);
+
+    // Every `Box` counts as one level
+    let config = config.with_max_depth::<254>();
+    let bytes = bincode::encode_to_vec(chain(255), config).unwrap();
+    let (decoded, _): (Node, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, chain(255));
+    let config = config.with_max_depth::<253>();
+    let result: Result<(Node, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::DepthLimitExceeded { max: 253 }
+    );
+
+    // A crafted chain that would otherwise overflow the stack
+    let mut bytes = [0, 1].repeat(1_000_000);
+    bytes.extend_from_slice(&[0, 0]);
+    let config = bincode::config::standard().with_max_depth::<500>();
+    let result: Result<(Node, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::DepthLimitExceeded { max: 500 }
+    );
+
+    // Nested collections count as well
+    let config = bincode::config::standard().with_max_depth::<2>();
+    let value = vec![vec![vec![1u8]]];
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let result: Result<(Vec<Vec<Vec<u8>>>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::DepthLimitExceeded { max: 2 }
+    );
+    let (decoded, _): (Vec<Vec<u8>>, usize) =
+        bincode::decode_from_slice(&bytes[1..], config).unwrap();
+    assert_eq!(decoded, value[0]);
+
+    // Every kind of collection counts, not only `Vec`
+    type Collections = VecDeque<BTreeMap<u8, BTreeSet<u8>>>;
+    let value: Collections = VecDeque::from([BTreeMap::from([(1u8, BTreeSet::from([2u8]))])]);
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let result: Result<(Collections, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::DepthLimitExceeded { max: 2 }
+    );
+    let (decoded, _): (Collections, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard().with_max_depth::<3>())
+            .unwrap();
+    assert_eq!(decoded, value);
+
+    // The depth is restored when decoding a nested value fails
+    let config = bincode::config::standard().with_max_depth::<1>();
+    let bytes = [1, 5, 1, 2];
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    assert!(Vec::<bool>::decode(&mut decoder).is_err());
+    assert_eq!(Vec::<u8>::decode(&mut decoder).unwrap(), [2]);
+}
+
+#[test]
+fn test_size_hint_collections() {
+    use bincode::{config::Config, Encode};
+
+    fn check<T: Encode, C: Config>(value: T, config: C) {
+        let bytes = bincode::encode_to_vec(&value, config).unwrap();
+        assert_eq!(value.size_hint::<C>(), Some(bytes.len()));
+    }
+
+    let standard = bincode::config::standard();
+    let legacy = bincode::config::legacy();
+    check(vec![1u8; 300], standard);
+    check(vec![1u32, 300, 70_000], standard);
+    check(vec![1u32, 300, 70_000], legacy);
+    check(vec![vec![1u16, 2], vec![], vec![1000]], standard);
+    check(String::from("hello world"), legacy);
+    check(vec![String::from("a"), String::from("bc")], standard);
+    check(Box::new(300u64), standard);
+    check(vec![1u32, 2].into_boxed_slice(), standard);
+    check(Cow::<str>::Borrowed("borrowed"), standard);
+    check(alloc::rc::Rc::new(5u16), legacy);
+    check(VecDeque::from(vec![1i32, -300]), standard);
+    check(BTreeSet::from([1u64, 1 << 40]), standard);
+    check(BinaryHeap::from(vec![3u8, 1, 2]), standard);
+    check(
+        BTreeMap::from([(1u8, String::from("one")), (2u8, String::from("two"))]),
+        legacy,
+    );
+
+    // `Foo` has no size hint, so neither does a collection of it
+    type Standard = bincode::config::Configuration;
+    assert_eq!(Foo { a: 1, b: 2 }.size_hint::<Standard>(), None);
+    assert_eq!(vec![Foo { a: 1, b: 2 }].size_hint::<Standard>(), None);
+    assert_eq!(Vec::<Foo>::new().size_hint::<Standard>(), Some(1));
+}
+
+#[test]
+fn test_cow_in_collections() {
+    the_same::<Vec<Cow<'static, str>>>(vec![Cow::Borrowed("borrowed"), Cow::Owned("owned".into())]);
+    the_same::<Option<Cow<'static, str>>>(Some(Cow::Borrowed("some")));
+    the_same::<Option<Cow<'static, str>>>(None);
+    the_same::<BTreeMap<String, Cow<'static, str>>>(
+        [
+            (String::from("a"), Cow::Borrowed("1")),
+            (String::from("b"), Cow::Owned(String::from("2"))),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    the_same::<Cow<'static, [u32]>>(Cow::Borrowed(&[1, 300, 70_000]));
+    the_same::<Cow<'static, [u8]>>(Cow::Owned(vec![1, 2, 3]));
+    the_same::<Vec<Cow<'static, [u16]>>>(vec![Cow::Borrowed(&[1, 2]), Cow::Owned(vec![3])]);
+
+    // A `Cow` is encoded the same as its owned type
+    let config = bincode::config::standard();
+    let cows: Vec<Cow<str>> = vec![Cow::Borrowed("a"), Cow::Owned(String::from("bc"))];
+    let bytes = bincode::encode_to_vec(&cows, config).unwrap();
+    let strings = vec![String::from("a"), String::from("bc")];
+    assert_eq!(bytes, bincode::encode_to_vec(&strings, config).unwrap());
+    let slice: &[u32] = &[1, 2];
+    assert_eq!(
+        bincode::encode_to_vec(Cow::Borrowed(slice), config).unwrap(),
+        bincode::encode_to_vec(vec![1u32, 2], config).unwrap()
+    );
+
+    // Decoding never borrows from the input
+    let (decoded, _): (Vec<Cow<str>>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, cows);
+    assert!(decoded.iter().all(|cow| matches!(cow, Cow::Owned(_))));
+}
+
+#[test]
+fn test_decode_seed() {
+    use bincode::{
+        de::{DecodeSeed, Decoder},
+        error::DecodeError,
+        Decode,
+    };
+    use core::marker::PhantomData;
+
+    /// Decodes a sequence without a length prefix, of which the length is known up front
+    struct Count<T>(usize, PhantomData<T>);
+
+    impl<T: Decode> DecodeSeed for Count<T> {
+        type Value = Vec<T>;
+
+        fn decode<D: Decoder>(self, decoder: &mut D) -> Result<Vec<T>, DecodeError> {
+            decoder.claim_container_read::<T>(self.0)?;
+            let mut items = Vec::with_capacity(self.0);
+            for _ in 0..self.0 {
+                decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                items.push(T::decode(decoder)?);
+            }
+            Ok(items)
+        }
+    }
+
+    let config = bincode::config::standard();
+    // A tuple is encoded without a length prefix
+    let bytes = bincode::encode_to_vec((1u32, 300u32, 7u32), config).unwrap();
+    assert_eq!(bytes, [1, 251, 44, 1, 7]);
+
+    let (items, len) =
+        bincode::decode_seed_from_slice(Count::<u32>(3, PhantomData), &bytes, config).unwrap();
+    assert_eq!(items, [1, 300, 7]);
+    assert_eq!(len, 5);
+
+    let (items, len) =
+        bincode::decode_seed_from_slice(Count::<u32>(2, PhantomData), &bytes, config).unwrap();
+    assert_eq!(items, [1, 300]);
+    assert_eq!(len, 4);
+
+    let result = bincode::decode_seed_from_slice(Count::<u32>(4, PhantomData), &bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    // Every `Decode` type can be decoded with a `PhantomData` seed
+    let (value, len) =
+        bincode::decode_seed_from_slice(PhantomData::<(u32, u32)>, &bytes, config).unwrap();
+    assert_eq!(value, (1, 300));
+    assert_eq!(len, 4);
+}
+
+#[test]
+fn test_sorted_heap() {
+    use bincode::SortedHeap;
+
+    let config = bincode::config::standard();
+
+    let mut first = BinaryHeap::new();
+    let mut second = BinaryHeap::new();
+    for i in 0..100u32 {
+        first.push(i * 7 % 100);
+        second.push(99 - i);
+    }
+    // The heap order depends on the push order
+    assert_ne!(
+        bincode::encode_to_vec(&first, config).unwrap(),
+        bincode::encode_to_vec(&second, config).unwrap()
+    );
+
+    let first_bytes = bincode::encode_to_vec(SortedHeap(first.clone()), config).unwrap();
+    let second_bytes = bincode::encode_to_vec(SortedHeap(second), config).unwrap();
+    assert_eq!(first_bytes, second_bytes);
+    assert_eq!(
+        first_bytes,
+        bincode::encode_to_vec((0..100u32).collect::<Vec<_>>(), config).unwrap()
+    );
+
+    let (decoded, len): (SortedHeap<u32>, usize) =
+        bincode::decode_from_slice(&first_bytes, config).unwrap();
+    assert_eq!(decoded.0.into_sorted_vec(), first.clone().into_sorted_vec());
+    assert_eq!(len, first_bytes.len());
+
+    // The wire format is the same as a regular `BinaryHeap`
+    let (decoded, _): (BinaryHeap<u32>, usize) =
+        bincode::decode_from_slice(&first_bytes, config).unwrap();
+    assert_eq!(decoded.peek(), Some(&99));
+    assert_eq!(decoded.into_sorted_vec(), first.into_sorted_vec());
+}
+
+#[test]
+fn test_encode_streaming() {
+    use bincode::error::EncodeError;
+
+    let config = bincode::config::standard();
+    let value = (
+        String::from("streamed"),
+        vec![1u64, 300, u64::MAX],
+        Some([7u8; 4]),
+    );
+
+    let mut chunks: Vec<Vec<u8>> = Vec::new();
+    bincode::encode_streaming(&value, config, |chunk: &[u8]| {
+        chunks.push(chunk.to_vec());
+        Ok(())
+    })
+    .unwrap();
+    assert!(chunks.len() > 1);
+
+    let bytes = chunks.concat();
+    assert_eq!(bytes, bincode::encode_to_vec(&value, config).unwrap());
+    type Value = (String, Vec<u64>, Option<[u8; 4]>);
+    let (decoded, len): (Value, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(len, bytes.len());
+
+    // An error from the callback stops the encoding
+    let mut calls = 0;
+    let result = bincode::encode_streaming(&value, config, |_: &[u8]| {
+        calls += 1;
+        if calls == 2 {
+            Err(EncodeError::Other("stop"))
+        } else {
+            Ok(())
+        }
+    });
+    assert!(matches!(result, Err(EncodeError::Other("stop"))));
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_indexed_map() {
+    use bincode::{
+        de::Decoder,
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode, IndexedMap,
+    };
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static ENCODED: AtomicUsize = AtomicUsize::new(0);
+    static DECODED: AtomicUsize = AtomicUsize::new(0);
+
+    /// A value that counts how often it is encoded and decoded
+    #[derive(PartialEq, Debug, Clone)]
+    struct Record(String);
+
+    impl Encode for Record {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            ENCODED.fetch_add(1, Ordering::SeqCst);
+            self.0.encode(encoder)
+        }
+    }
+
+    impl Decode for Record {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            DECODED.fetch_add(1, Ordering::SeqCst);
+            String::decode(decoder).map(Record)
+        }
+    }
+
+    let mut map = BTreeMap::new();
+    for i in 0..50u32 {
+        map.insert(i * 3, Record(format!("record {}", i)));
+    }
+    let map = IndexedMap(map);
+
+    // Every value is encoded once
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(&map, config).unwrap();
+    assert_eq!(ENCODED.load(Ordering::SeqCst), 50);
+
+    // A single key is looked up without decoding any of its neighbors
+    DECODED.store(0, Ordering::SeqCst);
+    let found = IndexedMap::<u32, Record>::lookup(&bytes, &42, config).unwrap();
+    assert_eq!(found, Some(Record(String::from("record 14"))));
+    assert_eq!(DECODED.load(Ordering::SeqCst), 1);
+
+    let missing = IndexedMap::<u32, Record>::lookup(&bytes, &43, config).unwrap();
+    assert_eq!(missing, None);
+    assert_eq!(DECODED.load(Ordering::SeqCst), 1);
+
+    // The map itself is encoded as a regular BTreeMap after its byte length
+    let (map_len, prefix_len): (u64, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    let plain = bincode::encode_to_vec(&map.0, config).unwrap();
+    assert_eq!(map_len as usize, plain.len());
+    assert_eq!(bytes[prefix_len..prefix_len + plain.len()], plain[..]);
+
+    // Decoding the whole map, followed by another value
+    let mut bytes = bytes;
+    bytes.push(9);
+    let ((decoded, next), len): ((IndexedMap<u32, Record>, u8), usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, map);
+    assert_eq!(next, 9);
+    assert_eq!(len, bytes.len());
+
+    let legacy = bincode::config::legacy();
+    let bytes = bincode::encode_to_vec(&map, legacy).unwrap();
+    let found = IndexedMap::<u32, Record>::lookup(&bytes, &0, legacy).unwrap();
+    assert_eq!(found, Some(Record(String::from("record 0"))));
+
+    // Only the encoded `IndexedMap` is padded with an aligned config, not the entries that are looked up
+    let aligned = config.with_alignment::<8>();
+    let bytes = bincode::encode_to_vec(&map, aligned).unwrap();
+    assert_eq!(bytes.len() % 8, 0);
+    let found = IndexedMap::<u32, Record>::lookup(&bytes, &42, aligned).unwrap();
+    assert_eq!(found, Some(Record(String::from("record 14"))));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_no_allocation() {
+    use bincode::{
+        de::{read::SliceReader, DecoderImpl},
+        error::DecodeError,
+        Decode,
+    };
+
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    struct Fixed {
+        id: u32,
+        payload: [u8; 4],
+    }
+
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    struct WithVec {
+        id: u32,
+        payload: Vec<u8>,
+    }
+
+    let config = bincode::config::standard().with_no_allocation();
+
+    // Types that do not allocate decode as usual
+    let fixed = Fixed {
+        id: 5,
+        payload: [1, 2, 3, 4],
+    };
+    let bytes = bincode::encode_to_vec(&fixed, config).unwrap();
+    let (decoded, _): (Fixed, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, fixed);
+    let (decoded, _): (&str, usize) = bincode::decode_from_slice(&[2, b'h', b'i'], config).unwrap();
+    assert_eq!(decoded, "hi");
+
+    // The `Vec` field is rejected, even if it is empty
+    for payload in [vec![1, 2, 3], Vec::new()] {
+        let bytes = bincode::encode_to_vec(WithVec { id: 5, payload }, config).unwrap();
+        let result: Result<(WithVec, usize), _> = bincode::decode_from_slice(&bytes, config);
+        assert!(matches!(
+            result.unwrap_err(),
+            DecodeError::AllocationDisallowed { type_name } if type_name.contains("Vec<u8>")
+        ));
+    }
+
+    let bytes = bincode::encode_to_vec("hi", config).unwrap();
+    let result: Result<(String, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::AllocationDisallowed { .. }
+    ));
+    let result: Result<(Box<u32>, usize), _> = bincode::decode_from_slice(&[1], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::AllocationDisallowed { .. }
+    ));
+    let result: Result<(BTreeMap<u8, u8>, usize), _> = bincode::decode_from_slice(&[0], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::AllocationDisallowed { .. }
+    ));
+
+    // Decoding into an existing `Vec` is fine, as long as it has room for the items
+    let mut vec = Vec::<u32>::with_capacity(3);
+    let mut decoder = DecoderImpl::new(SliceReader::new(&[3, 1, 2, 3, 100]), config);
+    Vec::decode_in_place(&mut decoder, &mut vec).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    assert!(matches!(
+        Vec::decode_in_place(&mut decoder, &mut vec).unwrap_err(),
+        DecodeError::AllocationDisallowed { .. }
+    ));
+
+    // Allocation is allowed by default
+    let with_vec = WithVec {
+        id: 5,
+        payload: vec![1, 2, 3],
+    };
+    let bytes = bincode::encode_to_vec(&with_vec, config).unwrap();
+    let (decoded, _): (WithVec, usize) =
+        bincode::decode_from_slice(&bytes, config.with_allocation()).unwrap();
+    assert_eq!(decoded, with_vec);
+}
@@ -1,6 +1,7 @@
 mod utils;
 
 use core::cell::{Cell, RefCell};
+use core::cmp::Reverse;
 use core::ops::Bound;
 use core::time::Duration;
 use std::num::*;
@@ -77,6 +78,7 @@ fn test_numbers() {
 
     the_same(Cell::<u32>::new(15));
     the_same(RefCell::<u32>::new(15));
+    the_same(Reverse(15u32));
 
     the_same(Duration::new(5, 730023852));
     the_same(5u8..10u8);
@@ -85,6 +87,19 @@ fn test_numbers() {
     the_same(Bound::<usize>::Included(105));
     the_same(Bound::<usize>::Excluded(5));
 
+    #[cfg(not(feature = "serde"))] // these wrappers do not implement serde's traits
+    {
+        the_same(bincode::FixedPoint::<1000>(0.0));
+        the_same(bincode::FixedPoint::<1000>(-1234.567));
+        the_same(bincode::FixedPoint::<1000>(-9_000_000_000_000.0));
+        the_same(bincode::DurationMillis(Duration::from_millis(999)));
+        the_same(bincode::DurationMillis(Duration::from_millis(
+            u32::MAX.into(),
+        )));
+        the_same(bincode::Handle(0x1000));
+        the_same(bincode::Handle(usize::MAX));
+    }
+
     // NonZero* types
     the_same(NonZeroU8::new(0));
     the_same(NonZeroU8::new(123));
@@ -137,7 +152,7 @@ fn test_slice() {
     assert_eq!(&buffer[..8], &[7, 1, 2, 3, 4, 5, 6, 7]);
 
     let (output, len): (&[u8], usize) =
-        bincode::decode_from_slice(&mut buffer[..8], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..8], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 8);
 }
@@ -176,7 +191,7 @@ fn test_str() {
     );
 
     let (output, len): (&str, usize) =
-        bincode::decode_from_slice(&mut buffer[..12], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..12], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 12);
 }
@@ -218,7 +233,7 @@ fn test_array() {
     );
 
     let (output, len): ([u8; 10], usize) =
-        bincode::decode_from_slice(&mut buffer[..11], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..11], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 11);
 
@@ -232,25 +247,108 @@ fn test_array() {
     assert_eq!(len, 9);
     assert_eq!(&buffer[..9], &[1, 0, 0, 0, 0, 0, 0, 0, 1]);
 
-    let (output, len): (&[u8], usize) =
-        bincode::decode_from_slice(&mut buffer[..9], config).unwrap();
+    let (output, len): (&[u8], usize) = bincode::decode_from_slice(&buffer[..9], config).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 9);
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_nested_array() {
+    let input: [[u8; 2]; 3] = [[1, 2], [3, 4], [5, 6]];
+
+    // Without array lengths the layout is flat
+    let config = bincode::config::standard().skip_fixed_array_length();
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 4, 5, 6]);
+    let (output, len): ([[u8; 2]; 3], usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(input, output);
+    assert_eq!(len, 6);
+
+    // By default every array, including the inner ones, is prefixed with its length
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+    assert_eq!(bytes, [3, 2, 1, 2, 2, 3, 4, 2, 5, 6]);
+    let (output, len): ([[u8; 2]; 3], usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(input, output);
+    assert_eq!(len, 10);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_integer_array() {
+    // A wrapper that is not an integer, so arrays of it are encoded and decoded one item at a time
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    struct Item(u32);
+
+    impl bincode::Encode for Item {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            self.0.encode(encoder)
+        }
+    }
+
+    impl bincode::Decode for Item {
+        fn decode<D: bincode::de::Decoder>(
+            decoder: &mut D,
+        ) -> Result<Self, bincode::error::DecodeError> {
+            u32::decode(decoder).map(Item)
+        }
+    }
+
+    fn check<C: bincode::config::Config>(config: C) {
+        let input: [u32; 64] =
+            core::array::from_fn(|i| (i as u32).wrapping_mul(0x0101_0101) ^ 0xA5);
+        let bytes = bincode::encode_to_vec(input, config).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::encode_to_vec(input.map(Item), config).unwrap()
+        );
+        let (output, len): ([u32; 64], usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+        let (expected, _): ([Item; 64], usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(output, input);
+        assert_eq!(output.map(Item), expected);
+        assert_eq!(len, bytes.len());
+
+        utils::assert_roundtrip([-1i8, 0, 1, i8::MIN, i8::MAX], config);
+        utils::assert_roundtrip([-1i16, 0, 1, i16::MIN, i16::MAX], config);
+        utils::assert_roundtrip([u64::MAX, 0, 1 << 40], config);
+        utils::assert_roundtrip([i128::MIN, -1, 0, i128::MAX], config);
+        utils::assert_roundtrip([[1u16, 2], [300, 40_000]], config);
+        utils::assert_roundtrip([0u32; 0], config);
+
+        let result: Result<([u32; 64], usize), _> =
+            bincode::decode_from_slice(&bytes[..bytes.len() - 1], config);
+        assert!(matches!(
+            result.unwrap_err(),
+            bincode::error::DecodeError::UnexpectedEnd { .. }
+        ));
+    }
+
+    let config = bincode::config::standard();
+    check(config);
+    check(config.with_fixed_int_encoding());
+    check(config.with_fixed_int_encoding().with_big_endian());
+    check(config.with_fixed_int_encoding().skip_fixed_array_length());
+    check(config.with_big_endian().skip_fixed_array_length());
+}
+
 #[test]
 fn test_duration_out_of_range() {
     let mut input = [0u8; 14];
 
     bincode::encode_into_slice(
-        &(u64::MAX, u32::MAX),
+        (u64::MAX, u32::MAX),
         &mut input,
         bincode::config::standard(),
     )
     .unwrap();
 
     let result: Result<(std::time::Duration, usize), _> =
-        bincode::decode_from_slice(&mut input, bincode::config::standard());
+        bincode::decode_from_slice(&input, bincode::config::standard());
 
     assert_eq!(
         result.unwrap_err(),
@@ -266,16 +364,1480 @@ fn test_duration_wrapping() {
     let mut input = [0u8; 14];
 
     bincode::encode_into_slice(
-        &(u64::MAX - 4, u32::MAX),
+        (u64::MAX - 4, u32::MAX),
         &mut input,
         bincode::config::standard(),
     )
     .unwrap();
 
     let (result, _): (std::time::Duration, _) =
-        bincode::decode_from_slice(&mut input, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&input, bincode::config::standard()).unwrap();
 
     assert_eq!(result.as_secs(), u64::MAX);
 
     assert_eq!(result.subsec_nanos(), 294967295);
 }
+
+#[test]
+fn test_skip_bytes() {
+    use bincode::{de::Decoder, error::DecodeError, Decode};
+
+    #[derive(Debug, PartialEq)]
+    struct Skipped {
+        a: u32,
+        b: u32,
+    }
+
+    impl Decode for Skipped {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let a = u32::decode(decoder)?;
+            let blob_len = usize::decode(decoder)?;
+            decoder.skip_bytes(blob_len)?;
+            let b = u32::decode(decoder)?;
+            Ok(Self { a, b })
+        }
+    }
+
+    let blob = [0xAAu8; 200];
+    let mut buffer = [0u8; 256];
+    let config = bincode::config::standard();
+    let len = bincode::encode_into_slice((5u32, &blob[..], 1000u32), &mut buffer, config).unwrap();
+
+    let (decoded, decoded_len): (Skipped, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, Skipped { a: 5, b: 1000 });
+    assert_eq!(decoded_len, len);
+
+    // The skipped bytes still count towards the limit
+    let result: Result<(Skipped, usize), _> =
+        bincode::decode_from_slice(&buffer[..len], config.with_limit::<100>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+}
+
+#[test]
+fn test_length_prefixed() {
+    use bincode::{
+        config::Config,
+        de::Decoder,
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Inner {
+        a: u32,
+        b: [u8; 4],
+    }
+
+    impl Encode for Inner {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.a.encode(encoder)?;
+            self.b.encode(encoder)
+        }
+
+        // The length prefix is written from the size hint, so the value is not buffered
+        fn size_hint<C: Config>(&self) -> Option<usize> {
+            Some(self.a.size_hint::<C>()? + self.b.size_hint::<C>()?)
+        }
+    }
+
+    impl Decode for Inner {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Self {
+                a: Decode::decode(decoder)?,
+                b: Decode::decode(decoder)?,
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Outer {
+        inner: Inner,
+        trailer: u16,
+    }
+
+    impl Encode for Outer {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encoder.encode_length_prefixed(&self.inner)?;
+            self.trailer.encode(encoder)
+        }
+    }
+
+    impl Decode for Outer {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Self {
+                inner: decoder.decode_length_prefixed()?,
+                trailer: Decode::decode(decoder)?,
+            })
+        }
+    }
+
+    let config = bincode::config::standard();
+    let value = Outer {
+        inner: Inner {
+            a: 1000,
+            b: [1, 2, 3, 4],
+        },
+        trailer: 7,
+    };
+    let mut buffer = [0u8; 64];
+    let len = bincode::encode_into_slice(&value, &mut buffer, config).unwrap();
+    // length, 3 bytes for `a`, 5 bytes for `b`, 1 byte for the trailer
+    assert_eq!(&buffer[..len], &[8, 251, 232, 3, 4, 1, 2, 3, 4, 7]);
+
+    let (decoded, decoded_len): (Outer, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(decoded_len, len);
+
+    // The sub-message can be skipped without decoding it
+    struct SkipInner(u16);
+
+    impl Decode for SkipInner {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = usize::decode(decoder)?;
+            decoder.skip_bytes(len)?;
+            Ok(Self(Decode::decode(decoder)?))
+        }
+    }
+
+    let (decoded, _): (SkipInner, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded.0, 7);
+
+    // The inner value can't read past its length prefix
+    let mut overrun = buffer;
+    overrun[0] = 3;
+    let result: Result<(Outer, usize), _> = bincode::decode_from_slice(&overrun[..len], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
+
+#[test]
+fn test_decode_context() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        error::DecodeError,
+        Decode,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Color(&'static str);
+
+    impl Decode for Color {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let index = u8::decode(decoder)?;
+            let table = decoder
+                .context()
+                .and_then(|ctx| ctx.downcast_ref::<[&'static str; 3]>())
+                .ok_or(DecodeError::Other("Missing color table"))?;
+            table
+                .get(index as usize)
+                .map(|name| Color(name))
+                .ok_or(DecodeError::Other("Unknown color"))
+        }
+    }
+
+    let table = ["red", "green", "blue"];
+    let input = [2, 0, 1];
+    let mut decoder =
+        DecoderImpl::with_context(SliceReader::new(&input), bincode::config::standard(), table);
+    let decoded = <(Color, Color, Color)>::decode(&mut decoder);
+    assert_eq!(
+        decoded.unwrap(),
+        (Color("blue"), Color("red"), Color("green"))
+    );
+    assert_eq!(decoder.into_context(), table);
+
+    // Without a context the lookup fails
+    let result: Result<(Color, usize), _> =
+        bincode::decode_from_slice(&input, bincode::config::standard());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::Other("Missing color table")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_encode_context() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        enc::{write::SliceWriter, Encoder, EncoderImpl},
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Name(String);
+
+    impl Encode for Name {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            let interner = encoder
+                .context()
+                .and_then(|ctx| ctx.downcast_mut::<Vec<String>>())
+                .ok_or(EncodeError::Other("Missing interner"))?;
+            let index = match interner.iter().position(|name| *name == self.0) {
+                Some(index) => index,
+                None => {
+                    interner.push(self.0.clone());
+                    interner.len() - 1
+                }
+            };
+            index.encode(encoder)
+        }
+    }
+
+    impl Decode for Name {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let index = usize::decode(decoder)?;
+            let table = decoder
+                .context()
+                .and_then(|ctx| ctx.downcast_ref::<Vec<String>>())
+                .ok_or(DecodeError::Other("Missing string table"))?;
+            table
+                .get(index)
+                .map(|name| Name(name.clone()))
+                .ok_or(DecodeError::Other("Unknown name"))
+        }
+    }
+
+    let names = vec![
+        Name("alice".to_string()),
+        Name("bob".to_string()),
+        Name("alice".to_string()),
+        Name("carol".to_string()),
+        Name("bob".to_string()),
+    ];
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+    let mut encoder =
+        EncoderImpl::with_context(SliceWriter::new(&mut buffer), config, Vec::<String>::new());
+    names.encode(&mut encoder).unwrap();
+    let interner = encoder.into_context();
+    assert_eq!(interner, ["alice", "bob", "carol"]);
+    assert_eq!(buffer[..6], [5, 0, 1, 0, 2, 1]);
+
+    let mut decoder = DecoderImpl::with_context(SliceReader::new(&buffer[..6]), config, interner);
+    let decoded = Vec::<Name>::decode(&mut decoder).unwrap();
+    assert_eq!(decoded, names);
+
+    // Without a context the interner is missing
+    let result = bincode::encode_to_vec(&names, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        EncodeError::Other("Missing interner")
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_limit_reader() {
+    use bincode::{
+        de::{
+            read::{LimitReader, SliceReader},
+            Decode, DecoderImpl,
+        },
+        error::DecodeError,
+    };
+
+    let config = bincode::config::standard();
+
+    // Two frames of 4 bytes each. The string in the first frame claims to be 5 bytes long, but only 3 bytes are left in the frame, so it would overrun into the second frame.
+    let input = [4, 5, b'a', b'b', b'c', 4, 3, b'd', b'e', b'f'];
+    let mut reader = SliceReader::new(&input);
+
+    let len = u8::decode(&mut DecoderImpl::new(&mut reader, config)).unwrap();
+    let mut frame = DecoderImpl::new(LimitReader::new(&mut reader, len as usize), config);
+    assert_eq!(
+        String::decode(&mut frame).unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    // Borrowed data is limited as well
+    let mut frame = DecoderImpl::new(LimitReader::new(SliceReader::new(&input), 4), config);
+    assert_eq!(
+        <&str as bincode::BorrowDecode>::borrow_decode(&mut frame).unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    // A well formed frame decodes fine and leaves the next frame untouched
+    let input = [3, 2, b'h', b'i', 1, b'!'];
+    let mut reader = LimitReader::new(SliceReader::new(&input[1..]), 3);
+    let value = String::decode(&mut DecoderImpl::new(&mut reader, config)).unwrap();
+    assert_eq!(value, "hi");
+    assert_eq!(reader.remaining(), 0);
+    let value = String::decode(&mut DecoderImpl::new(reader.into_inner(), config)).unwrap();
+    assert_eq!(value, "!");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_progress_reader() {
+    use bincode::de::read::{ProgressReader, SliceReader};
+
+    let config = bincode::config::standard();
+    let value: Vec<(u32, String)> = (0..20_000).map(|i| (i * 7, i.to_string())).collect();
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+
+    let mut progress = Vec::new();
+    let reader = ProgressReader::new(SliceReader::new(&bytes), 4096, |consumed| {
+        progress.push(consumed)
+    });
+    let decoded: Vec<(u32, String)> = bincode::decode_from_reader(reader, config).unwrap();
+    assert_eq!(decoded, value);
+
+    // Called about once every 4096 bytes, with increasing byte counts
+    assert_eq!(progress.len(), bytes.len() / 4096);
+    assert!(progress.windows(2).all(|pair| pair[0] < pair[1]));
+    for (index, consumed) in progress.iter().enumerate() {
+        assert!(*consumed >= (index + 1) * 4096);
+        assert!(*consumed < (index + 2) * 4096);
+    }
+    assert!(*progress.last().unwrap() <= bytes.len());
+
+    // Borrowed data is counted as well
+    let strings: Vec<&str> = value.iter().map(|(_, s)| s.as_str()).collect();
+    let bytes = bincode::encode_to_vec(&strings, config).unwrap();
+    let mut reader = ProgressReader::new(SliceReader::new(&bytes), 1000, |_| {});
+    let mut decoder = bincode::de::DecoderImpl::new(&mut reader, config);
+    let decoded: Vec<&str> = bincode::BorrowDecode::borrow_decode(&mut decoder).unwrap();
+    assert_eq!(decoded, strings);
+    assert_eq!(reader.bytes_consumed(), bytes.len());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_non_zero_option() {
+    use bincode::NonZeroOption;
+
+    let config = bincode::config::standard().with_fixed_int_encoding();
+
+    let some = NonZeroOption(NonZeroU32::new(5));
+    let bytes = bincode::encode_to_vec(some, config).unwrap();
+    assert_eq!(bytes, [5, 0, 0, 0]);
+    let (decoded, len): (NonZeroOption<NonZeroU32>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, some);
+    assert_eq!(len, 4);
+
+    let none = NonZeroOption::<NonZeroU32>(None);
+    let bytes = bincode::encode_to_vec(none, config).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0]);
+    let (decoded, len): (NonZeroOption<NonZeroU32>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, none);
+    assert_eq!(len, 4);
+
+    // A regular option needs an extra byte for the variant
+    let bytes = bincode::encode_to_vec(NonZeroU32::new(5), config).unwrap();
+    assert_eq!(bytes, [1, 5, 0, 0, 0]);
+
+    // Varint encoding writes `None` as a single byte
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(NonZeroOption::<NonZeroI64>(None), config).unwrap();
+    assert_eq!(bytes, [0]);
+
+    utils::assert_roundtrip(NonZeroOption(NonZeroU8::new(u8::MAX)), config);
+    utils::assert_roundtrip(NonZeroOption(NonZeroI16::new(-1)), config);
+    utils::assert_roundtrip(NonZeroOption(NonZeroU128::new(u128::MAX)), config);
+    utils::assert_roundtrip(NonZeroOption::<NonZeroUsize>(None), config);
+    utils::assert_roundtrip(NonZeroOption(NonZeroIsize::new(isize::MIN)), config);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bool_decoding() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard();
+    for (byte, expected) in [(0u8, false), (1, true)] {
+        let (value, len): (bool, usize) = bincode::decode_from_slice(&[byte], config).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(len, 1);
+    }
+
+    // Bools are strict by default
+    let result: Result<(bool, usize), _> = bincode::decode_from_slice(&[2], config);
+    assert_eq!(result.unwrap_err(), DecodeError::InvalidBooleanValue(2));
+    let result: Result<(bool, usize), _> =
+        bincode::decode_from_slice(&[2], config.with_fixed_int_encoding().with_strict_bool());
+    assert_eq!(result.unwrap_err(), DecodeError::InvalidBooleanValue(2));
+
+    // Any non-zero value is true with lenient bools
+    let config = config.with_lenient_bool();
+    for (byte, expected) in [(0u8, false), (1, true), (2, true), (255, true)] {
+        let (value, len): (bool, usize) = bincode::decode_from_slice(&[byte], config).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(len, 1);
+    }
+    assert_eq!(bincode::encode_to_vec(true, config).unwrap(), [1]);
+}
+
+#[test]
+fn test_borrow_slice() {
+    use bincode::{
+        de::{BorrowDecode, BorrowDecoder},
+        error::DecodeError,
+    };
+
+    /// A slice with a single byte as length prefix
+    #[derive(Debug, PartialEq)]
+    struct ShortBytes<'a>(&'a [u8]);
+
+    impl<'de: 'a, 'a> BorrowDecode<'de> for ShortBytes<'a> {
+        fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = u8::borrow_decode(decoder)?;
+            decoder.borrow_slice(len as usize).map(ShortBytes)
+        }
+    }
+
+    let config = bincode::config::standard();
+    let input = [3, 1, 2, 3, 4];
+    let (value, len): (ShortBytes, usize) = bincode::decode_from_slice(&input, config).unwrap();
+    assert_eq!(value, ShortBytes(&[1, 2, 3]));
+    assert_eq!(len, 4);
+    // The slice borrows from the input
+    assert_eq!(value.0.as_ptr(), input[1..].as_ptr());
+
+    let (value, len): (ShortBytes, usize) = bincode::decode_from_slice(&[0], config).unwrap();
+    assert_eq!(value, ShortBytes(&[]));
+    assert_eq!(len, 1);
+
+    let result: Result<(ShortBytes, usize), _> = bincode::decode_from_slice(&input[..3], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    let result: Result<(ShortBytes, usize), _> =
+        bincode::decode_from_slice(&input, config.with_limit::<3>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_fieldless_std_enums() {
+    use bincode::error::{AllowedEnumVariants, DecodeError};
+    use core::num::FpCategory;
+    use core::sync::atomic;
+
+    let config = bincode::config::standard();
+
+    let categories = [
+        FpCategory::Nan,
+        FpCategory::Infinite,
+        FpCategory::Zero,
+        FpCategory::Subnormal,
+        FpCategory::Normal,
+    ];
+    for (byte, category) in categories.into_iter().enumerate() {
+        assert_eq!(
+            bincode::encode_to_vec(category, config).unwrap(),
+            [byte as u8]
+        );
+        utils::assert_roundtrip(category, config);
+        utils::assert_roundtrip(category, config.with_fixed_int_encoding());
+    }
+    assert_eq!(
+        bincode::encode_to_vec(1.5f64.classify(), config).unwrap(),
+        [4]
+    );
+    let result: Result<(FpCategory, usize), _> = bincode::decode_from_slice(&[5], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "core::num::FpCategory",
+            allowed: AllowedEnumVariants::Range { min: 0, max: 4 },
+            found: 5,
+        }
+    );
+
+    let orderings = [
+        atomic::Ordering::Relaxed,
+        atomic::Ordering::Release,
+        atomic::Ordering::Acquire,
+        atomic::Ordering::AcqRel,
+        atomic::Ordering::SeqCst,
+    ];
+    for (byte, ordering) in orderings.into_iter().enumerate() {
+        assert_eq!(
+            bincode::encode_to_vec(ordering, config).unwrap(),
+            [byte as u8]
+        );
+        utils::assert_roundtrip(ordering, config);
+        utils::assert_roundtrip(ordering, config.with_fixed_int_encoding());
+    }
+    let result: Result<(atomic::Ordering, usize), _> = bincode::decode_from_slice(&[255], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "core::sync::atomic::Ordering",
+            allowed: AllowedEnumVariants::Range { min: 0, max: 4 },
+            found: 255,
+        }
+    );
+}
+
+#[test]
+fn test_encode_buffer_full() {
+    use bincode::error::EncodeError;
+
+    let config = bincode::config::standard();
+    let value: (u8, &str) = (1, "Hello world");
+
+    let mut buffer = [0u8; 4];
+    let result = bincode::encode_into_slice(value, &mut buffer, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        EncodeError::BufferFull {
+            required: 13,
+            capacity: 4
+        }
+    ));
+
+    // Growing the buffer to the required size succeeds
+    let mut buffer = [0u8; 13];
+    let len = bincode::encode_into_slice(value, &mut buffer, config).unwrap();
+    assert_eq!(len, 13);
+
+    // `write_repeated` reports the same error
+    struct Padding(usize);
+
+    impl bincode::Encode for Padding {
+        fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            use bincode::enc::write::Writer;
+            encoder.writer().write_repeated(0, self.0)
+        }
+    }
+
+    let mut buffer = [0u8; 8];
+    let result = bincode::encode_into_slice((1u8, Padding(16)), &mut buffer, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        EncodeError::BufferFull {
+            required: 17,
+            capacity: 8
+        }
+    ));
+}
+
+#[test]
+fn test_range_bounds() {
+    use bincode::de::{decode_range_bounds, read::SliceReader, DecoderImpl};
+    use bincode::enc::{encode_range_bounds, Encoder};
+    use bincode::error::EncodeError;
+    use core::ops::{RangeBounds, RangeInclusive};
+
+    struct Bounds<R>(R);
+
+    impl<R: RangeBounds<u32>> bincode::Encode for Bounds<R> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            encode_range_bounds(&self.0, encoder)
+        }
+    }
+
+    fn roundtrip<R: RangeBounds<u32>>(range: R) -> (Bound<u32>, Bound<u32>) {
+        let config = bincode::config::standard();
+        let mut buffer = [0u8; 32];
+        let len = bincode::encode_into_slice(Bounds(range), &mut buffer, config).unwrap();
+
+        let mut decoder = DecoderImpl::new(SliceReader::new(&buffer[..len]), config);
+        decode_range_bounds(&mut decoder).unwrap()
+    }
+
+    let range: RangeInclusive<u32> = 3..=300;
+    let bounds = roundtrip(range.clone());
+    assert_eq!(bounds, (Bound::Included(3), Bound::Included(300)));
+    for i in [0, 2, 3, 150, 300, 301] {
+        assert_eq!(bounds.contains(&i), range.contains(&i));
+    }
+
+    assert_eq!(roundtrip(3..5), (Bound::Included(3), Bound::Excluded(5)));
+    assert_eq!(roundtrip(3..), (Bound::Included(3), Bound::Unbounded));
+    assert_eq!(roundtrip(..=5), (Bound::Unbounded, Bound::Included(5)));
+    assert_eq!(roundtrip(..), (Bound::Unbounded, Bound::Unbounded));
+    assert_eq!(
+        roundtrip((Bound::Excluded(1), Bound::Excluded(5))),
+        (Bound::Excluded(1), Bound::Excluded(5))
+    );
+}
+
+#[test]
+fn test_read_array() {
+    use bincode::{de::Decoder, error::DecodeError, Decode};
+
+    #[derive(Debug, PartialEq)]
+    struct Message {
+        header: [u8; 8],
+        body: u32,
+    }
+
+    impl Decode for Message {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let header = decoder.read_array()?;
+            let body = u32::decode(decoder)?;
+            Ok(Self { header, body })
+        }
+    }
+
+    let input = *b"BINCODE\0";
+    let mut bytes = input.to_vec();
+    bytes.extend_from_slice(&[251, 0x39, 0x05, 0xFF, 0xFF]);
+
+    let config = bincode::config::standard();
+    let (decoded, len): (Message, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(
+        decoded,
+        Message {
+            header: input,
+            body: 1337,
+        }
+    );
+    assert_eq!(len, 11);
+
+    // The header counts towards the limit
+    let result: Result<(Message, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_limit::<7>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+
+    // Not enough bytes for the header
+    let result: Result<(Message, usize), _> = bincode::decode_from_slice(&input[..5], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 3 }
+    );
+}
+
+#[test]
+fn test_read_fixed_width_ints() {
+    use bincode::{
+        de::{read::SliceReader, Decoder, DecoderImpl},
+        error::DecodeError,
+        Decode,
+    };
+
+    // A raw little-endian length, followed by a varint
+    let bytes = [0x78, 0x56, 0x34, 0x12, 251, 0x39, 0x05];
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), bincode::config::standard());
+    assert_eq!(decoder.read_u32_le().unwrap(), 0x1234_5678);
+    assert_eq!(u32::decode(&mut decoder).unwrap(), 1337);
+
+    // The endianness of the config is ignored as well
+    let bytes = [
+        0x12, 0x34, // u16 be
+        0x34, 0x12, // u16 le
+        1, 2, 3, 4, 5, 6, 7, 8, // u64 be
+        8, 7, 6, 5, 4, 3, 2, 1, // u64 le
+        0, 0, 0, 1, // u32 be
+    ];
+    let config = bincode::config::legacy().with_big_endian();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    assert_eq!(decoder.read_u16_be().unwrap(), 0x1234);
+    assert_eq!(decoder.read_u16_le().unwrap(), 0x1234);
+    assert_eq!(decoder.read_u64_be().unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(decoder.read_u64_le().unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(decoder.read_u32_be().unwrap(), 1);
+    assert_eq!(
+        decoder.read_u32_le().unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 4 }
+    );
+
+    // The bytes count towards the limit
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config.with_limit::<3>());
+    assert_eq!(decoder.read_u16_be().unwrap(), 0x1234);
+    assert_eq!(
+        decoder.read_u16_le().unwrap_err(),
+        DecodeError::LimitExceeded
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_size_hint() {
+    use bincode::{config::Config, Encode};
+
+    fn check<T: Encode, C: Config>(value: T, config: C, expected: usize) {
+        let mut buffer = [0u8; 64];
+        let len = bincode::encode_into_slice(&value, &mut buffer, config).unwrap();
+        assert_eq!(len, expected);
+        assert_eq!(value.size_hint::<C>(), Some(len));
+    }
+
+    let standard = bincode::config::standard();
+    let legacy = bincode::config::legacy();
+    check(7u8, standard, 1);
+    check(250u32, standard, 1);
+    check(251u32, standard, 3);
+    check(u64::MAX, standard, 9);
+    check(u128::MAX, standard, 17);
+    check(-126i16, standard, 3);
+    check(i128::MIN, standard, 17);
+    check(250u32, legacy, 4);
+    check(-1i64, legacy, 8);
+    check(5usize, legacy, core::mem::size_of::<usize>());
+    check(NonZeroU32::new(300).unwrap(), standard, 3);
+    check(true, standard, 1);
+    check('\u{1F600}', standard, 4);
+    check(1.5f32, standard, 4);
+    check(1.5f64, legacy, 8);
+    check((), standard, 0);
+    check("hello", standard, 6);
+    check("héllo", standard.with_string_len_in_chars(), 7);
+    check(&[1u8, 2, 3][..], legacy, 11);
+    check(&[1u32, 300][..], standard, 1 + 1 + 3);
+    check([300u16; 4], standard, 1 + 4 * 3);
+    check([300u16; 4], standard.skip_fixed_array_length(), 4 * 3);
+    check(Some(300u32), standard, 4);
+    check(None::<u32>, standard, 1);
+    check(Ok::<u8, u64>(3), standard, 2);
+    check(Err::<u8, u64>(3), legacy, 12);
+    check((1u8, 300u16, -1i32), standard, 5);
+    check(Duration::new(3, 500), standard, 1 + 3);
+    check(Cell::new(5u16), legacy, 2);
+    check(RefCell::new(5u16), legacy, 2);
+    check(2u32..300, standard, 4);
+
+    // Types without a size hint, and any type that contains them
+    struct Unknown;
+    impl Encode for Unknown {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            _: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            Ok(())
+        }
+    }
+    type Standard = bincode::config::Configuration;
+    assert_eq!(Unknown.size_hint::<Standard>(), None);
+    assert_eq!(Some(Unknown).size_hint::<Standard>(), None);
+    assert_eq!(None::<Unknown>.size_hint::<Standard>(), Some(1));
+    assert_eq!((1u8, Unknown).size_hint::<Standard>(), None);
+    assert_eq!([Unknown, Unknown].size_hint::<Standard>(), None);
+
+    // A `RefCell` that is borrowed mutably can not be encoded
+    let cell = RefCell::new(5u16);
+    let _guard = cell.borrow_mut();
+    assert_eq!(cell.size_hint::<Standard>(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_fixed_point() {
+    use bincode::{error::EncodeError, FixedPoint};
+    use utils::assert_roundtrip;
+
+    let config = bincode::config::standard();
+
+    // Values are rounded to the nearest multiple of `1 / SCALE`, with halfway cases rounded away from zero
+    let bytes = bincode::encode_to_vec(FixedPoint::<2>(0.25), config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(1i64, config).unwrap());
+    let bytes = bincode::encode_to_vec(FixedPoint::<2>(-0.25), config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(-1i64, config).unwrap());
+    let bytes = bincode::encode_to_vec(FixedPoint::<1000>(1.2344), config).unwrap();
+    let (decoded, _): (FixedPoint<1000>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.0, 1.234);
+
+    // The largest and smallest values that fit in an `i64` after scaling
+    let max = FixedPoint::<1>(i64::MAX as f64 - 1024.0);
+    assert_eq!(max.to_fixed(), Some(i64::MAX - 1023));
+    assert_roundtrip(max, config);
+    let min = FixedPoint::<1>(i64::MIN as f64);
+    assert_eq!(min.to_fixed(), Some(i64::MIN));
+    assert_roundtrip(min, config);
+    assert_eq!(
+        FixedPoint::<1>::from_fixed(i64::MIN).to_fixed(),
+        Some(i64::MIN)
+    );
+
+    // Scaling past the range of an `i64` is an error
+    let too_large = i64::MAX as f64 / 1000.0;
+    for value in [too_large, -too_large * 1.01, f64::INFINITY, f64::NAN] {
+        match bincode::encode_to_vec(FixedPoint::<1000>(value), config).unwrap_err() {
+            EncodeError::FixedPointOverflow {
+                value: found,
+                scale: 1000,
+            } => assert!(found == value || (found.is_nan() && value.is_nan())),
+            e => panic!("Expected FixedPointOverflow, got {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn test_encode_into_custom_writer() {
+    use bincode::{enc::write::Writer, error::EncodeError};
+
+    /// A fixed-size ring buffer that counts the bytes that are written to it
+    struct RingWriter<const N: usize> {
+        buffer: [u8; N],
+        written: usize,
+    }
+
+    impl<const N: usize> Writer for RingWriter<N> {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+            for &byte in bytes {
+                self.buffer[self.written % N] = byte;
+                self.written += 1;
+            }
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let value = (1u8, 300u16, [5u8; 6], "hello");
+    let mut slice = [0u8; 32];
+    let len = bincode::encode_into_slice(value, &mut slice, config).unwrap();
+
+    let mut writer = RingWriter {
+        buffer: [0u8; 8],
+        written: 0,
+    };
+    bincode::encode_into_writer(value, &mut writer, config).unwrap();
+    assert_eq!(writer.written, len);
+    for (i, byte) in slice[len - 8..len].iter().enumerate() {
+        assert_eq!(writer.buffer[(len - 8 + i) % 8], *byte);
+    }
+}
+
+#[test]
+fn test_decode_from_custom_reader() {
+    use bincode::{de::read::Reader, error::DecodeError};
+
+    /// A reader that hands out its bytes one at a time
+    struct ByteReader<'a> {
+        bytes: &'a [u8],
+        reads: usize,
+    }
+
+    impl Reader for ByteReader<'_> {
+        fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+            let len = bytes.len();
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                let (first, rest) = self.bytes.split_first().ok_or(DecodeError::UnexpectedEnd {
+                    additional: len - i,
+                })?;
+                *byte = *first;
+                self.bytes = rest;
+                self.reads += 1;
+            }
+            Ok(())
+        }
+    }
+
+    type Value = (u8, u16, [u8; 6], u64, Option<char>);
+
+    let config = bincode::config::standard();
+    let value: Value = (1, 300, [5u8; 6], u64::MAX, Some('a'));
+    let mut slice = [0u8; 32];
+    let len = bincode::encode_into_slice(value, &mut slice, config).unwrap();
+
+    let mut reader = ByteReader {
+        bytes: &slice[..len],
+        reads: 0,
+    };
+    let decoded: Value = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(reader.reads, len);
+    assert!(reader.bytes.is_empty());
+
+    // Running out of bytes is reported by the reader
+    let reader = ByteReader {
+        bytes: &slice[..len - 1],
+        reads: 0,
+    };
+    let result: Result<Value, _> = bincode::decode_from_reader(reader, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+}
+
+#[test]
+fn test_decoder_finish() {
+    use bincode::{
+        de::Decoder,
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    /// A message that must be the only thing in its frame
+    #[derive(Debug, PartialEq)]
+    struct Frame {
+        id: u8,
+        payload: u32,
+    }
+
+    impl Encode for Frame {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.id.encode(encoder)?;
+            self.payload.encode(encoder)
+        }
+    }
+
+    impl Decode for Frame {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let frame = Frame {
+                id: u8::decode(decoder)?,
+                payload: u32::decode(decoder)?,
+            };
+            decoder.finish()?;
+            Ok(frame)
+        }
+    }
+
+    let config = bincode::config::standard();
+    let frame = Frame {
+        id: 3,
+        payload: 1000,
+    };
+    let mut buffer = [0u8; 16];
+    let len = bincode::encode_into_slice(&frame, &mut buffer, config).unwrap();
+
+    // The whole input is consumed
+    let (decoded, decoded_len): (Frame, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, frame);
+    assert_eq!(decoded_len, len);
+
+    // The input has a byte that was not consumed
+    let result: Result<(Frame, usize), _> = bincode::decode_from_slice(&buffer[..len + 1], config);
+    assert_eq!(result.unwrap_err(), DecodeError::TrailingBytes);
+
+    // Readers that can not peek can not detect trailing bytes
+    #[cfg(feature = "std")]
+    {
+        let result: Result<Frame, _> =
+            bincode::decode_from_std_read(&mut &buffer[..len + 1], config);
+        assert_eq!(result.unwrap(), frame);
+    }
+}
+
+#[test]
+fn test_once_cell() {
+    use core::cell::OnceCell;
+    use utils::assert_roundtrip;
+
+    let config = bincode::config::standard();
+
+    let empty = OnceCell::<u32>::new();
+    let mut buffer = [0u8; 8];
+    let len = bincode::encode_into_slice(&empty, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], [0]);
+    let (decoded, _): (OnceCell<u32>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded.get(), None);
+
+    let initialized = OnceCell::from(1000u32);
+    let len = bincode::encode_into_slice(&initialized, &mut buffer, config).unwrap();
+    let mut option_buffer = [0u8; 8];
+    let option_len = bincode::encode_into_slice(Some(1000u32), &mut option_buffer, config).unwrap();
+    assert_eq!(buffer[..len], option_buffer[..option_len]);
+    let (decoded, _): (OnceCell<u32>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded.get(), Some(&1000));
+
+    assert_roundtrip(empty, config);
+    assert_roundtrip(initialized, config);
+}
+
+#[test]
+fn test_varint_128() {
+    use bincode::error::{DecodeError, IntegerType};
+
+    fn check<T>(value: T, expected: &[u8])
+    where
+        T: bincode::Encode + bincode::Decode + PartialEq + core::fmt::Debug,
+    {
+        let config = bincode::config::standard();
+        let mut buffer = [0u8; 32];
+        let len = bincode::encode_into_slice(&value, &mut buffer, config).unwrap();
+        assert_eq!(&buffer[..len], expected);
+        let (decoded, decoded_len): (T, usize) =
+            bincode::decode_from_slice(&buffer[..len], config).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(decoded_len, len);
+    }
+
+    let mut max = [0xFF; 17];
+    max[0] = 254;
+    let mut above_u64 = [0; 17];
+    above_u64[0] = 254;
+    above_u64[9] = 1;
+
+    check(5u128, &[5]);
+    check(300u128, &[251, 44, 1]);
+    check(
+        u64::MAX as u128,
+        &[253, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    check(u64::MAX as u128 + 1, &above_u64);
+    check(u128::MAX, &max);
+
+    // Signed integers are zigzag encoded
+    check(-1i128, &[1]);
+    check(150i128, &[251, 44, 1]);
+    check(i128::MAX, &{
+        let mut bytes = max;
+        bytes[1] = 0xFE;
+        bytes
+    });
+    check(i128::MIN, &max);
+
+    // The fixed int encoding always uses 16 bytes
+    let mut buffer = [0u8; 32];
+    let len = bincode::encode_into_slice(300u128, &mut buffer, bincode::config::legacy()).unwrap();
+    assert_eq!(len, 16);
+
+    // A 128-bit varint does not fit in a smaller integer
+    let result: Result<(u64, usize), _> =
+        bincode::decode_from_slice(&max, bincode::config::standard());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidIntegerType {
+            expected: IntegerType::U64,
+            found: IntegerType::U128,
+        }
+    );
+    let result: Result<(i64, usize), _> =
+        bincode::decode_from_slice(&max, bincode::config::standard());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidIntegerType {
+            expected: IntegerType::I64,
+            found: IntegerType::I128,
+        }
+    );
+
+    // 255 is not a valid discriminant
+    let mut reserved = [0; 17];
+    reserved[0] = 255;
+    let result: Result<(u128, usize), _> =
+        bincode::decode_from_slice(&reserved, bincode::config::standard());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidIntegerType {
+            expected: IntegerType::U128,
+            found: IntegerType::Reserved,
+        }
+    );
+}
+
+#[test]
+fn test_len_over_u32_max() {
+    use bincode::error::DecodeError;
+
+    let len = u32::MAX as u64 + 1;
+    // A varint u64, followed by a single byte
+    let varint = [253, 0, 0, 0, 0, 1, 0, 0, 0, 7];
+    let mut fixed = [7u8; 9];
+    fixed[..8].copy_from_slice(&len.to_le_bytes());
+
+    // The length must never be truncated, e.g. to 0 on a 32-bit target
+    let result: Result<(&[u8], usize), _> =
+        bincode::decode_from_slice(&varint, bincode::config::standard());
+    assert!(matches!(
+        result,
+        Err(DecodeError::UnexpectedEnd { .. } | DecodeError::IntegerOverflow { .. })
+    ));
+    let result: Result<(&[u8], usize), _> =
+        bincode::decode_from_slice(&fixed, bincode::config::legacy());
+    assert!(matches!(
+        result,
+        Err(DecodeError::UnexpectedEnd { .. } | DecodeError::IntegerOverflow { .. })
+    ));
+
+    // `usize` itself is decoded from the same integer
+    let result: Result<(usize, usize), _> =
+        bincode::decode_from_slice(&varint, bincode::config::standard());
+    let fixed_result: Result<(usize, usize), _> =
+        bincode::decode_from_slice(&fixed, bincode::config::legacy());
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(result.unwrap(), (len as usize, 9));
+        assert_eq!(fixed_result.unwrap(), (len as usize, 8));
+    } else {
+        assert_eq!(result.unwrap_err(), DecodeError::OutsideUsizeRange(len));
+        assert_eq!(
+            fixed_result.unwrap_err(),
+            DecodeError::OutsideUsizeRange(len)
+        );
+    }
+}
+
+#[test]
+fn test_non_canonical_varint() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard();
+
+    // 5 padded to a u16
+    let result: Result<(u32, usize), _> = bincode::decode_from_slice(&[251, 5, 0], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+
+    // 250 is the largest value that fits in a single byte, 251 is the smallest u16
+    let (value, _): (u32, usize) = bincode::decode_from_slice(&[250], config).unwrap();
+    assert_eq!(value, 250);
+    let (value, _): (u32, usize) = bincode::decode_from_slice(&[251, 251, 0], config).unwrap();
+    assert_eq!(value, 251);
+    let result: Result<(u32, usize), _> = bincode::decode_from_slice(&[251, 250, 0], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+
+    // u16::MAX padded to a u32, and u32::MAX padded to a u64
+    let result: Result<(u64, usize), _> =
+        bincode::decode_from_slice(&[252, 0xFF, 0xFF, 0, 0], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+    let result: Result<(u64, usize), _> =
+        bincode::decode_from_slice(&[253, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+
+    // Signed integers are zigzag encoded, so -3 is encoded as 5
+    let (value, _): (i32, usize) = bincode::decode_from_slice(&[5], config).unwrap();
+    assert_eq!(value, -3);
+    let result: Result<(i32, usize), _> = bincode::decode_from_slice(&[251, 5, 0], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+
+    // Lengths are varints as well
+    let result: Result<(&[u8], usize), _> = bincode::decode_from_slice(&[251, 1, 0, 7], config);
+    assert_eq!(result.unwrap_err(), DecodeError::NonCanonicalVarint);
+
+    // The fixed int encoding has only one encoding per value
+    let (value, _): (u32, usize) =
+        bincode::decode_from_slice(&[251, 5, 0, 0], config.with_fixed_int_encoding()).unwrap();
+    assert_eq!(value, 0x05FB);
+}
+
+#[test]
+fn test_config_descriptor() {
+    use bincode::config::{self, Config, ConfigDescriptor};
+
+    let descriptor = config::standard().descriptor();
+    assert!(!descriptor.big_endian);
+    assert!(!descriptor.fixed_int_encoding);
+    assert!(!descriptor.skip_fixed_array_length);
+    assert_eq!(descriptor.limit, None);
+
+    let custom = config::legacy()
+        .with_big_endian()
+        .skip_fixed_array_length()
+        .with_limit::<1024>()
+        .with_max_collection_len::<16>()
+        .with_lenient_bool()
+        .with_string_len_in_chars()
+        .with_strict_map_ordering()
+        .with_max_depth::<64>()
+        .with_alignment::<16>()
+        .with_no_allocation();
+    let descriptor = custom.descriptor();
+    assert!(descriptor.big_endian);
+    assert!(descriptor.fixed_int_encoding);
+    assert!(descriptor.skip_fixed_array_length);
+    assert_eq!(descriptor.limit, Some(1024));
+    assert_eq!(descriptor.max_collection_len, Some(16));
+    assert!(descriptor.lenient_bool);
+    assert!(descriptor.string_len_in_chars);
+    assert!(descriptor.strict_map_ordering);
+    assert_eq!(descriptor.max_depth, Some(64));
+    assert_eq!(descriptor.alignment, Some(16));
+    assert!(descriptor.no_allocation);
+
+    for descriptor in [config::standard().descriptor(), descriptor] {
+        let mut buffer = [0u8; 32];
+        let len = bincode::encode_into_slice(descriptor, &mut buffer, config::standard()).unwrap();
+        let (decoded, decoded_len): (ConfigDescriptor, usize) =
+            bincode::decode_from_slice(&buffer[..len], config::standard()).unwrap();
+        assert_eq!(decoded, descriptor);
+        assert_eq!(decoded_len, len);
+    }
+
+    // Pick the matching config at runtime, based on a stored descriptor
+    fn decode_with(descriptor: ConfigDescriptor, bytes: &[u8]) -> u32 {
+        fn decode<C: Config>(descriptor: ConfigDescriptor, bytes: &[u8], config: C) -> u32 {
+            assert_eq!(config.descriptor(), descriptor);
+            bincode::decode_from_slice(bytes, config).unwrap().0
+        }
+        match (descriptor.big_endian, descriptor.fixed_int_encoding) {
+            (false, false) => decode(descriptor, bytes, config::standard()),
+            (false, true) => decode(
+                descriptor,
+                bytes,
+                config::standard().with_fixed_int_encoding(),
+            ),
+            (true, false) => decode(descriptor, bytes, config::standard().with_big_endian()),
+            (true, true) => decode(descriptor, bytes, config::legacy().with_big_endian()),
+        }
+    }
+
+    let mut buffer = [0u8; 32];
+    let config = config::legacy().with_big_endian();
+    let mut len =
+        bincode::encode_into_slice(config.descriptor(), &mut buffer, config::standard()).unwrap();
+    len += bincode::encode_into_slice(70_000u32, &mut buffer[len..], config).unwrap();
+
+    let (descriptor, header_len): (ConfigDescriptor, usize) =
+        bincode::decode_from_slice(&buffer[..len], config::standard()).unwrap();
+    assert_eq!(&buffer[header_len..len], &[0, 1, 0x11, 0x70]);
+    assert_eq!(decode_with(descriptor, &buffer[header_len..len]), 70_000);
+}
+
+#[test]
+fn test_config_by_reference() {
+    use bincode::config::{self, Config};
+
+    // Not `Copy`, so the config can only be borrowed out of it
+    struct Settings {
+        _name: [u8; 4],
+        config: config::Configuration<config::BigEndian, config::Fixint>,
+        _marker: core::marker::PhantomData<core::cell::Cell<()>>,
+    }
+
+    let settings = Settings {
+        _name: *b"test",
+        config: config::standard()
+            .with_big_endian()
+            .with_fixed_int_encoding(),
+        _marker: core::marker::PhantomData,
+    };
+    let config = &settings.config;
+    assert_eq!(config.descriptor(), settings.config.descriptor());
+
+    let mut by_ref = [0u8; 8];
+    let mut by_value = [0u8; 8];
+    let len = bincode::encode_into_slice(0x1234_5678u32, &mut by_ref, config).unwrap();
+    assert_eq!(len, 4);
+    bincode::encode_into_slice(0x1234_5678u32, &mut by_value, settings.config).unwrap();
+    assert_eq!(by_ref, by_value);
+    assert_eq!(&by_ref[..4], &[0x12, 0x34, 0x56, 0x78]);
+
+    let (decoded, decoded_len): (u32, usize) =
+        bincode::decode_from_slice(&by_ref[..len], config).unwrap();
+    assert_eq!(decoded, 0x1234_5678);
+    assert_eq!(decoded_len, len);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_duration_millis() {
+    use bincode::{error::EncodeError, DurationMillis};
+
+    let config = bincode::config::standard();
+
+    // Always encoded as a single `u32`
+    let bytes = bincode::encode_to_vec(
+        DurationMillis(Duration::from_secs(2)),
+        bincode::config::legacy(),
+    )
+    .unwrap();
+    assert_eq!(bytes, 2000u32.to_le_bytes());
+
+    // Sub-millisecond durations are rounded to the nearest millisecond, with halfway cases rounded up
+    for (duration, millis) in [
+        (Duration::from_nanos(1), 0u32),
+        (Duration::from_nanos(499_999), 0),
+        (Duration::from_nanos(500_000), 1),
+        (Duration::from_micros(1_499), 1),
+        (Duration::from_micros(1_500), 2),
+        (Duration::new(3, 999_600_000), 4_000),
+    ] {
+        assert_eq!(DurationMillis(duration).to_millis(), Some(millis));
+        let bytes = bincode::encode_to_vec(DurationMillis(duration), config).unwrap();
+        assert_eq!(bytes, bincode::encode_to_vec(millis, config).unwrap());
+        let (decoded, _): (DurationMillis, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded.0, Duration::from_millis(millis.into()));
+    }
+
+    // More than `u32::MAX` milliseconds, also after rounding, is an error
+    let max = Duration::from_millis(u32::MAX as u64);
+    for duration in [
+        max + Duration::from_micros(500),
+        max + Duration::from_millis(1),
+        Duration::MAX,
+    ] {
+        assert_eq!(DurationMillis(duration).to_millis(), None);
+        match bincode::encode_to_vec(DurationMillis(duration), config).unwrap_err() {
+            EncodeError::DurationMillisOverflow { duration: found } => assert_eq!(found, duration),
+            e => panic!("Expected DurationMillisOverflow, got {:?}", e),
+        }
+    }
+    assert_eq!(
+        DurationMillis(max + Duration::from_micros(499)).to_millis(),
+        Some(u32::MAX)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_handle() {
+    use bincode::Handle;
+
+    // Encoded exactly like the `usize`
+    let config = bincode::config::standard();
+    assert_eq!(
+        bincode::encode_to_vec(Handle(0x1000), config).unwrap(),
+        bincode::encode_to_vec(0x1000usize, config).unwrap()
+    );
+
+    let values = [1u64, 2, 3];
+    let handle = Handle::from_ptr(&values[1] as *const u64);
+    assert_eq!(handle.0, &values[1] as *const u64 as usize);
+    let bytes = bincode::encode_to_vec(handle, config).unwrap();
+    let (decoded, _): (Handle, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.to_ptr::<u64>(), &values[1] as *const u64);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_chain_reader() {
+    use bincode::{
+        de::{read::ChainReader, Decode, Decoder},
+        error::DecodeError,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Packet {
+        id: u32,
+        payload: [u8; 6],
+        checksum: u64,
+    }
+
+    impl Decode for Packet {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Ok(Packet {
+                id: u32::decode(decoder)?,
+                payload: <[u8; 6]>::decode(decoder)?,
+                checksum: u64::decode(decoder)?,
+            })
+        }
+    }
+
+    let expected = Packet {
+        id: 0x0102_0304,
+        payload: [10, 11, 12, 13, 14, 15],
+        checksum: u64::MAX - 1,
+    };
+    let config = bincode::config::legacy().skip_fixed_array_length();
+    let bytes =
+        bincode::encode_to_vec((expected.id, expected.payload, expected.checksum), config).unwrap();
+    assert_eq!(bytes.len(), 18);
+
+    // Split the encoding at every possible position, so every field straddles the boundary at some point
+    for split in 0..=bytes.len() {
+        let segments = [&bytes[..split], &bytes[split..]];
+        let packet: Packet =
+            bincode::decode_from_reader(ChainReader::new(&segments), config).unwrap();
+        assert_eq!(packet, expected);
+    }
+
+    // Many small and empty segments
+    let segments: Vec<&[u8]> = bytes.chunks(1).flat_map(|chunk| [&[][..], chunk]).collect();
+    let packet: Packet = bincode::decode_from_reader(ChainReader::new(&segments), config).unwrap();
+    assert_eq!(packet, expected);
+
+    // Running out of segments
+    let segments = [&bytes[..10], &bytes[10..15]];
+    assert_eq!(
+        bincode::decode_from_reader::<Packet, _, _>(ChainReader::new(&segments), config)
+            .unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 3 }
+    );
+    let segments: [&[u8]; 0] = [];
+    assert_eq!(
+        bincode::decode_from_reader::<u8, _, _>(ChainReader::new(&segments), config).unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+
+    // Varints are read through `peek_read` if they fit in the current segment, and with `read` otherwise
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec((1_000_000u32, 70_000u64), config).unwrap();
+    for split in 0..=bytes.len() {
+        let segments = [&bytes[..split], &bytes[split..]];
+        let decoded: (u32, u64) =
+            bincode::decode_from_reader(ChainReader::new(&segments), config).unwrap();
+        assert_eq!(decoded, (1_000_000, 70_000));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_option_discriminant() {
+    fn check<C: bincode::config::Config>(config: C) {
+        // The discriminant is a single byte, the same as a `u8` and as the varint of `0` and `1`
+        let none = bincode::encode_to_vec(None::<u64>, config).unwrap();
+        assert_eq!(none, [0]);
+        assert_eq!(none, bincode::encode_to_vec(0u8, config).unwrap());
+
+        let some = bincode::encode_to_vec(Some(7u64), config).unwrap();
+        assert_eq!(some[0], 1);
+        assert_eq!(some[..1], bincode::encode_to_vec(1u8, config).unwrap());
+        assert_eq!(some[1..], bincode::encode_to_vec(7u64, config).unwrap());
+
+        // Any other discriminant is rejected
+        let result: Result<(Option<u64>, usize), _> = bincode::decode_from_slice(&[2, 7], config);
+        assert!(matches!(
+            result.unwrap_err(),
+            bincode::error::DecodeError::UnexpectedVariant { found: 2, .. }
+        ));
+    }
+    check(bincode::config::standard());
+    check(bincode::config::standard().with_fixed_int_encoding());
+    check(bincode::config::legacy().with_big_endian());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_alignment() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard().with_alignment::<8>();
+    for len in 0..20 {
+        let value: Vec<u8> = (0..len).collect();
+        let encoded = bincode::encode_to_vec(&value, config).unwrap();
+        assert_eq!(encoded.len() % 8, 0);
+        let (decoded, decoded_len): (Vec<u8>, usize) =
+            bincode::decode_from_slice(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+        assert_eq!(decoded_len, encoded.len());
+
+        let mut buffer = [0xFF; 32];
+        let slice_len = bincode::encode_into_slice(&value, &mut buffer, config).unwrap();
+        assert_eq!(&buffer[..slice_len], encoded.as_slice());
+
+        let mut streamed = Vec::new();
+        bincode::encode_streaming(&value, config, |chunk: &[u8]| {
+            streamed.extend_from_slice(chunk);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(streamed, encoded);
+    }
+
+    // The value, the amount of padding, and the padding itself
+    let encoded = bincode::encode_to_vec(5u8, config).unwrap();
+    assert_eq!(encoded, [5, 6, 0, 0, 0, 0, 0, 0]);
+    // A value that fills the alignment exactly still needs room for the padding length
+    let encoded = bincode::encode_to_vec(1u64, config.with_fixed_int_encoding()).unwrap();
+    assert_eq!(encoded.len(), 16);
+    assert_eq!(encoded[8], 7);
+
+    let result: Result<(u8, usize), _> =
+        bincode::decode_from_slice(&[5, 6, 0, 0, 1, 0, 0, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidAlignmentPadding { alignment: 8 }
+    );
+    let result: Result<(u8, usize), _> =
+        bincode::decode_from_slice(&[5, 8, 0, 0, 0, 0, 0, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidAlignmentPadding { alignment: 8 }
+    );
+
+    // Without an alignment, no padding is written
+    let encoded = bincode::encode_to_vec(5u8, bincode::config::standard()).unwrap();
+    assert_eq!(encoded, [5]);
+}
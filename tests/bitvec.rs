@@ -0,0 +1,47 @@
+#![cfg(feature = "bitvec")]
+
+mod utils;
+
+use bitvec::{bitvec, order::Lsb0, vec::BitVec};
+use utils::assert_roundtrip;
+
+#[test]
+fn test_bitvec() {
+    let config = bincode::config::standard();
+
+    // 13 bits, so the last byte is only partially used
+    let bits: BitVec<u8, Lsb0> = bitvec![u8, Lsb0; 1, 0, 1, 1, 0, 0, 0, 1, 1, 1, 0, 0, 1];
+    let bytes = bincode::encode_to_vec(&bits, config).unwrap();
+    assert_eq!(bytes, [13, 0b1000_1101, 0b0001_0011]);
+
+    let (decoded, len): (BitVec<u8, Lsb0>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, bits);
+    assert_eq!(decoded.len(), 13);
+    assert_eq!(len, bytes.len());
+
+    assert_roundtrip(BitVec::<u8, Lsb0>::new(), config);
+    assert_roundtrip(bitvec![u8, Lsb0; 1; 16], config);
+    assert_roundtrip(bitvec![u8, Lsb0; 0, 1, 1], bincode::config::legacy());
+}
+
+#[test]
+fn test_bitvec_unused_bits() {
+    let config = bincode::config::standard();
+
+    // Truncating leaves the removed bits in the underlying byte, but they are not encoded
+    let mut bits: BitVec<u8, Lsb0> = bitvec![u8, Lsb0; 1; 8];
+    bits.truncate(3);
+    let bytes = bincode::encode_to_vec(&bits, config).unwrap();
+    assert_eq!(bytes, [3, 0b0000_0111]);
+
+    // The unused bits are ignored when decoding
+    let (decoded, _): (BitVec<u8, Lsb0>, usize) =
+        bincode::decode_from_slice(&[3, 0b1111_0101], config).unwrap();
+    assert_eq!(decoded, bitvec![u8, Lsb0; 1, 0, 1]);
+
+    // A bit slice that does not start at the beginning of a byte
+    let bits: BitVec<u8, Lsb0> = BitVec::from_bitslice(&bitvec![u8, Lsb0; 0, 0, 1, 1, 0, 1][2..]);
+    let bytes = bincode::encode_to_vec(&bits, config).unwrap();
+    assert_eq!(bytes, [4, 0b0000_1011]);
+}
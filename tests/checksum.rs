@@ -0,0 +1,94 @@
+use bincode::checksum::{Adler32, Checksum, ChecksumReader, ChecksumWriter, Fletcher16};
+use bincode::de::read::SliceReader;
+use bincode::enc::write::SliceWriter;
+use bincode::error::DecodeError;
+
+#[test]
+fn test_checksum_values() {
+    let mut adler = Adler32::default();
+    adler.update(b"Wiki");
+    adler.update(b"pedia");
+    assert_eq!(adler.value(), 0x11E6_0398);
+
+    let mut fletcher = Fletcher16::default();
+    fletcher.update(b"abcde");
+    assert_eq!(fletcher.value(), 0xC8F0);
+}
+
+#[test]
+fn test_checksum_roundtrip() {
+    let config = bincode::config::standard();
+    let value = (7u32, [1u16, 2, 3], 'c');
+    let mut buffer = [0u8; 64];
+
+    let len = bincode::encode_into_slice(value, &mut buffer, config).unwrap();
+    let mut fletcher = Fletcher16::default();
+    fletcher.update(&buffer[..len]);
+
+    // The checksum is appended as big-endian bytes
+    let checksum_len =
+        bincode::checksum::encode_into_slice::<Fletcher16, _, _>(value, &mut buffer, config)
+            .unwrap();
+    assert_eq!(checksum_len, len + 2);
+    assert_eq!(
+        buffer[len..checksum_len],
+        (fletcher.value() as u16).to_be_bytes()
+    );
+
+    let (decoded, read): ((u32, [u16; 3], char), usize) =
+        bincode::checksum::decode_from_slice::<Fletcher16, _, _>(&buffer[..checksum_len], config)
+            .unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(read, checksum_len);
+
+    // The writer and reader can also be used with any other writer and reader
+    let mut writer = ChecksumWriter::<_, Adler32>::new(SliceWriter::new(&mut buffer));
+    bincode::encode_into_writer(value, &mut writer, config).unwrap();
+    let checksum = writer.checksum();
+    let len = writer.finish().unwrap().bytes_written();
+    assert_eq!(buffer[len - 4..len], checksum.to_be_bytes());
+
+    let mut reader = ChecksumReader::<_, Adler32>::new(SliceReader::new(&buffer[..len]));
+    let decoded: (u32, [u16; 3], char) = bincode::decode_from_reader(&mut reader, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(reader.checksum(), checksum);
+    reader.verify().unwrap();
+
+    // Borrowed values are checked as well
+    let len =
+        bincode::checksum::encode_into_slice::<Adler32, _, _>("borrowed", &mut buffer, config)
+            .unwrap();
+    let (decoded, _): (&str, usize) =
+        bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, "borrowed");
+}
+
+#[test]
+fn test_checksum_mismatch() {
+    let config = bincode::config::standard();
+    let value = (1000u32, *b"corrupt me");
+    let mut buffer = [0u8; 64];
+    let len =
+        bincode::checksum::encode_into_slice::<Adler32, _, _>(value, &mut buffer, config).unwrap();
+
+    // Flip a bit in the array, which still decodes, but no longer matches the checksum
+    buffer[6] ^= 0b100;
+    let result: Result<((u32, [u8; 10]), usize), _> =
+        bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len], config);
+    match result.unwrap_err() {
+        DecodeError::ChecksumMismatch { stored, computed } => assert_ne!(stored, computed),
+        e => panic!("Expected ChecksumMismatch, got {:?}", e),
+    }
+
+    // A corrupted checksum is detected as well
+    buffer[6] ^= 0b100;
+    buffer[len - 1] ^= 1;
+    let result: Result<((u32, [u8; 10]), usize), _> =
+        bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len], config);
+    assert!(matches!(result, Err(DecodeError::ChecksumMismatch { .. })));
+
+    // A missing checksum is an unexpected end
+    let result: Result<((u32, [u8; 10]), usize), _> =
+        bincode::checksum::decode_from_slice::<Adler32, _, _>(&buffer[..len - 2], config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+}
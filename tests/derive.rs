@@ -83,9 +83,9 @@ fn test_encode_tuple() {
 #[test]
 fn test_decode_tuple() {
     let start = TestTupleStruct(5, 10, 1024);
-    let mut slice = [5, 10, 251, 0, 4];
+    let slice = [5, 10, 251, 0, 4];
     let (result, len): (TestTupleStruct, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 5);
 }
@@ -109,9 +109,9 @@ fn test_encode_enum_struct_variant() {
 #[test]
 fn test_decode_enum_struct_variant() {
     let start = TestEnum::Bar { name: 5u32 };
-    let mut slice = [1, 5];
+    let slice = [1, 5];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 2);
 }
@@ -119,9 +119,9 @@ fn test_decode_enum_struct_variant() {
 #[test]
 fn test_decode_enum_unit_variant() {
     let start = TestEnum::Foo;
-    let mut slice = [0];
+    let slice = [0];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 1);
 }
@@ -149,9 +149,9 @@ fn test_encode_enum_tuple_variant() {
 #[test]
 fn test_decode_enum_tuple_variant() {
     let start = TestEnum::Baz(5, 10, 1024);
-    let mut slice = [2, 5, 10, 251, 0, 4];
+    let slice = [2, 5, 10, 251, 0, 4];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 6);
 }
@@ -176,9 +176,9 @@ fn test_encode_borrowed_enum_struct_variant() {
 #[test]
 fn test_decode_borrowed_enum_struct_variant() {
     let start = TestEnum2::Bar { name: "foo" };
-    let mut slice = [1, 3, 102, 111, 111];
+    let slice = [1, 3, 102, 111, 111];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 5);
 }
@@ -186,9 +186,9 @@ fn test_decode_borrowed_enum_struct_variant() {
 #[test]
 fn test_decode_borrowed_enum_unit_variant() {
     let start = TestEnum2::Foo;
-    let mut slice = [0];
+    let slice = [0];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 1);
 }
@@ -216,9 +216,9 @@ fn test_encode_borrowed_enum_tuple_variant() {
 #[test]
 fn test_decode_borrowed_enum_tuple_variant() {
     let start = TestEnum2::Baz(5, 10, 1024);
-    let mut slice = [2, 5, 10, 251, 0, 4];
+    let slice = [2, 5, 10, 251, 0, 4];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 6);
 }
@@ -254,7 +254,7 @@ fn test_c_style_enum() {
         Ok(result)
     }
 
-    fn expected_err(idx: u32) -> Result<CStyleEnum, bincode::error::DecodeError> {
+    fn expected_err(idx: i128) -> Result<CStyleEnum, bincode::error::DecodeError> {
         Err(bincode::error::DecodeError::UnexpectedVariant {
             type_name: "CStyleEnum",
             allowed: bincode::error::AllowedEnumVariants::Allowed(&[1, 2, 3, 5, 6]),
@@ -272,6 +272,195 @@ fn test_c_style_enum() {
     assert_eq!(de(7), expected_err(7));
 }
 
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(u16)]
+enum ReprEnum {
+    Nop,
+    Move { x: i32, y: i32 } = 10,
+    Jump(u32),
+    Halt = 20,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(C, u8)]
+enum ReprU8Enum {
+    A,
+    B(u8) = 3,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::BorrowDecode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(u8)]
+enum ReprBorrowEnum<'a> {
+    A(&'a str) = 2,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(i8)]
+enum SignedReprEnum {
+    Down = -1,
+    Stay,
+    Up(u8) = 1 << 2,
+    Far,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(u64)]
+enum WideReprEnum {
+    A,
+    B(u8) = 1 << 40,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+#[repr(u8)]
+enum SingleReprEnum {
+    A(u32),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_repr_enum() {
+    let config = bincode::config::legacy();
+
+    let bytes = bincode::encode_to_vec(ReprEnum::Nop, config).unwrap();
+    assert_eq!(bytes, [0, 0]);
+    let bytes = bincode::encode_to_vec(ReprEnum::Move { x: 1, y: -1 }, config).unwrap();
+    assert_eq!(bytes, [10, 0, 1, 0, 0, 0, 255, 255, 255, 255]);
+    let (decoded, len): (ReprEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, ReprEnum::Move { x: 1, y: -1 });
+    assert_eq!(len, 10);
+    let bytes = bincode::encode_to_vec(ReprEnum::Jump(5), config).unwrap();
+    assert_eq!(bytes, [11, 0, 5, 0, 0, 0]);
+    let bytes = bincode::encode_to_vec(ReprEnum::Halt, config).unwrap();
+    assert_eq!(bytes, [20, 0]);
+
+    let result: Result<(ReprEnum, usize), _> = bincode::decode_from_slice(&[1, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant {
+            type_name: "ReprEnum",
+            allowed: bincode::error::AllowedEnumVariants::Allowed(&[0, 10, 11, 20]),
+            found: 1,
+        }
+    );
+
+    let bytes = bincode::encode_to_vec(ReprU8Enum::B(7), config).unwrap();
+    assert_eq!(bytes, [3, 7]);
+    let (decoded, len): (ReprU8Enum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, ReprU8Enum::B(7));
+    assert_eq!(len, 2);
+    let (decoded, _): (ReprU8Enum, usize) = bincode::decode_from_slice(&[0], config).unwrap();
+    assert_eq!(decoded, ReprU8Enum::A);
+
+    let bytes = bincode::encode_to_vec(ReprBorrowEnum::A("hi"), config).unwrap();
+    assert_eq!(bytes, [2, 2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i']);
+    let (decoded, _): (ReprBorrowEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, ReprBorrowEnum::A("hi"));
+
+    // Negative discriminants and discriminants that are expressions
+    for (value, expected) in [
+        (SignedReprEnum::Down, &[255][..]),
+        (SignedReprEnum::Stay, &[0]),
+        (SignedReprEnum::Up(3), &[4, 3]),
+        (SignedReprEnum::Far, &[5]),
+    ] {
+        let bytes = bincode::encode_to_vec(&value, config).unwrap();
+        assert_eq!(bytes, expected);
+        let (decoded, _): (SignedReprEnum, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+    let result: Result<(SignedReprEnum, usize), _> = bincode::decode_from_slice(&[1], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant {
+            type_name: "SignedReprEnum",
+            allowed: bincode::error::AllowedEnumVariants::Allowed(&[-1, 0, 4, 5]),
+            found: 1,
+        }
+    );
+    let result: Result<(SignedReprEnum, usize), _> = bincode::decode_from_slice(&[254], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant { found: -2, .. }
+    ));
+
+    let bytes = bincode::encode_to_vec(WideReprEnum::B(7), config).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0, 0, 1, 0, 0, 7]);
+    let (decoded, _): (WideReprEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, WideReprEnum::B(7));
+    let bytes = bincode::encode_to_vec(WideReprEnum::A, config).unwrap();
+    assert_eq!(bytes, [0; 8]);
+    // Discriminants that do not fit in a `u32` are reported as they are
+    let result: Result<(WideReprEnum, usize), _> =
+        bincode::decode_from_slice(&[5, 0, 0, 0, 0, 1, 0, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant {
+            type_name: "WideReprEnum",
+            allowed: bincode::error::AllowedEnumVariants::Allowed(&[0, 1 << 40]),
+            found: 1 << 40 | 5,
+        }
+    );
+
+    // An explicit repr is encoded, even if the enum has a single variant
+    let bytes = bincode::encode_to_vec(SingleReprEnum::A(5), config).unwrap();
+    assert_eq!(bytes, [0, 5, 0, 0, 0]);
+    let (decoded, _): (SingleReprEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SingleReprEnum::A(5));
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+enum SingleVariantEnum {
+    A(u32),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Decode, bincode::Encode, PartialEq, Eq, Debug)]
+enum SingleUnitEnum {
+    A,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::BorrowDecode, bincode::Encode, PartialEq, Eq, Debug)]
+enum SingleBorrowEnum<'a> {
+    A { a: &'a str, b: u8 },
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_single_variant_enum() {
+    let config = bincode::config::legacy();
+
+    // The variant index is not encoded
+    let bytes = bincode::encode_to_vec(SingleVariantEnum::A(5), config).unwrap();
+    assert_eq!(bytes, [5, 0, 0, 0]);
+    let (decoded, len): (SingleVariantEnum, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SingleVariantEnum::A(5));
+    assert_eq!(len, 4);
+
+    let bytes = bincode::encode_to_vec(SingleUnitEnum::A, config).unwrap();
+    assert!(bytes.is_empty());
+    let (decoded, len): (SingleUnitEnum, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SingleUnitEnum::A);
+    assert_eq!(len, 0);
+
+    let input = SingleBorrowEnum::A { a: "hi", b: 7 };
+    let bytes = bincode::encode_to_vec(&input, config).unwrap();
+    assert_eq!(bytes, [2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i', 7]);
+    let (decoded, _): (SingleBorrowEnum, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, input);
+}
+
 macro_rules! macro_newtype {
     ($name:ident) => {
         #[derive(bincode::Encode, bincode::Decode, PartialEq, Eq, Debug)]
@@ -365,3 +554,956 @@ fn test_enum_with_generics_roundtrip() {
             .0;
     assert_eq!(start, decoded);
 }
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct TestWhereClause<T, U>
+where
+    T: Clone + core::fmt::Debug,
+    U: Default,
+{
+    inner: T,
+    other: U,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub enum TestEnumWhereClause<T>
+where
+    T: Clone,
+{
+    A(T),
+    B { inner: T },
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+pub enum TestBorrowWhereClause<'a, T>
+where
+    T: Copy,
+{
+    A(T),
+    B(&'a str),
+}
+
+#[test]
+fn test_where_clause() {
+    let start = TestWhereClause {
+        inner: 5u32,
+        other: 10u8,
+    };
+    let mut slice = [0u8; 1024];
+    let bytes_written =
+        bincode::encode_into_slice(&start, &mut slice, bincode::config::standard()).unwrap();
+    assert_eq!(&slice[..bytes_written], &[5, 10]);
+    let (result, len): (TestWhereClause<u32, u8>, usize) =
+        bincode::decode_from_slice(&slice[..bytes_written], bincode::config::standard()).unwrap();
+    assert_eq!(result, start);
+    assert_eq!(len, 2);
+
+    let start = TestEnumWhereClause::B { inner: 5u32 };
+    let bytes_written =
+        bincode::encode_into_slice(&start, &mut slice, bincode::config::standard()).unwrap();
+    assert_eq!(&slice[..bytes_written], &[1, 5]);
+    let (result, len): (TestEnumWhereClause<u32>, usize) =
+        bincode::decode_from_slice(&slice[..bytes_written], bincode::config::standard()).unwrap();
+    assert_eq!(result, start);
+    assert_eq!(len, 2);
+
+    let start = TestBorrowWhereClause::<u32>::B("abc");
+    let bytes_written =
+        bincode::encode_into_slice(&start, &mut slice, bincode::config::standard()).unwrap();
+    assert_eq!(&slice[..bytes_written], &[1, 3, b'a', b'b', b'c']);
+    let (result, len): (TestBorrowWhereClause<u32>, usize) =
+        bincode::decode_from_slice(&slice[..bytes_written], bincode::config::standard()).unwrap();
+    assert_eq!(result, start);
+    assert_eq!(len, 5);
+}
+
+#[rustfmt::skip]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub enum LargeEnum {
+    V0, V1, V2, V3, V4, V5, V6, V7, V8, V9,
+    V10, V11, V12, V13, V14, V15, V16, V17, V18, V19,
+    V20, V21, V22, V23, V24, V25, V26, V27, V28, V29,
+    V30, V31, V32, V33, V34, V35, V36, V37, V38, V39,
+    V40, V41, V42, V43, V44, V45, V46, V47, V48, V49,
+    V50, V51, V52, V53, V54, V55, V56, V57, V58, V59,
+    V60, V61, V62, V63, V64, V65, V66, V67, V68, V69,
+    V70, V71, V72, V73, V74, V75, V76, V77, V78, V79,
+    V80, V81, V82, V83, V84, V85, V86, V87, V88, V89,
+    V90, V91, V92, V93, V94, V95, V96, V97, V98, V99,
+    V100, V101, V102, V103, V104, V105, V106, V107, V108, V109,
+    V110, V111, V112, V113, V114, V115, V116, V117, V118, V119,
+    V120, V121, V122, V123, V124, V125, V126, V127, V128, V129,
+    V130, V131, V132, V133, V134, V135, V136, V137, V138, V139,
+    V140, V141, V142, V143, V144, V145, V146, V147, V148, V149,
+    V150, V151, V152, V153, V154, V155, V156, V157, V158, V159,
+    V160, V161, V162, V163, V164, V165, V166, V167, V168, V169,
+    V170, V171, V172, V173, V174, V175, V176, V177, V178, V179,
+    V180, V181, V182, V183, V184, V185, V186, V187, V188, V189,
+    V190, V191, V192, V193, V194, V195, V196, V197, V198, V199,
+    V200, V201, V202, V203, V204, V205, V206, V207, V208, V209,
+    V210, V211, V212, V213, V214, V215, V216, V217, V218, V219,
+    V220, V221, V222, V223, V224, V225, V226, V227, V228, V229,
+    V230, V231, V232, V233, V234, V235, V236, V237, V238, V239,
+    V240, V241, V242, V243, V244, V245, V246, V247, V248, V249,
+    V250, V251, V252, V253, V254, V255, V256, V257, V258, V259,
+    V260, V261, V262, V263, V264, V265, V266, V267, V268, V269,
+    V270, V271, V272, V273, V274, V275, V276, V277, V278, V279,
+    V280, V281, V282, V283, V284, V285, V286, V287, V288, V289,
+    V290, V291, V292, V293, V294, V295, V296, V297, V298,
+    Last(u8),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_large_enum() {
+    let config = bincode::config::standard();
+
+    // The variant index is a varint, so the first 251 variants take a single byte
+    let bytes = bincode::encode_to_vec(LargeEnum::V250, config).unwrap();
+    assert_eq!(bytes, [250]);
+    let bytes = bincode::encode_to_vec(LargeEnum::V251, config).unwrap();
+    assert_eq!(bytes, [251, 251, 0]);
+
+    let bytes = bincode::encode_to_vec(LargeEnum::Last(7), config).unwrap();
+    assert_eq!(bytes, [251, 0x2B, 0x01, 7]);
+    let (decoded, len): (LargeEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, LargeEnum::Last(7));
+    assert_eq!(len, 4);
+
+    let config = bincode::config::legacy();
+    let bytes = bincode::encode_to_vec(LargeEnum::Last(7), config).unwrap();
+    assert_eq!(bytes, [0x2B, 0x01, 0, 0, 7]);
+    let (decoded, _): (LargeEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, LargeEnum::Last(7));
+
+    let result: Result<(LargeEnum, usize), _> =
+        bincode::decode_from_slice(&[0x2C, 0x01, 0, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant {
+            type_name: "LargeEnum",
+            allowed: bincode::error::AllowedEnumVariants::Range { min: 0, max: 299 },
+            found: 300,
+        }
+    );
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(transparent)]
+pub struct Hash([u8; 32]);
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(transparent)]
+pub struct Digest<const N: usize> {
+    bytes: [u8; N],
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_transparent_array() {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    let config = bincode::config::standard().skip_fixed_array_length();
+    let encoded = bincode::encode_to_vec(Hash(bytes), config).unwrap();
+    assert_eq!(encoded, bytes);
+    let (decoded, len): (Hash, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Hash(bytes));
+    assert_eq!(len, 32);
+
+    // The struct is always encoded the same as its field
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(Hash(bytes), config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(bytes, config).unwrap());
+    let (decoded, _): (Hash, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, Hash(bytes));
+
+    let digest = Digest { bytes: [1u8; 20] };
+    let config = bincode::config::standard().skip_fixed_array_length();
+    let encoded = bincode::encode_to_vec(&digest, config).unwrap();
+    assert_eq!(encoded, [1u8; 20]);
+    let (decoded, _): (Digest<20>, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, digest);
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct FlattenInner {
+    a: u8,
+    b: String,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct FlattenOuter {
+    #[bincode(flatten)]
+    inner: FlattenInner,
+    x: u8,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct FlattenManual {
+    a: u8,
+    b: String,
+    x: u8,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::BorrowDecode, PartialEq, Debug)]
+pub struct FlattenBorrowed<'a> {
+    s: &'a str,
+    n: u16,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::BorrowDecode, PartialEq, Debug)]
+pub enum FlattenEnum<'a> {
+    Empty,
+    Borrowed {
+        x: u8,
+        #[bincode(flatten)]
+        inner: FlattenBorrowed<'a>,
+    },
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_flatten() {
+    let outer = FlattenOuter {
+        inner: FlattenInner {
+            a: 5,
+            b: String::from("hello"),
+        },
+        x: 10,
+    };
+    let manual = FlattenManual {
+        a: 5,
+        b: String::from("hello"),
+        x: 10,
+    };
+
+    let config = bincode::config::standard();
+    let encoded = bincode::encode_to_vec(&outer, config).unwrap();
+    assert_eq!(encoded, bincode::encode_to_vec(&manual, config).unwrap());
+    assert_eq!(encoded, [5, 5, b'h', b'e', b'l', b'l', b'o', 10]);
+
+    let (decoded, len): (FlattenOuter, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, outer);
+    assert_eq!(len, encoded.len());
+    let (decoded, _): (FlattenManual, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, manual);
+
+    let encoded = [1, 10, 2, b'h', b'i', 7];
+    let (decoded, _): (FlattenEnum, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(
+        decoded,
+        FlattenEnum::Borrowed {
+            x: 10,
+            inner: FlattenBorrowed { s: "hi", n: 7 }
+        }
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode)]
+pub struct InFieldInner {
+    a: u8,
+    cell: core::cell::RefCell<u32>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode)]
+pub struct InFieldOuter {
+    x: u32,
+    inner: InFieldInner,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode)]
+pub enum InFieldEnum {
+    A,
+    B(u8, core::cell::RefCell<u32>),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_encode_error_in_field() {
+    use bincode::error::EncodeError;
+
+    let config = bincode::config::standard();
+    let value = InFieldOuter {
+        x: 5,
+        inner: InFieldInner {
+            a: 1,
+            cell: core::cell::RefCell::new(2),
+        },
+    };
+    assert!(bincode::encode_to_vec(&value, config).is_ok());
+
+    let _borrow = value.inner.cell.borrow_mut();
+    let err = bincode::encode_to_vec(&value, config).unwrap_err();
+    let inner = match err {
+        EncodeError::InField { name, inner } => {
+            assert_eq!(name, "inner");
+            inner
+        }
+        e => panic!("Expected InField, got {:?}", e),
+    };
+    assert!(format!("{}", inner).contains("\"cell\""));
+    match *inner {
+        EncodeError::InField { name, inner } => {
+            assert_eq!(name, "cell");
+            assert!(matches!(*inner, EncodeError::RefCellAlreadyBorrowed { .. }));
+        }
+        e => panic!("Expected InField, got {:?}", e),
+    }
+
+    // Tuple fields are named by their index
+    let value = InFieldEnum::B(1, core::cell::RefCell::new(2));
+    let _borrow = match &value {
+        InFieldEnum::B(_, cell) => cell.borrow_mut(),
+        InFieldEnum::A => unreachable!(),
+    };
+    let err = bincode::encode_to_vec(&value, config).unwrap_err();
+    assert!(matches!(err, EncodeError::InField { name: "1", .. }));
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(version = 1)]
+struct VersionedV1 {
+    id: u32,
+    name: String,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(version = 2)]
+struct VersionedV2 {
+    id: u32,
+    #[bincode(since = 2)]
+    flags: u8,
+    name: String,
+    #[bincode(since = 2)]
+    tags: Vec<String>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(version = 3)]
+struct VersionedTuple<'a>(&'a str, #[bincode(since = 3)] Option<&'a str>);
+
+// `value` is set to its default value in old data, so `T` must implement `Default`. `U` does not need to.
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(version = 2)]
+struct VersionedGeneric<T, U> {
+    #[bincode(since = 2)]
+    value: T,
+    other: U,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct NoDefault(u8);
+
+#[test]
+fn test_versioned_generic_struct() {
+    let config = bincode::config::standard();
+
+    let (decoded, _): (VersionedGeneric<u32, NoDefault>, usize) =
+        bincode::decode_from_slice(&[1, 5], config).unwrap();
+    assert_eq!(
+        decoded,
+        VersionedGeneric {
+            value: 0,
+            other: NoDefault(5)
+        }
+    );
+    let (decoded, _): (VersionedGeneric<u32, NoDefault>, usize) =
+        bincode::decode_from_slice(&[2, 3, 5], config).unwrap();
+    assert_eq!(
+        decoded,
+        VersionedGeneric {
+            value: 3,
+            other: NoDefault(5)
+        }
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_versioned_struct() {
+    let config = bincode::config::standard();
+
+    let v1 = VersionedV1 {
+        id: 7,
+        name: String::from("old"),
+    };
+    let v1_bytes = bincode::encode_to_vec(&v1, config).unwrap();
+    assert_eq!(v1_bytes, [1, 7, 3, b'o', b'l', b'd']);
+
+    // Old data decodes into the current struct, with the new fields set to their default
+    let (decoded, len): (VersionedV2, usize) =
+        bincode::decode_from_slice(&v1_bytes, config).unwrap();
+    assert_eq!(len, v1_bytes.len());
+    assert_eq!(
+        decoded,
+        VersionedV2 {
+            id: 7,
+            flags: 0,
+            name: String::from("old"),
+            tags: Vec::new(),
+        }
+    );
+
+    let v2 = VersionedV2 {
+        id: 8,
+        flags: 3,
+        name: String::from("new"),
+        tags: vec![String::from("a")],
+    };
+    let v2_bytes = bincode::encode_to_vec(&v2, config).unwrap();
+    assert_eq!(v2_bytes, [2, 8, 3, 3, b'n', b'e', b'w', 1, 1, b'a']);
+    let (decoded, len): (VersionedV2, usize) =
+        bincode::decode_from_slice(&v2_bytes, config).unwrap();
+    assert_eq!(len, v2_bytes.len());
+    assert_eq!(decoded, v2);
+
+    // Data that is newer than the struct is rejected
+    assert!(matches!(
+        bincode::decode_from_slice::<VersionedV1, _>(&v2_bytes, config),
+        Err(bincode::error::DecodeError::UnsupportedVersion {
+            found: 2,
+            latest: 1
+        })
+    ));
+
+    let old_tuple = [2, 3, b'a', b'b', b'c'];
+    let (decoded, _): (VersionedTuple, usize) =
+        bincode::decode_from_slice(&old_tuple, config).unwrap();
+    assert_eq!(decoded, VersionedTuple("abc", None));
+
+    let tuple = VersionedTuple("abc", Some("d"));
+    let tuple_bytes = bincode::encode_to_vec(&tuple, config).unwrap();
+    assert_eq!(tuple_bytes, [3, 3, b'a', b'b', b'c', 1, 1, b'd']);
+    let (decoded, _): (VersionedTuple, usize) =
+        bincode::decode_from_slice(&tuple_bytes, config).unwrap();
+    assert_eq!(decoded, tuple);
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum MessageV1 {
+    Ping,
+    Text(String),
+    #[bincode(other)]
+    Unknown(u32, Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum MessageV2 {
+    Ping,
+    Text(String),
+    Move {
+        x: i32,
+        y: i32,
+    },
+    #[bincode(other)]
+    Unknown {
+        tag: u32,
+        bytes: Vec<u8>,
+    },
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+enum BorrowedMessage<'a> {
+    Text(&'a str),
+    #[bincode(other)]
+    Unknown(u32, &'a [u8]),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_enum_other_variant() {
+    let config = bincode::config::standard();
+
+    // The fields of every variant are prefixed with their length
+    let bytes = bincode::encode_to_vec(MessageV2::Ping, config).unwrap();
+    assert_eq!(bytes, [0, 0]);
+    let bytes = bincode::encode_to_vec(MessageV2::Text(String::from("hi")), config).unwrap();
+    assert_eq!(bytes, [1, 3, 2, b'h', b'i']);
+
+    // Known variants decode as usual
+    let (decoded, len): (MessageV1, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, MessageV1::Text(String::from("hi")));
+    assert_eq!(len, bytes.len());
+
+    // A variant that was added later decodes into the catch-all, followed by the next value
+    let mut bytes = bincode::encode_to_vec(MessageV2::Move { x: -1, y: 300 }, config).unwrap();
+    assert_eq!(bytes, [2, 4, 1, 251, 88, 2]);
+    bytes.push(7);
+    let ((decoded, next), len): ((MessageV1, u8), usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, MessageV1::Unknown(2, vec![1, 251, 88, 2]));
+    assert_eq!(next, 7);
+    assert_eq!(len, bytes.len());
+
+    // Re-encoding the catch-all writes the original bytes, which the newer version can decode
+    let reencoded = bincode::encode_to_vec(&decoded, config).unwrap();
+    assert_eq!(reencoded, bytes[..bytes.len() - 1]);
+    let (decoded, _): (MessageV2, usize) = bincode::decode_from_slice(&reencoded, config).unwrap();
+    assert_eq!(decoded, MessageV2::Move { x: -1, y: 300 });
+
+    let (decoded, _): (MessageV2, usize) =
+        bincode::decode_from_slice(&[9, 2, 5, 6], config).unwrap();
+    assert_eq!(
+        decoded,
+        MessageV2::Unknown {
+            tag: 9,
+            bytes: vec![5, 6]
+        }
+    );
+
+    // A known variant can not read past its length
+    let result: Result<(MessageV1, usize), _> =
+        bincode::decode_from_slice(&[1, 2, 3, b'a', b'b', b'c'], config);
+    assert!(matches!(
+        result,
+        Err(bincode::error::DecodeError::UnexpectedEnd { additional: 1 })
+    ));
+
+    let bytes = [0, 3, 2, b'h', b'i', 4, 2, 1, 2];
+    let (text, len): (BorrowedMessage, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(text, BorrowedMessage::Text("hi"));
+    let (unknown, _): (BorrowedMessage, usize) =
+        bincode::decode_from_slice(&bytes[len..], config).unwrap();
+    assert_eq!(unknown, BorrowedMessage::Unknown(4, &[1, 2]));
+    assert_eq!(
+        bincode::encode_to_vec((&text, &unknown), config).unwrap(),
+        bytes
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_enum_other_variant_raw_bytes() {
+    let config = bincode::config::standard().with_fixed_int_encoding();
+
+    // A `Move { x: -1, y: 300 }` from a newer version of the enum
+    let bytes = [
+        2, 0, 0, 0, // variant index
+        8, 0, 0, 0, 0, 0, 0, 0, // length of the fields
+        255, 255, 255, 255, // x
+        44, 1, 0, 0, // y
+    ];
+    let (decoded, len): (MessageV1, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(
+        decoded,
+        MessageV1::Unknown(2, vec![255, 255, 255, 255, 44, 1, 0, 0])
+    );
+    assert_eq!(bincode::encode_to_vec(&decoded, config).unwrap(), bytes);
+}
+
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Debug)]
+struct Scaled(u32);
+
+#[cfg(feature = "alloc")]
+impl bincode::Encode for Scaled {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        let scale = encoder
+            .context()
+            .and_then(|ctx| ctx.downcast_ref::<u32>())
+            .copied()
+            .ok_or(bincode::error::EncodeError::Other("Missing scale"))?;
+        (self.0 * scale).encode(encoder)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl bincode::Decode for Scaled {
+    fn decode<D: bincode::de::Decoder>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        let value = u32::decode(decoder)?;
+        let scale = decoder
+            .context()
+            .and_then(|ctx| ctx.downcast_ref::<u32>())
+            .copied()
+            .ok_or(bincode::error::DecodeError::Other("Missing scale"))?;
+        Ok(Scaled(value / scale))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Measurement {
+    Length(Scaled),
+    #[bincode(skip_encoding)]
+    Cached(u32),
+    #[bincode(other)]
+    Unknown(u32, Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_enum_other_variant_context() {
+    use bincode::{
+        de::{read::SliceReader, DecoderImpl},
+        enc::{write::SliceWriter, EncoderImpl},
+        error::{AllowedEnumVariants, DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    // The context is available while encoding the length prefixed fields, and while decoding them
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 8];
+    let mut encoder = EncoderImpl::with_context(SliceWriter::new(&mut buffer), config, 1000u32);
+    Measurement::Length(Scaled(5)).encode(&mut encoder).unwrap();
+    let len = encoder.into_writer().bytes_written();
+    assert_eq!(buffer[..len], [0, 3, 251, 0x88, 0x13]);
+
+    let mut decoder = DecoderImpl::with_context(SliceReader::new(&buffer[..len]), config, 1000u32);
+    let decoded = Measurement::decode(&mut decoder).unwrap();
+    assert_eq!(decoded, Measurement::Length(Scaled(5)));
+
+    // The index of a skipped variant is rejected, instead of being decoded into the `other` variant
+    let result = bincode::encode_to_vec(Measurement::Cached(5), config);
+    assert!(matches!(
+        result.unwrap_err(),
+        EncodeError::UnsupportedVariant {
+            type_name: "Measurement",
+            name: "Cached"
+        }
+    ));
+    let result: Result<(Measurement, usize), _> = bincode::decode_from_slice(&[1, 0], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "Measurement",
+            allowed: AllowedEnumVariants::Allowed(&[0]),
+            found: 1,
+        }
+    );
+    let (decoded, _): (Measurement, usize) =
+        bincode::decode_from_slice(&[2, 1, 9], config).unwrap();
+    assert_eq!(decoded, Measurement::Unknown(2, vec![9]));
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Expr {
+    Literal(u32),
+    Negate(Box<Expr>),
+    Add(Vec<Expr>),
+    #[bincode(other)]
+    Unknown(u32, Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_enum_other_variant_recursive() {
+    let config = bincode::config::standard();
+
+    // `Literal` has a known size, the length of the other variants is calculated by encoding them into a buffer
+    let expr = Expr::Add(vec![
+        Expr::Literal(1),
+        Expr::Negate(Box::new(Expr::Literal(300))),
+    ]);
+    let bytes = bincode::encode_to_vec(&expr, config).unwrap();
+    assert_eq!(bytes, [2, 11, 2, 0, 1, 1, 1, 5, 0, 3, 251, 44, 1]);
+
+    let (decoded, len): (Expr, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, expr);
+    assert_eq!(len, bytes.len());
+
+    // An unknown variant inside of a known variant is decoded into the catch-all
+    let (decoded, _): (Expr, usize) = bincode::decode_from_slice(&[1, 3, 7, 1, 5], config).unwrap();
+    assert_eq!(decoded, Expr::Negate(Box::new(Expr::Unknown(7, vec![5]))));
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+enum Shape {
+    Square(u32),
+    /// Derived from the other variants, so it must never be sent
+    #[bincode(skip_encoding)]
+    Cached {
+        area: u64,
+    },
+    Circle(u32),
+}
+
+#[test]
+fn test_skip_encoding_variant() {
+    use bincode::error::{AllowedEnumVariants, DecodeError, EncodeError};
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+
+    let result = bincode::encode_into_slice(Shape::Cached { area: 9 }, &mut buffer, config);
+    assert!(matches!(
+        result,
+        Err(EncodeError::UnsupportedVariant {
+            type_name: "Shape",
+            name: "Cached"
+        })
+    ));
+
+    // The skipped variant keeps its index, so the other variants are not affected
+    let len = bincode::encode_into_slice(Shape::Circle(3), &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [2, 3]);
+    let (decoded, _): (Shape, usize) = bincode::decode_from_slice(&[2, 3], config).unwrap();
+    assert_eq!(decoded, Shape::Circle(3));
+
+    // The index of the skipped variant is never decoded
+    let result: Result<(Shape, usize), _> = bincode::decode_from_slice(&[1, 9], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "Shape",
+            allowed: AllowedEnumVariants::Allowed(&[0, 2]),
+            found: 1,
+        }
+    );
+}
+
+#[test]
+fn test_trailing_phantom_data() {
+    use core::marker::PhantomData;
+
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    struct Tagged<X> {
+        a: u32,
+        _p: PhantomData<X>,
+    }
+
+    let config = bincode::config::standard().with_fixed_int_encoding();
+    let start = Tagged::<u64> {
+        a: 0x1234_5678,
+        _p: PhantomData,
+    };
+    let mut buffer = [0u8; 16];
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0x78, 0x56, 0x34, 0x12]);
+
+    // The PhantomData does not claim any bytes, so the struct decodes within a limit of exactly 4 bytes,
+    // and `decode_from_slice` checks in debug builds that every claim was released
+    let (decoded, read): (Tagged<u64>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config.with_limit::<4>()).unwrap();
+    assert_eq!(decoded, start);
+    assert_eq!(read, 4);
+
+    let (decoded, read): ((u32, PhantomData<u64>), usize) =
+        bincode::decode_from_slice(&buffer[..len], config.with_limit::<4>()).unwrap();
+    assert_eq!(decoded, (0x1234_5678, PhantomData));
+    assert_eq!(read, 4);
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct PartialUpdate {
+    id: u32,
+    #[bincode(optional)]
+    name: [u8; 2],
+    #[bincode(optional)]
+    x: i32,
+    #[bincode(optional)]
+    y: i32,
+    #[bincode(optional)]
+    visible: bool,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(version = 1)]
+struct PartialTuple<'a>(#[bincode(optional)] &'a str, u8);
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug, Default)]
+struct WidePartialUpdate {
+    #[bincode(optional)]
+    f0: u8,
+    #[bincode(optional)]
+    f1: u8,
+    #[bincode(optional)]
+    f2: u8,
+    #[bincode(optional)]
+    f3: u8,
+    #[bincode(optional)]
+    f4: u8,
+    #[bincode(optional)]
+    f5: u8,
+    #[bincode(optional)]
+    f6: u8,
+    #[bincode(optional)]
+    f7: u8,
+    #[bincode(optional)]
+    f8: u8,
+}
+
+// Like `VersionedGeneric`, only `T` must implement `Default`
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+struct PartialGeneric<T, U> {
+    #[bincode(optional)]
+    value: T,
+    other: U,
+}
+
+#[test]
+fn test_optional_generic_field() {
+    let config = bincode::config::standard();
+
+    let (decoded, _): (PartialGeneric<u32, NoDefault>, usize) =
+        bincode::decode_from_slice(&[0, 5], config).unwrap();
+    assert_eq!(
+        decoded,
+        PartialGeneric {
+            value: 0,
+            other: NoDefault(5)
+        }
+    );
+    let (decoded, _): (PartialGeneric<u32, NoDefault>, usize) =
+        bincode::decode_from_slice(&[1, 3, 5], config).unwrap();
+    assert_eq!(
+        decoded,
+        PartialGeneric {
+            value: 3,
+            other: NoDefault(5)
+        }
+    );
+}
+
+#[test]
+fn test_optional_fields() {
+    use bincode::error::DecodeError;
+    use bincode::{PartialEncode, PartialStruct};
+
+    let config = bincode::config::standard();
+    let update = PartialUpdate {
+        id: 7,
+        name: *b"ab",
+        x: 3,
+        y: -4,
+        visible: true,
+    };
+    assert_eq!(
+        PartialUpdate::optional_fields(),
+        ["name", "x", "y", "visible"]
+    );
+    assert_eq!(PartialUpdate::all_present(), 0b1111);
+
+    // Only `x` and `visible` are written, after the presence mask
+    let mut buffer = [0u8; 32];
+    let partial = PartialStruct::new(&update)
+        .with_field("x")
+        .with_field("visible");
+    assert_eq!(partial.present(), 0b1010);
+    let len = bincode::encode_into_slice(partial, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0b1010, 7, 6, 1]);
+    let checked = PartialStruct::new(&update)
+        .try_with_field("x")
+        .and_then(|partial| partial.try_with_field("visible"))
+        .unwrap();
+    assert_eq!(checked.present(), partial.present());
+    assert!(matches!(
+        PartialStruct::new(&update).try_with_field("id"),
+        Err(bincode::error::EncodeError::UnknownOptionalField {
+            type_name: "derive::PartialUpdate"
+        })
+    ));
+
+    // The presence mask of 4 optional fields is a `u8`, also with fixed size integers
+    let legacy = bincode::config::legacy();
+    let legacy_len = bincode::encode_into_slice(partial, &mut buffer, legacy).unwrap();
+    assert_eq!(buffer[..legacy_len], [0b1010, 7, 0, 0, 0, 3, 0, 0, 0, 1]);
+    let (decoded, _): (PartialUpdate, usize) =
+        bincode::decode_from_slice(&buffer[..legacy_len], legacy).unwrap();
+    assert_eq!(decoded.x, 3);
+
+    // With more than 8 optional fields, the presence mask is a `u16`
+    let wide = WidePartialUpdate {
+        f8: 9,
+        ..Default::default()
+    };
+    let wide_len = bincode::encode_into_slice(
+        PartialStruct::new(&wide).with_field("f8"),
+        &mut buffer,
+        legacy,
+    )
+    .unwrap();
+    assert_eq!(buffer[..wide_len], [0, 1, 9]);
+    let (decoded, _): (WidePartialUpdate, usize) =
+        bincode::decode_from_slice(&buffer[..wide_len], legacy).unwrap();
+    assert_eq!(decoded, wide);
+    let len = bincode::encode_into_slice(partial, &mut buffer, config).unwrap();
+
+    // The absent fields are decoded as their default value
+    let (decoded, read): (PartialUpdate, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(
+        decoded,
+        PartialUpdate {
+            id: 7,
+            name: [0, 0],
+            x: 3,
+            y: 0,
+            visible: true,
+        }
+    );
+
+    // The struct itself is encoded with every optional field
+    let len = bincode::encode_into_slice(&update, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [0b1111, 7, 2, b'a', b'b', 6, 7, 1]);
+    let (decoded, _): (PartialUpdate, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, update);
+    let all_len =
+        bincode::encode_into_slice(PartialStruct::all(&update), &mut buffer[len..], config)
+            .unwrap();
+    assert_eq!(buffer[..len], buffer[len..len + all_len]);
+
+    // Bits for fields that the struct does not have are rejected
+    let result: Result<(PartialUpdate, usize), _> =
+        bincode::decode_from_slice(&[0b10000, 7], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidPresenceMask {
+            type_name: "derive::PartialUpdate",
+            mask: 0b10000,
+        }
+    );
+
+    // The presence mask is written after the version, and works with borrowed fields
+    let tuple = PartialTuple("hi", 5);
+    let len = bincode::encode_into_slice(PartialStruct::new(&tuple), &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [1, 0, 5]);
+    let (decoded, _): (PartialTuple, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, PartialTuple("", 5));
+    let len = bincode::encode_into_slice(&tuple, &mut buffer, config).unwrap();
+    assert_eq!(buffer[..len], [1, 1, 2, b'h', b'i', 5]);
+    let (decoded, _): (PartialTuple, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, tuple);
+}
+
+#[test]
+#[should_panic(expected = "does not have an optional field called id")]
+fn test_partial_struct_unknown_field() {
+    let update = PartialUpdate {
+        id: 7,
+        name: *b"ab",
+        x: 3,
+        y: -4,
+        visible: true,
+    };
+    let _ = bincode::PartialStruct::new(&update).with_field("id");
+}
@@ -5,9 +5,69 @@ extern crate bincode as bincode_new;
 // Make sure that the `bincode` crate exists, just symlink it to `core.
 extern crate core as bincode;
 
+#[allow(dead_code)]
 #[derive(bincode_new::Encode)]
 #[bincode(crate = "bincode_new")]
 struct DeriveRenameTest {
     a: u32,
     b: u32,
 }
+
+#[derive(bincode_new::Encode, bincode_new::Decode, PartialEq, Debug)]
+#[bincode(crate = "bincode_new")]
+struct DeriveRenameGeneric<T> {
+    a: T,
+    b: (u8, u16),
+}
+
+#[derive(bincode_new::Encode, bincode_new::BorrowDecode, PartialEq, Debug)]
+#[bincode(crate = "bincode_new")]
+struct DeriveRenameBorrowed<'a> {
+    a: &'a str,
+    b: &'a [u8],
+}
+
+#[derive(bincode_new::Encode, bincode_new::Decode, PartialEq, Debug)]
+#[bincode(crate = "bincode_new")]
+enum DeriveRenameEnum {
+    A,
+    B(u32),
+    C { a: u8, b: DeriveRenameGeneric<u64> },
+}
+
+#[derive(bincode_new::Encode, bincode_new::BorrowDecode, PartialEq, Debug)]
+#[bincode(crate = "bincode_new")]
+enum DeriveRenameBorrowedEnum<'a> {
+    A(&'a str),
+    B { b: &'a [u8] },
+}
+
+#[test]
+fn test_derive_rename() {
+    let config = bincode_new::config::standard();
+    let mut buffer = [0u8; 32];
+
+    let input = DeriveRenameEnum::C {
+        a: 5,
+        b: DeriveRenameGeneric { a: 10, b: (1, 2) },
+    };
+    let len = bincode_new::encode_into_slice(&input, &mut buffer, config).unwrap();
+    let (output, _): (DeriveRenameEnum, usize) =
+        bincode_new::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(input, output);
+
+    let input = DeriveRenameBorrowed {
+        a: "Hello",
+        b: &[1, 2, 3],
+    };
+    let len = bincode_new::encode_into_slice(&input, &mut buffer, config).unwrap();
+    let (output, _): (DeriveRenameBorrowed, usize) =
+        bincode_new::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(input, output);
+
+    let input = DeriveRenameBorrowedEnum::B { b: &[4, 5] };
+    let len = bincode_new::encode_into_slice(&input, &mut buffer, config).unwrap();
+    let (output, _): (DeriveRenameBorrowedEnum, usize) =
+        bincode_new::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(input, output);
+}
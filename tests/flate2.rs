@@ -0,0 +1,75 @@
+#![cfg(feature = "flate2")]
+
+use bincode::{
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+
+#[derive(PartialEq, Debug)]
+struct Log {
+    name: String,
+    lines: Vec<String>,
+}
+
+impl Encode for Log {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.name.encode(encoder)?;
+        self.lines.encode(encoder)
+    }
+}
+
+impl Decode for Log {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            name: String::decode(decoder)?,
+            lines: Vec::decode(decoder)?,
+        })
+    }
+}
+
+#[test]
+fn test_gzip_roundtrip() {
+    let config = bincode::config::standard();
+    let log = Log {
+        name: String::from("server"),
+        lines: (0..200)
+            .map(|i| format!("request {} handled in 3ms", i % 10))
+            .collect(),
+    };
+
+    let uncompressed = bincode::encode_to_vec(&log, config).unwrap();
+    let compressed = bincode::encode_to_vec_gzip(&log, config).unwrap();
+    assert!(compressed.len() < uncompressed.len() / 4);
+    // The gzip magic bytes
+    assert_eq!(compressed[..2], [0x1f, 0x8b]);
+
+    let decoded: Log = bincode::decode_from_slice_gzip(&compressed, config).unwrap();
+    assert_eq!(decoded, log);
+
+    // Decompressing with flate2 itself gives the uncompressed encoding
+    let mut decompressed = Vec::new();
+    std::io::Read::read_to_end(
+        &mut flate2::read::GzDecoder::new(&compressed[..]),
+        &mut decompressed,
+    )
+    .unwrap();
+    assert_eq!(decompressed, uncompressed);
+}
+
+#[test]
+fn test_gzip_invalid_input() {
+    let config = bincode::config::standard();
+
+    // Bytes that are not compressed can not be decoded
+    let uncompressed = bincode::encode_to_vec(5u32, config).unwrap();
+    let result: Result<u32, _> = bincode::decode_from_slice_gzip(&uncompressed, config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+
+    // The compressed value is cut off
+    let compressed = bincode::encode_to_vec_gzip(vec![1u64; 100], config).unwrap();
+    let result: Result<Vec<u64>, _> =
+        bincode::decode_from_slice_gzip(&compressed[..compressed.len() / 2], config);
+    assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })));
+}
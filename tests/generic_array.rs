@@ -0,0 +1,34 @@
+#![cfg(all(feature = "generic-array", feature = "alloc"))]
+
+mod utils;
+
+use bincode::error::DecodeError;
+use generic_array::{typenum::U32, typenum::U4, GenericArray};
+
+#[test]
+fn test_generic_array() {
+    let config = bincode::config::standard();
+
+    let key: GenericArray<u8, U32> = GenericArray::from_array([7u8; 32]);
+    let bytes = bincode::encode_to_vec(key, config).unwrap();
+    // No length prefix
+    assert_eq!(bytes, [7u8; 32]);
+    utils::assert_roundtrip(key, config);
+
+    let words: GenericArray<String, U4> = GenericArray::from_array([
+        String::from("a"),
+        String::from("b"),
+        String::from("c"),
+        String::from("d"),
+    ]);
+    utils::assert_roundtrip(words.clone(), config);
+
+    // Elements that were already decoded are dropped when decoding fails
+    let bytes = bincode::encode_to_vec(&words, config).unwrap();
+    let result: Result<(GenericArray<String, U4>, usize), _> =
+        bincode::decode_from_slice(&bytes[..5], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+}
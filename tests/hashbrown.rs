@@ -0,0 +1,114 @@
+#![cfg(feature = "hashbrown")]
+
+extern crate alloc;
+
+mod utils;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use bincode::de::read::Reader;
+use bincode::error::DecodeError;
+use core::hash::{BuildHasherDefault, Hasher};
+use utils::assert_roundtrip;
+
+/// A simple FNV-1a hasher, so these tests do not depend on `std` or the default hasher of `hashbrown`.
+#[derive(Default)]
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        if self.0 == 0 {
+            self.0 = 0xcbf2_9ce4_8422_2325;
+        }
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+type HashMap<K, V> = hashbrown::HashMap<K, V, BuildHasherDefault<FnvHasher>>;
+type HashSet<T> = hashbrown::HashSet<T, BuildHasherDefault<FnvHasher>>;
+
+#[test]
+fn test_hashbrown_map() {
+    let config = bincode::config::standard();
+
+    let mut map = HashMap::default();
+    map.insert(1u32, String::from("one"));
+    map.insert(2, String::from("two"));
+    map.insert(300, String::from("three hundred"));
+    assert_roundtrip(map.clone(), config);
+    assert_roundtrip(map.clone(), bincode::config::legacy());
+    assert_roundtrip(HashMap::<u8, u8>::default(), config);
+
+    // Encoded the same as a list of entries with a length
+    let bytes = bincode::encode_to_vec(&map, config).unwrap();
+    let (entries, _): (Vec<(u32, String)>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(entries.len(), 3);
+    for (key, value) in entries {
+        assert_eq!(map.get(&key), Some(&value));
+    }
+}
+
+#[test]
+fn test_hashbrown_set() {
+    let config = bincode::config::standard();
+
+    let set: HashSet<i64> = [-5, 0, 7, 1 << 40].into_iter().collect();
+    assert_roundtrip(set.clone(), config);
+    assert_roundtrip(set.clone(), bincode::config::legacy());
+
+    let bytes = bincode::encode_to_vec(&set, config).unwrap();
+    let (items, _): (Vec<i64>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(items.len(), 4);
+    assert!(items.iter().all(|item| set.contains(item)));
+}
+
+#[test]
+fn test_hashbrown_out_of_memory() {
+    /// A reader that does not know how many bytes are left, so the reservation is not bounded by the size of the input.
+    struct UnboundedReader<'a>(&'a [u8]);
+
+    impl Reader for UnboundedReader<'_> {
+        fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+            if bytes.len() > self.0.len() {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: bytes.len() - self.0.len(),
+                });
+            }
+            let (head, tail) = self.0.split_at(bytes.len());
+            bytes.copy_from_slice(head);
+            self.0 = tail;
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let bytes = bincode::encode_to_vec(u64::MAX / 2, config).unwrap();
+
+    let result: Result<HashMap<u64, u64>, _> =
+        bincode::decode_from_reader(UnboundedReader(&bytes), config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::HashbrownOutOfMemory { .. }
+    ));
+
+    let result: Result<HashSet<u64>, _> =
+        bincode::decode_from_reader(UnboundedReader(&bytes), config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::HashbrownOutOfMemory { .. }
+    ));
+
+    // Slices know their length, so only the entries that can fit in the input are reserved
+    let result: Result<(HashMap<u64, u64>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { .. }
+    ));
+}
@@ -5,6 +5,7 @@
 /// # Remarks
 /// Used to store HID-IO data chunks. Will be chunked into individual packets on transmission.
 #[repr(C)]
+#[allow(dead_code)]
 #[derive(PartialEq, Clone, Debug, bincode::Encode)]
 pub struct HidIoPacketBuffer<const H: usize> {
     /// Type of packet (Continued is automatically set if needed)
@@ -65,10 +65,10 @@ impl MemCache {
         let config = bincode::config::standard();
         let mut guard = self.cache.write().unwrap();
 
-        let encoded = bincode::serde::encode_to_vec(&cache_data, config)?;
+        let encoded = bincode::serde::encode_to_vec(cache_data, config)?;
         let cache_item = CacheItem::new(encoded, expire_seconds);
 
-        guard.insert(key.clone(), cache_item);
+        guard.insert(*key, cache_item);
         Ok(())
     }
 
@@ -5,5 +5,6 @@ extern crate std;
 use bincode::{Decode, Encode};
 use std::borrow::Cow;
 
+#[allow(dead_code)]
 #[derive(Clone, Encode, Decode)]
 pub struct Foo<'a>(Cow<'a, str>);
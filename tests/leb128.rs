@@ -0,0 +1,103 @@
+use bincode::{
+    de::read::SliceReader,
+    enc::write::SliceWriter,
+    error::DecodeError,
+    leb128::{decode_i64, decode_u64, encode_i64, encode_u64},
+};
+
+fn encode_unsigned(value: u64) -> Vec<u8> {
+    let mut buffer = [0u8; 16];
+    let mut writer = SliceWriter::new(&mut buffer);
+    encode_u64(&mut writer, value).unwrap();
+    let len = writer.bytes_written();
+    buffer[..len].to_vec()
+}
+
+fn encode_signed(value: i64) -> Vec<u8> {
+    let mut buffer = [0u8; 16];
+    let mut writer = SliceWriter::new(&mut buffer);
+    encode_i64(&mut writer, value).unwrap();
+    let len = writer.bytes_written();
+    buffer[..len].to_vec()
+}
+
+#[test]
+fn test_leb128_unsigned() {
+    // Examples from the DWARF 5 specification, section 7.6, and from wikipedia
+    let cases: &[(u64, &[u8])] = &[
+        (0, &[0x00]),
+        (2, &[0x02]),
+        (127, &[0x7F]),
+        (128, &[0x80, 0x01]),
+        (129, &[0x81, 0x01]),
+        (130, &[0x82, 0x01]),
+        (12857, &[0xB9, 0x64]),
+        (624_485, &[0xE5, 0x8E, 0x26]),
+        (
+            u64::MAX,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+    ];
+
+    for &(value, bytes) in cases {
+        assert_eq!(encode_unsigned(value), bytes, "encoding {}", value);
+        let mut reader = SliceReader::new(bytes);
+        assert_eq!(decode_u64(&mut reader).unwrap(), value);
+    }
+
+    // Non-canonical encodings with padding are accepted
+    assert_eq!(
+        decode_u64(&mut SliceReader::new(&[0x82, 0x80, 0x00])),
+        Ok(2)
+    );
+}
+
+#[test]
+fn test_leb128_signed() {
+    let cases: &[(i64, &[u8])] = &[
+        (0, &[0x00]),
+        (-1, &[0x01]),
+        (1, &[0x02]),
+        (-2, &[0x03]),
+        (63, &[0x7E]),
+        (-64, &[0x7F]),
+        (64, &[0x80, 0x01]),
+        (
+            i64::MAX,
+            &[0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+        (
+            i64::MIN,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+    ];
+
+    for &(value, bytes) in cases {
+        assert_eq!(encode_signed(value), bytes, "encoding {}", value);
+        let mut reader = SliceReader::new(bytes);
+        assert_eq!(decode_i64(&mut reader).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_leb128_invalid() {
+    // More than 64 bits
+    let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+    assert_eq!(
+        decode_u64(&mut SliceReader::new(&bytes)),
+        Err(DecodeError::Leb128Overflow)
+    );
+
+    // More than 10 bytes
+    let bytes = [0x80; 11];
+    assert_eq!(
+        decode_u64(&mut SliceReader::new(&bytes)),
+        Err(DecodeError::Leb128Overflow)
+    );
+
+    // The continuation bit is set on the last byte
+    assert_eq!(
+        decode_u64(&mut SliceReader::new(&[0x80, 0x80])),
+        Err(DecodeError::UnexpectedEnd { additional: 1 })
+    );
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "ndarray")]
+
+mod utils;
+
+use bincode::error::DecodeError;
+use ndarray::{arr2, arr3, Array, Array2, Array3, ArrayD, Axis, IxDyn};
+use utils::assert_roundtrip;
+
+#[test]
+fn test_array2() {
+    let config = bincode::config::standard();
+
+    let array = arr2(&[[1.5f64, 2.0, 3.0], [4.0, 5.0, 6.25]]);
+    assert_roundtrip(array.clone(), config);
+    assert_roundtrip(array.clone(), bincode::config::legacy());
+
+    // The shape, followed by the items in row-major order
+    let bytes = bincode::encode_to_vec(&array, config).unwrap();
+    let mut expected = bincode::encode_to_vec(vec![2usize, 3], config).unwrap();
+    expected.extend(bincode::encode_to_vec((1.5f64, 2.0f64, 3.0f64), config).unwrap());
+    expected.extend(bincode::encode_to_vec((4.0f64, 5.0f64, 6.25f64), config).unwrap());
+    assert_eq!(bytes, expected);
+
+    assert_roundtrip(Array2::<f64>::zeros((0, 4)), config);
+}
+
+#[test]
+fn test_array3() {
+    let config = bincode::config::standard();
+
+    let array = arr3(&[[[1u32, 2], [3, 4], [5, 6]], [[7, 8], [9, 10], [11, 12]]]);
+    assert_eq!(array.shape(), &[2, 3, 2]);
+    assert_roundtrip(array.clone(), config);
+
+    // A dynamic dimension can be decoded from any shape
+    let bytes = bincode::encode_to_vec(&array, config).unwrap();
+    let (decoded, len): (ArrayD<u32>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, array.clone().into_dyn());
+    assert_eq!(decoded.raw_dim(), IxDyn(&[2, 3, 2]));
+    assert_eq!(len, bytes.len());
+
+    // A fixed dimension has to match the shape
+    let result: Result<(Array2<u32>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::ArrayLengthMismatch {
+            required: 2,
+            found: 3
+        }
+    );
+}
+
+#[test]
+fn test_non_contiguous() {
+    let config = bincode::config::standard();
+
+    let array = Array::from_iter(0u16..24)
+        .into_shape_with_order((2, 3, 4))
+        .unwrap();
+
+    // A transposed view is encoded the same as a contiguous copy of it
+    let transposed = array.t();
+    assert!(!transposed.is_standard_layout());
+    let bytes = bincode::encode_to_vec(transposed, config).unwrap();
+    let contiguous = transposed.as_standard_layout().into_owned();
+    assert_eq!(bytes, bincode::encode_to_vec(&contiguous, config).unwrap());
+    let (decoded, _): (Array3<u16>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, transposed);
+    assert_eq!(decoded.shape(), &[4, 3, 2]);
+
+    // As is a strided slice
+    let view = array.slice(ndarray::s![.., ..;2, 1..]);
+    let view = view.index_axis(Axis(0), 1);
+    let bytes = bincode::encode_to_vec(view, config).unwrap();
+    let (decoded, _): (Array2<u16>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, view);
+    assert_eq!(decoded, arr2(&[[13, 14, 15], [21, 22, 23]]));
+}
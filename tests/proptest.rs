@@ -0,0 +1,100 @@
+#![cfg(feature = "alloc")]
+
+extern crate alloc;
+
+mod utils;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use proptest::prelude::*;
+use utils::the_same;
+
+/// Asserts that decoding any strict prefix of the encoded `value` fails with `DecodeError::UnexpectedEnd`, instead of panicking or decoding a different value.
+fn assert_prefixes_are_unexpected_end<V>(value: &V)
+where
+    V: bincode::Encode + bincode::Decode + Debug,
+{
+    fn check<V, C>(value: &V, config: C)
+    where
+        V: bincode::Encode + bincode::Decode + Debug,
+        C: bincode::config::Config,
+    {
+        let bytes = bincode::encode_to_vec(value, config).unwrap();
+        for len in 0..bytes.len() {
+            let result: Result<(V, usize), _> = bincode::decode_from_slice(&bytes[..len], config);
+            assert!(
+                matches!(
+                    result,
+                    Err(bincode::error::DecodeError::UnexpectedEnd { .. })
+                ),
+                "Decoding {} of {} bytes did not fail with UnexpectedEnd\nResult: {:?}\nBytes: {:?}",
+                len,
+                bytes.len(),
+                result,
+                bytes,
+            );
+        }
+    }
+    check(value, bincode::config::standard());
+    check(value, bincode::config::legacy());
+}
+
+proptest! {
+    #[test]
+    fn proptest_primitives(value in any::<(u8, i16, u32, i64, u128, bool, char)>()) {
+        the_same(value);
+        assert_prefixes_are_unexpected_end(&value);
+    }
+
+    #[test]
+    fn proptest_string(value in any::<String>()) {
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+
+    #[test]
+    fn proptest_option(value in any::<Option<(u64, Option<i8>)>>()) {
+        the_same(value);
+        assert_prefixes_are_unexpected_end(&value);
+    }
+
+    #[test]
+    fn proptest_vec(value in prop::collection::vec(any::<u32>(), 0..64)) {
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+
+    #[test]
+    fn proptest_byte_vec(value in prop::collection::vec(any::<u8>(), 0..256)) {
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+
+    #[test]
+    fn proptest_btree_map(value in prop::collection::btree_map(any::<i32>(), "[a-z]{0,8}", 0..16)) {
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+
+    #[test]
+    fn proptest_nested(value in prop::collection::vec(
+        (any::<u16>(), any::<Option<String>>(), prop::collection::vec(any::<i64>(), 0..4)),
+        0..8,
+    )) {
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+
+    #[test]
+    fn proptest_map_of_vecs(value in prop::collection::btree_map(
+        any::<u8>(),
+        prop::collection::vec(any::<Option<bool>>(), 0..8),
+        0..8,
+    )) {
+        let value: BTreeMap<u8, Vec<Option<bool>>> = value;
+        assert_prefixes_are_unexpected_end(&value);
+        the_same(value);
+    }
+}
@@ -128,6 +128,92 @@ fn test_serialize_deserialize_owned_data() {
     assert_eq!(len, 13);
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "serde_incl")]
+pub struct SerdeOnly {
+    pub id: u32,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub parent: Option<u64>,
+}
+
+#[test]
+fn test_serde_compat() {
+    use bincode::serde::Compat;
+
+    let input = SerdeOnly {
+        id: 300,
+        name: String::from("leaf"),
+        tags: vec![String::from("a"), String::from("bc")],
+        parent: Some(7),
+    };
+
+    #[rustfmt::skip]
+    let expected = &[
+        251, 44, 1, // id
+        4, b'l', b'e', b'a', b'f', // name
+        2, 1, b'a', 2, b'b', b'c', // tags
+        1, 7, // parent
+    ];
+
+    // `Compat` implements `Encode` and `Decode`, so it can be used with bincode's own functions
+    let bytes = bincode::encode_to_vec(Compat(&input), bincode::config::standard()).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(
+        bytes,
+        bincode::serde::encode_to_vec(&input, bincode::config::standard()).unwrap()
+    );
+
+    let (Compat(output), len): (Compat<SerdeOnly>, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(len, bytes.len());
+
+    // And inside of other types that implement `Encode` and `Decode`
+    let list = vec![Compat(input)];
+    let bytes = bincode::encode_to_vec(&list, bincode::config::standard()).unwrap();
+    let (output, _): (Vec<Compat<SerdeOnly>>, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+    assert_eq!(output[0].0, list[0].0);
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "serde_incl")]
+pub struct SerdeBorrowed<'a> {
+    pub id: u32,
+    #[serde(borrow)]
+    pub name: &'a str,
+    #[serde(borrow)]
+    pub data: &'a [u8],
+}
+
+#[test]
+fn test_serde_borrow_compat() {
+    use bincode::serde::BorrowCompat;
+
+    let input = SerdeBorrowed {
+        id: 3,
+        name: "borrowed",
+        data: &[1, 2, 3],
+    };
+    let bytes = bincode::encode_to_vec(BorrowCompat(&input), bincode::config::standard()).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::serde::encode_to_vec(&input, bincode::config::standard()).unwrap()
+    );
+
+    let (BorrowCompat(output), len): (BorrowCompat<SerdeBorrowed>, usize) =
+        bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+    assert_eq!(output, input);
+    assert_eq!(len, bytes.len());
+
+    // The borrowed fields point into `bytes` instead of being copied
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&output.name.as_ptr()));
+    assert_eq!(&bytes[2..10], output.name.as_bytes());
+    assert!(range.contains(&output.data.as_ptr()));
+}
+
 #[cfg(feature = "derive")]
 mod derive {
     use bincode::{Decode, Encode};
@@ -166,7 +252,7 @@ mod derive {
             assert_eq!(len, expected_len);
             let slice = &slice[..len];
             let (result, len): (T, usize) =
-                bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
+                bincode::decode_from_slice(slice, bincode::config::standard()).unwrap();
 
             assert_eq!(start, result);
             assert_eq!(len, expected_len);
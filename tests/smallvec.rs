@@ -0,0 +1,80 @@
+#![cfg(feature = "smallvec")]
+
+mod utils;
+
+use bincode::de::{read::SliceReader, DecoderImpl};
+use bincode::error::DecodeError;
+use smallvec::{smallvec, SmallVec};
+use utils::assert_roundtrip;
+
+#[test]
+fn test_decode_smallvec_inline() {
+    let config = bincode::config::standard();
+
+    let bytes = bincode::encode_to_vec(vec![1u16, 2, 300], config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    let items = bincode::decode_smallvec::<u16, 4>(&mut decoder).unwrap();
+    assert_eq!(items.as_slice(), [1, 2, 300]);
+    assert!(!items.spilled());
+
+    // Exactly `N` items still fit inline
+    let bytes = bincode::encode_to_vec(vec![String::from("a"), String::from("b")], config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    let items = bincode::decode_smallvec::<String, 2>(&mut decoder).unwrap();
+    assert_eq!(items.as_slice(), ["a", "b"]);
+    assert!(!items.spilled());
+
+    // Inline items do not allocate, so they can be decoded without allocation
+    let bytes = bincode::encode_to_vec(vec![1u16, 2, 300], config).unwrap();
+    let no_allocation = config.with_no_allocation();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), no_allocation);
+    let items = bincode::decode_smallvec::<u16, 4>(&mut decoder).unwrap();
+    assert_eq!(items.as_slice(), [1, 2, 300]);
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), no_allocation);
+    assert!(matches!(
+        bincode::decode_smallvec::<u16, 2>(&mut decoder).unwrap_err(),
+        DecodeError::AllocationDisallowed { .. }
+    ));
+
+    let bytes = bincode::encode_to_vec(Vec::<u16>::new(), config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    let items = bincode::decode_smallvec::<u16, 4>(&mut decoder).unwrap();
+    assert!(items.is_empty());
+    assert!(!items.spilled());
+}
+
+#[test]
+fn test_decode_smallvec_spilled() {
+    let config = bincode::config::standard();
+
+    let vec: Vec<u32> = (0..100).collect();
+    let bytes = bincode::encode_to_vec(&vec, config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config);
+    let items = bincode::decode_smallvec::<u32, 4>(&mut decoder).unwrap();
+    assert_eq!(items.as_slice(), vec.as_slice());
+    assert!(items.spilled());
+
+    // A length that is larger than the input is rejected by the limit, before anything is allocated
+    let bytes = bincode::encode_to_vec(u64::MAX, config).unwrap();
+    let mut decoder = DecoderImpl::new(SliceReader::new(&bytes), config.with_limit::<64>());
+    let result = bincode::decode_smallvec::<u32, 4>(&mut decoder);
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+}
+
+#[test]
+fn test_smallvec() {
+    let config = bincode::config::standard();
+
+    let inline: SmallVec<[u8; 4]> = smallvec![1, 2, 3];
+    // Encoded the same as a `Vec`
+    let bytes = bincode::encode_to_vec(&inline, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::encode_to_vec(inline.to_vec(), config).unwrap()
+    );
+    assert_roundtrip(inline, config);
+
+    let spilled: SmallVec<[String; 1]> = smallvec![String::from("a"), String::from("b")];
+    assert!(spilled.spilled());
+    assert_roundtrip(spilled, config);
+}
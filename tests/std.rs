@@ -3,13 +3,13 @@
 mod utils;
 
 use std::{
-    ffi::{CStr, CString},
+    ffi::CString,
     io::{Cursor, Seek, SeekFrom},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     path::{Path, PathBuf},
     sync::{Mutex, RwLock},
 };
-use utils::the_same;
+use utils::{assert_roundtrip, the_same};
 
 use crate::utils::the_same_with_comparer;
 
@@ -89,10 +89,10 @@ fn test_std_commons() {
         0,
     )));
     the_same_with_comparer(Mutex::new("Hello world".to_string()), |a, b| {
-        &*a.lock().unwrap() == &*b.lock().unwrap()
+        *a.lock().unwrap() == *b.lock().unwrap()
     });
     the_same_with_comparer(RwLock::new("Hello world".to_string()), |a, b| {
-        &*a.read().unwrap() == &*b.read().unwrap()
+        *a.read().unwrap() == *b.read().unwrap()
     });
 
     let mut map = std::collections::HashMap::new();
@@ -125,10 +125,10 @@ fn test_std_commons() {
     let mut buffer = [0u8; 1024];
 
     // &CStr
-    let cstr = CStr::from_bytes_with_nul(b"Hello world\0").unwrap();
+    let cstr = c"Hello world";
     let len = bincode::encode_into_slice(cstr, &mut buffer, config).unwrap();
     let (decoded, len): (CString, usize) =
-        bincode::decode_from_slice(&mut buffer[..len], config).unwrap();
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
     assert_eq!(cstr, decoded.as_c_str());
     assert_eq!(len, 12);
 
@@ -136,17 +136,19 @@ fn test_std_commons() {
     let path = Path::new("C:/Program Files/Foo");
     let len = bincode::encode_into_slice(path, &mut buffer, config).unwrap();
     let (decoded, len): (&Path, usize) =
-        bincode::decode_from_slice(&mut buffer[..len], config).unwrap();
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
     assert_eq!(path, decoded);
     assert_eq!(len, 21);
 }
 
 #[test]
 fn test_system_time_out_of_range() {
-    let mut input = [0xfd, 0x90, 0x0c, 0xfd, 0xfd, 0x90, 0x0c, 0xfd, 0x90, 0x90];
+    let input = [
+        0, 0xfd, 0x90, 0x0c, 0xfd, 0xfd, 0x90, 0x0c, 0xfd, 0x90, 0x90,
+    ];
 
     let result: Result<(std::time::SystemTime, usize), _> =
-        bincode::decode_from_slice(&mut input, bincode::config::standard());
+        bincode::decode_from_slice(&input, bincode::config::standard());
 
     assert_eq!(
         result.unwrap_err(),
@@ -154,6 +156,89 @@ fn test_system_time_out_of_range() {
             duration: std::time::Duration::new(10447520527445462160, 144),
         }
     );
+
+    let input = [2, 0, 0];
+    let result: Result<(std::time::SystemTime, usize), _> =
+        bincode::decode_from_slice(&input, bincode::config::standard());
+    assert!(matches!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant { found: 2, .. }
+    ));
+}
+
+#[test]
+fn test_system_time() {
+    use std::time::{Duration, SystemTime};
+
+    let config = bincode::config::standard();
+
+    // The sign byte makes the encoding differ from serde's, so `the_same` can't be used.
+    // 1901-12-13, before `UNIX_EPOCH`
+    let before_epoch = SystemTime::UNIX_EPOCH - Duration::new(2_147_483_648, 123_456_789);
+    assert_roundtrip(before_epoch, config);
+    assert_roundtrip(before_epoch, bincode::config::legacy());
+    let bytes = bincode::encode_to_vec(before_epoch, config).unwrap();
+    assert_eq!(bytes[0], 1);
+
+    // 2106-02-07, after 2100
+    let after_2100 = SystemTime::UNIX_EPOCH + Duration::new(4_294_967_296, 987_654_321);
+    assert_roundtrip(after_2100, config);
+    assert_roundtrip(after_2100, bincode::config::legacy());
+    let bytes = bincode::encode_to_vec(after_2100, config).unwrap();
+    assert_eq!(bytes[0], 0);
+
+    assert_roundtrip(SystemTime::UNIX_EPOCH, config);
+    assert_roundtrip(SystemTime::UNIX_EPOCH - Duration::from_nanos(1), config);
+    assert_roundtrip(SystemTime::now(), config);
+}
+
+#[test]
+fn test_system_time_nanos() {
+    use bincode::SystemTimeNanos;
+    use std::time::{Duration, SystemTime};
+
+    fn check(time: SystemTime, nanos: i128) {
+        let wrapped = SystemTimeNanos(time);
+        assert_eq!(wrapped.to_nanos(), nanos);
+        let bytes = bincode::encode_to_vec(wrapped, bincode::config::legacy()).unwrap();
+        assert_eq!(bytes, nanos.to_le_bytes());
+        let (decoded, len): (SystemTimeNanos, usize) =
+            bincode::decode_from_slice(&bytes, bincode::config::legacy()).unwrap();
+        assert_eq!(decoded.0, time);
+        assert_eq!(len, 16);
+
+        let bytes = bincode::encode_to_vec(wrapped, bincode::config::standard()).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::encode_to_vec(nanos, bincode::config::standard()).unwrap()
+        );
+        let (decoded, _): (SystemTimeNanos, usize) =
+            bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+        assert_eq!(decoded.0, time);
+    }
+
+    check(SystemTime::UNIX_EPOCH, 0);
+    // 5138-11-16, far in the future
+    check(
+        SystemTime::UNIX_EPOCH + Duration::new(100_000_000_000, 123_456_789),
+        100_000_000_000_123_456_789,
+    );
+    // 1901-12-13, before `UNIX_EPOCH`
+    check(
+        SystemTime::UNIX_EPOCH - Duration::new(2_147_483_648, 123_456_789),
+        -2_147_483_648_123_456_789,
+    );
+    check(SystemTime::UNIX_EPOCH - Duration::from_nanos(1), -1);
+
+    // More seconds than fit in a `Duration`
+    let nanos = i128::MAX;
+    let bytes = bincode::encode_to_vec(nanos, bincode::config::standard()).unwrap();
+    let result: Result<(SystemTimeNanos, usize), _> =
+        bincode::decode_from_slice(&bytes, bincode::config::standard());
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::InvalidSystemTimeNanos { nanos }
+    );
 }
 
 /// Simple example of user-defined hasher to test encoding/decoding HashMap and HashSet with custom hash algorithms.
@@ -173,3 +258,436 @@ impl std::hash::Hasher for ExampleCustomHasher {
         self.hash
     }
 }
+
+#[test]
+fn test_deterministic_map() {
+    use bincode::DeterministicMap;
+    use std::collections::HashMap;
+
+    let config = bincode::config::standard();
+
+    let mut first = HashMap::new();
+    let mut second = HashMap::new();
+    for i in 0..100u32 {
+        first.insert(i, i.to_string());
+        second.insert(99 - i, (99 - i).to_string());
+    }
+
+    let first_bytes = bincode::encode_to_vec(DeterministicMap(first.clone()), config).unwrap();
+    let second_bytes = bincode::encode_to_vec(DeterministicMap(second), config).unwrap();
+    assert_eq!(first_bytes, second_bytes);
+
+    let (decoded, len): (DeterministicMap<u32, String>, usize) =
+        bincode::decode_from_slice(&first_bytes, config).unwrap();
+    assert_eq!(decoded.0, first);
+    assert_eq!(len, first_bytes.len());
+
+    // The wire format is the same as a regular `HashMap`
+    let (decoded, _): (HashMap<u32, String>, usize) =
+        bincode::decode_from_slice(&first_bytes, config).unwrap();
+    assert_eq!(decoded, first);
+
+    // The entries are sorted by their keys, not by the encoded bytes of their keys
+    let mut map = HashMap::new();
+    map.insert(512u32, ());
+    map.insert(511u32, ());
+    let bytes = bincode::encode_to_vec(DeterministicMap(map), config).unwrap();
+    assert_eq!(bytes, [2, 251, 255, 1, 251, 0, 2]);
+
+    // The keys are not padded with an aligned config
+    let aligned = config.with_alignment::<8>();
+    let mut map = HashMap::new();
+    map.insert(1u8, 2u8);
+    let bytes = bincode::encode_to_vec(DeterministicMap(map.clone()), aligned).unwrap();
+    assert_eq!(bytes, [1, 1, 2, 4, 0, 0, 0, 0]);
+    let (decoded, _): (HashMap<u8, u8>, usize) =
+        bincode::decode_from_slice(&bytes, aligned).unwrap();
+    assert_eq!(decoded, map);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str() {
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let config = bincode::config::standard();
+
+    // serde encodes `OsString` as an enum, so we can't use `the_same` here
+    let owned = OsString::from("Hello world");
+    let bytes = bincode::encode_to_vec(&owned, config).unwrap();
+    let (decoded, _): (OsString, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, owned);
+
+    let input = OsStr::from_bytes(b"not \xFF utf8");
+    let bytes = bincode::encode_to_vec(input, config).unwrap();
+
+    let (decoded, len): (&OsStr, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, input);
+    assert_eq!(len, bytes.len());
+    // The decoded value points into the input buffer
+    assert_eq!(decoded.as_bytes().as_ptr(), bytes[1..].as_ptr());
+
+    // A windows path with an unpaired surrogate is encoded as WTF-8, which unix reads as those bytes
+    let (decoded, _): (OsString, usize) =
+        bincode::decode_from_slice(&[4, b'a', 0xED, 0xA0, 0x80], config).unwrap();
+    assert_eq!(decoded.as_bytes(), b"a\xED\xA0\x80");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let config = bincode::config::standard();
+    let path = Path::new(OsStr::from_bytes(b"/tmp/not \xFF utf8"));
+    assert!(path.to_str().is_none());
+
+    // A borrowed path is encoded as the bytes of its `OsStr`, the same as a `PathBuf`
+    let bytes = bincode::encode_to_vec(path, config).unwrap();
+    assert_eq!(bytes[0], 15);
+    assert_eq!(bytes[1..], *path.as_os_str().as_bytes());
+    assert_eq!(
+        bincode::encode_to_vec(path.to_path_buf(), config).unwrap(),
+        bytes
+    );
+
+    let (decoded, len): (PathBuf, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, path);
+    assert_eq!(len, bytes.len());
+
+    let (decoded, _): (&Path, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, path);
+}
+
+#[cfg(windows)]
+#[test]
+fn test_os_str() {
+    use bincode::error::DecodeError;
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+    let config = bincode::config::standard();
+
+    // Valid unicode is encoded the same as on unix
+    let owned = OsString::from("Hello wörld");
+    let bytes = bincode::encode_to_vec(&owned, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::encode_to_vec("Hello wörld", config).unwrap()
+    );
+    let (decoded, _): (OsString, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, owned);
+
+    // Unpaired surrogates round-trip
+    for wide in [
+        &[b'a' as u16, 0xD800, b'b' as u16][..],
+        &[0xDC00],
+        &[0xDFFF, 0xD800],
+        &[0xD83D, 0xDE00, 0xDC00],
+    ] {
+        let path = OsString::from_wide(wide);
+        let bytes = bincode::encode_to_vec(path.as_os_str(), config).unwrap();
+        let (decoded, len): (OsString, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded.encode_wide().collect::<Vec<u16>>(), wide);
+        assert_eq!(len, bytes.len());
+    }
+    let bytes =
+        bincode::encode_to_vec(OsString::from_wide(&[b'a' as u16, 0xD800]), config).unwrap();
+    assert_eq!(bytes, [4, b'a', 0xED, 0xA0, 0x80]);
+
+    // Bytes from unix that are not valid WTF-8 are rejected
+    let result: Result<(OsString, usize), _> =
+        bincode::decode_from_slice(&[5, b'n', b'o', 0xFF, b'p', b'e'], config);
+    assert_eq!(result.unwrap_err(), DecodeError::InvalidWtf8 { index: 2 });
+    // A surrogate pair has to be encoded as a single code point
+    let result: Result<(OsString, usize), _> =
+        bincode::decode_from_slice(&[6, 0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80], config);
+    assert_eq!(result.unwrap_err(), DecodeError::InvalidWtf8 { index: 3 });
+}
+
+#[test]
+fn test_hashmap_out_of_memory() {
+    use bincode::error::DecodeError;
+    use std::collections::{HashMap, HashSet};
+
+    // A length prefix that can never be allocated. This is read with `std::io::Read`, which does not know
+    // how many bytes are left, so the reservation is not bounded by the size of the input.
+    let bytes = bincode::encode_to_vec(u64::MAX / 2, bincode::config::standard()).unwrap();
+
+    let result: Result<HashMap<u64, u64>, _> =
+        bincode::decode_from_std_read(&mut &bytes[..], bincode::config::standard());
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::OutOfMemory { .. }
+    ));
+
+    let result: Result<HashSet<u64>, _> =
+        bincode::decode_from_std_read(&mut &bytes[..], bincode::config::standard());
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::OutOfMemory { .. }
+    ));
+
+    let result: Result<(Vec<u64>, usize), _> =
+        bincode::decode_from_slice(&bytes, bincode::config::standard());
+    assert!(matches!(
+        result.unwrap_err(),
+        DecodeError::OutOfMemory { .. }
+    ));
+}
+
+#[test]
+fn test_cow_path_cstr() {
+    use std::{borrow::Cow, ffi::CStr};
+
+    let config = bincode::config::standard();
+
+    // Cow<Path> is encoded the same as a PathBuf and decodes through the owned path
+    let path = Path::new("C:/Program Files/Foo");
+    let cow: Cow<'_, Path> = Cow::Borrowed(path);
+    let bytes = bincode::encode_to_vec(&cow, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::encode_to_vec(path.to_path_buf(), config).unwrap()
+    );
+    let (decoded, len): (Cow<'_, Path>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(matches!(decoded, Cow::Owned(_)));
+    assert_eq!(decoded, cow);
+    assert_eq!(len, bytes.len());
+    assert_roundtrip(Cow::<Path>::Owned(PathBuf::from("/usr/bin")), config);
+
+    // Cow<CStr> is encoded the same as a CString and decodes through the owned path
+    let cstr: &CStr = c"Hello world";
+    let cow: Cow<'_, CStr> = Cow::Borrowed(cstr);
+    let bytes = bincode::encode_to_vec(&cow, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::encode_to_vec(cstr.to_owned(), config).unwrap()
+    );
+    let (decoded, len): (Cow<'_, CStr>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(matches!(decoded, Cow::Owned(_)));
+    assert_eq!(decoded, cow);
+    assert_eq!(len, bytes.len());
+    assert_roundtrip(Cow::<CStr>::Owned(CString::new("").unwrap()), config);
+
+    // An interior nul is still rejected
+    let bytes = bincode::encode_to_vec(&b"a\0b"[..], config).unwrap();
+    let result: Result<(Cow<'_, CStr>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert!(matches!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::CStringNulError { .. }
+    ));
+}
+
+#[test]
+fn test_shutdown() {
+    use std::net::Shutdown;
+
+    let config = bincode::config::standard();
+    for (shutdown, byte) in [
+        (Shutdown::Read, 0u8),
+        (Shutdown::Write, 1),
+        (Shutdown::Both, 2),
+    ] {
+        let bytes = bincode::encode_to_vec(shutdown, config).unwrap();
+        assert_eq!(bytes, [byte]);
+        assert_roundtrip(shutdown, config);
+        assert_roundtrip(shutdown, bincode::config::legacy());
+    }
+
+    let result: Result<(Shutdown, usize), _> = bincode::decode_from_slice(&[3], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant {
+            allowed: bincode::error::AllowedEnumVariants::Range { min: 0, max: 2 },
+            found: 3,
+            type_name: "std::net::Shutdown",
+        }
+    );
+}
+
+#[test]
+fn test_hashmap_btreemap_interop() {
+    use bincode::error::DecodeError;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn check<C: bincode::config::Config>(
+        btree: &BTreeMap<u32, String>,
+        hash: &HashMap<u32, String>,
+        config: C,
+    ) {
+        // BTreeMap -> HashMap
+        let bytes = bincode::encode_to_vec(btree, config).unwrap();
+        let (decoded, len): (HashMap<u32, String>, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(&decoded, hash);
+        assert_eq!(len, bytes.len());
+
+        // HashMap -> BTreeMap
+        let bytes = bincode::encode_to_vec(hash, config).unwrap();
+        let (decoded, len): (BTreeMap<u32, String>, usize) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(&decoded, btree);
+        assert_eq!(len, bytes.len());
+    }
+
+    let mut btree = BTreeMap::new();
+    for i in 0..100u32 {
+        btree.insert(i, i.to_string());
+    }
+    let hash: HashMap<u32, String> = btree.clone().into_iter().collect();
+    check(&btree, &hash, bincode::config::standard());
+    check(&btree, &hash, bincode::config::legacy());
+
+    // A sorted HashMap is encoded exactly like a BTreeMap, so it can be decoded with strict map ordering
+    let config = bincode::config::standard();
+    let sorted = bincode::encode_to_vec(bincode::DeterministicMap(hash.clone()), config).unwrap();
+    assert_eq!(sorted, bincode::encode_to_vec(&btree, config).unwrap());
+    let (decoded, _): (BTreeMap<u32, String>, usize) =
+        bincode::decode_from_slice(&sorted, config.with_strict_map_ordering()).unwrap();
+    assert_eq!(decoded, btree);
+
+    // This includes keys whose encoded bytes are not in the same order as the keys
+    let names: HashMap<String, u32> = [("b", 1), ("a", 2), ("ab", 3), ("", 4)]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .chain((500..520u32).map(|i| (i.to_string(), i)))
+        .collect();
+    let large: HashMap<u32, String> = (200..300u32).map(|i| (i * 10, i.to_string())).collect();
+    let bytes = bincode::encode_to_vec(bincode::DeterministicMap(names.clone()), config).unwrap();
+    let (decoded, _): (BTreeMap<String, u32>, usize) =
+        bincode::decode_from_slice(&bytes, config.with_strict_map_ordering()).unwrap();
+    assert_eq!(decoded, names.into_iter().collect::<BTreeMap<_, _>>());
+    let bytes = bincode::encode_to_vec(bincode::DeterministicMap(large.clone()), config).unwrap();
+    let (decoded, _): (BTreeMap<u32, String>, usize) =
+        bincode::decode_from_slice(&bytes, config.with_strict_map_ordering()).unwrap();
+    assert_eq!(decoded, large.into_iter().collect::<BTreeMap<_, _>>());
+
+    // An unsorted HashMap is rejected by a strict BTreeMap decode
+    let mut unsorted = HashMap::new();
+    unsorted.insert(1u32, ());
+    unsorted.insert(0u32, ());
+    // Maps have the same layout as a `Vec` of key-value tuples
+    let bytes = bincode::encode_to_vec(vec![(1u32, ()), (0u32, ())], config).unwrap();
+    let (decoded, _): (HashMap<u32, ()>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, unsorted);
+    let result: Result<(BTreeMap<u32, ()>, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_strict_map_ordering());
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::InvalidMapOrdering { index: 1 }
+    );
+}
+
+#[test]
+fn test_once_lock() {
+    use std::sync::OnceLock;
+
+    let config = bincode::config::standard();
+
+    let empty = OnceLock::<String>::new();
+    assert_eq!(bincode::encode_to_vec(&empty, config).unwrap(), [0]);
+    let (decoded, _): (OnceLock<String>, usize) = bincode::decode_from_slice(&[0], config).unwrap();
+    assert_eq!(decoded.get(), None);
+
+    let initialized = OnceLock::from("Hello world".to_string());
+    let bytes = bincode::encode_to_vec(&initialized, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::encode_to_vec(Some("Hello world"), config).unwrap()
+    );
+    let (decoded, _): (OnceLock<String>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.get().map(String::as_str), Some("Hello world"));
+
+    assert_roundtrip(empty, config);
+    assert_roundtrip(initialized, config);
+
+    let result: Result<(OnceLock<String>, usize), _> = bincode::decode_from_slice(&[2], config);
+    assert!(matches!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedVariant { found: 2, .. }
+    ));
+}
+
+#[test]
+fn test_map_reserve_hint() {
+    use bincode::de::read::{ChainReader, LimitReader, Reader, SliceReader};
+    use bincode::error::DecodeError;
+    use std::collections::{HashMap, HashSet};
+
+    let input = [1, 2, 3, 4, 5];
+    assert_eq!(SliceReader::new(&input).remaining_bytes(), Some(5));
+    assert_eq!(
+        LimitReader::new(SliceReader::new(&input), 3).remaining_bytes(),
+        Some(3)
+    );
+    assert_eq!(
+        LimitReader::new(SliceReader::new(&input), 10).remaining_bytes(),
+        Some(5)
+    );
+    let segments: [&[u8]; 3] = [&input[..2], &[], &input[2..]];
+    let mut reader = ChainReader::new(&segments);
+    reader.consume(3);
+    assert_eq!(reader.remaining_bytes(), Some(2));
+
+    // A huge length, followed by only a few bytes of entries. Reserving room for all entries up front would fail,
+    // instead only room for the entries that fit in the remaining input is reserved.
+    let config = bincode::config::standard();
+    let mut bytes = bincode::encode_to_vec(1u64 << 60, config).unwrap();
+    bytes.extend([1, 2, 3, 4, 5]);
+
+    let result: Result<(HashMap<u64, u64>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+    let result: Result<(HashSet<u64>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+}
+
+#[test]
+fn test_string_utf8() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard();
+    let text = "héllo wörld ".repeat(100);
+    let bytes = bincode::encode_to_vec(&text, config).unwrap();
+
+    // Buffered readers validate the bytes in place, other readers read them into a `Vec<u8>` first
+    let (decoded, len): (String, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, text);
+    assert_eq!(len, bytes.len());
+    let decoded: String = bincode::decode_from_std_read(&mut &bytes[..], config).unwrap();
+    assert_eq!(decoded, text);
+
+    let mut invalid = bytes.clone();
+    let last = invalid.len() - 1;
+    invalid[last] = 0xFF;
+    let result: Result<(String, usize), _> = bincode::decode_from_slice(&invalid, config);
+    match result.unwrap_err() {
+        DecodeError::Utf8(e) => assert_eq!(e.valid_up_to(), text.len() - 1),
+        e => panic!("Expected Utf8, got {:?}", e),
+    }
+    let result: Result<String, _> = bincode::decode_from_std_read(&mut &invalid[..], config);
+    match result.unwrap_err() {
+        DecodeError::Utf8(e) => assert_eq!(e.valid_up_to(), text.len() - 1),
+        e => panic!("Expected Utf8, got {:?}", e),
+    }
+
+    // Truncated input fails at the first missing byte, the same as a `Vec<u8>`
+    let result: Result<(String, usize), _> =
+        bincode::decode_from_slice(&bytes[..bytes.len() - 3], config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::UnexpectedEnd { additional: 1 }
+    );
+}
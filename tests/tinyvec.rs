@@ -0,0 +1,69 @@
+#![cfg(all(feature = "tinyvec", feature = "alloc"))]
+
+mod utils;
+
+use bincode::error::DecodeError;
+use tinyvec::{array_vec, tiny_vec, ArrayVec, TinyVec};
+use utils::assert_roundtrip;
+
+#[test]
+fn test_array_vec() {
+    let config = bincode::config::standard();
+
+    let vec: ArrayVec<[u16; 4]> = array_vec!(1, 2, 300);
+    // Encoded the same as a `Vec`
+    let bytes = bincode::encode_to_vec(vec, config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(vec.to_vec(), config).unwrap());
+    assert_roundtrip(vec, config);
+    assert_roundtrip(ArrayVec::<[u16; 4]>::new(), config);
+    assert_roundtrip(ArrayVec::from([1u16, 2, 3, 4]), config);
+
+    // Only 4 items fit in the `ArrayVec`
+    let bytes = bincode::encode_to_vec(vec![1u16, 2, 3, 4, 5], config).unwrap();
+    let result: Result<(ArrayVec<[u16; 4]>, usize), _> = bincode::decode_from_slice(&bytes, config);
+    assert_eq!(
+        result.unwrap_err(),
+        DecodeError::CollectionTooLong { len: 5, max: 4 }
+    );
+}
+
+#[test]
+fn test_tiny_vec() {
+    let config = bincode::config::standard();
+
+    let inline: TinyVec<[String; 2]> =
+        tiny_vec!([String; 2] => String::from("a"), String::from("b"));
+    assert!(inline.is_inline());
+    assert_roundtrip(inline.clone(), config);
+    assert_roundtrip(TinyVec::<[String; 2]>::new(), config);
+
+    let bytes = bincode::encode_to_vec(&inline, config).unwrap();
+    assert_eq!(bytes, [2, 1, b'a', 1, b'b']);
+    let (decoded, _): (TinyVec<[String; 2]>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(decoded.is_inline());
+
+    // More items than the inline capacity are decoded on the heap
+    let bytes = bincode::encode_to_vec(vec!["a", "b", "c"], config).unwrap();
+    let (decoded, _): (TinyVec<[String; 2]>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(decoded.is_heap());
+    assert_eq!(decoded.as_slice(), ["a", "b", "c"]);
+    assert_roundtrip(decoded, config);
+
+    // A heap-allocated `TinyVec` with few items is decoded inline
+    let mut heap: TinyVec<[u8; 4]> = TinyVec::Heap(vec![1, 2]);
+    assert!(heap.is_heap());
+    let bytes = bincode::encode_to_vec(&heap, config).unwrap();
+    let (decoded, _): (TinyVec<[u8; 4]>, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert!(decoded.is_inline());
+    assert_eq!(decoded, heap);
+    heap.push(3);
+    assert_roundtrip(heap, config.with_fixed_int_encoding());
+
+    let bytes = bincode::encode_to_vec(vec![0u8; 100], config).unwrap();
+    let result: Result<(TinyVec<[u8; 4]>, usize), _> =
+        bincode::decode_from_slice(&bytes, config.with_limit::<50>());
+    assert_eq!(result.unwrap_err(), DecodeError::LimitExceeded);
+}
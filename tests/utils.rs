@@ -7,7 +7,7 @@ where
     CMP: Fn(&V, &V) -> bool,
 {
     let mut buffer = [0u8; 2048];
-    let len = bincode::encode_into_slice(&element, &mut buffer, config).unwrap();
+    let len = bincode::encode_into_slice(element, &mut buffer, config).unwrap();
     println!(
         "{:?} ({}): {:?} ({:?})",
         element,
@@ -15,11 +15,13 @@ where
         &buffer[..len],
         core::any::type_name::<C>()
     );
-    let (decoded, decoded_len): (V, usize) =
-        bincode::decode_from_slice(&mut buffer, config).unwrap();
+    if let Some(size_hint) = element.size_hint::<C>() {
+        assert_eq!(size_hint, len, "Wrong size hint for {:?}", element);
+    }
+    let (decoded, decoded_len): (V, usize) = bincode::decode_from_slice(&buffer, config).unwrap();
 
     assert!(
-        cmp(&element, &decoded),
+        cmp(element, &decoded),
         "Comparison failed\nDecoded:  {:?}\nExpected: {:?}\nBytes: {:?}",
         decoded,
         element,
@@ -27,14 +29,16 @@ where
     );
     assert_eq!(len, decoded_len);
 
+    assert_truncated_is_unexpected_end::<V, C>(&buffer[..len], config);
+
     #[cfg(feature = "serde")]
     // skip_fixed_array_length is not supposed on serde
     if !C::SKIP_FIXED_ARRAY_LENGTH {
-        let encoded = bincode::serde::encode_to_vec(&element, config).unwrap();
+        let encoded = bincode::serde::encode_to_vec(element, config).unwrap();
         assert_eq!(&buffer[..len], &encoded);
         let (decoded, decoded_len) = bincode::serde::decode_from_slice(&encoded, config).unwrap();
         assert!(
-            cmp(&element, &decoded),
+            cmp(element, &decoded),
             "Comparison failed\nDecoded:  {:?}\nExpected: {:?}\nBytes: {:?}",
             decoded,
             element,
@@ -112,6 +116,45 @@ where
     );
 }
 
+/// Asserts that decoding `bytes` with the last byte missing fails with `DecodeError::UnexpectedEnd`, instead of panicking or decoding a different value.
+fn assert_truncated_is_unexpected_end<V, C>(bytes: &[u8], config: C)
+where
+    V: bincode::Decode + Debug,
+    C: bincode::config::Config,
+{
+    if let Some((_, truncated)) = bytes.split_last() {
+        let result: Result<(V, usize), _> = bincode::decode_from_slice(truncated, config);
+        assert!(
+            matches!(
+                result,
+                Err(bincode::error::DecodeError::UnexpectedEnd { .. })
+            ),
+            "Decoding truncated input did not fail with UnexpectedEnd\nResult: {:?}\nBytes: {:?}",
+            result,
+            truncated,
+        );
+    }
+}
+
+/// Encodes `element` with `config`, decodes it again and asserts that the decoded value is equal to `element`.
+///
+/// This also asserts that all encoded bytes are consumed by decoding, and that decoding the encoded bytes without the last byte fails with `DecodeError::UnexpectedEnd`.
+#[allow(dead_code)] // This is not used in every test
+pub fn assert_roundtrip<V, C>(element: V, config: C)
+where
+    V: bincode::Encode + bincode::Decode + PartialEq + Debug,
+    C: bincode::config::Config,
+{
+    let mut buffer = [0u8; 2048];
+    let len = bincode::encode_into_slice(&element, &mut buffer, config).unwrap();
+    let (decoded, decoded_len): (V, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(element, decoded, "Bytes: {:?}", &buffer[..len]);
+    assert_eq!(len, decoded_len, "Not all bytes were consumed");
+
+    assert_truncated_is_unexpected_end::<V, C>(&buffer[..len], config);
+}
+
 #[cfg(feature = "serde")]
 pub trait TheSameTrait:
     bincode::Encode
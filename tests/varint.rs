@@ -0,0 +1,64 @@
+use bincode::varint::{encoded_len_i128, encoded_len_i64, encoded_len_u128, encoded_len_u64};
+
+fn actual_len<T: bincode::Encode>(value: T) -> usize {
+    let mut buffer = [0u8; 32];
+    bincode::encode_into_slice(value, &mut buffer, bincode::config::standard()).unwrap()
+}
+
+#[test]
+fn test_encoded_len_unsigned() {
+    // The values where the encoded length increments
+    let cases: &[(u64, usize)] = &[
+        (0, 1),
+        (250, 1),
+        (251, 3),
+        (u16::MAX as u64, 3),
+        (u16::MAX as u64 + 1, 5),
+        (u32::MAX as u64, 5),
+        (u32::MAX as u64 + 1, 9),
+        (u64::MAX, 9),
+    ];
+    for &(value, len) in cases {
+        assert_eq!(encoded_len_u64(value), len, "{}", value);
+        assert_eq!(actual_len(value), len, "{}", value);
+        assert_eq!(encoded_len_u128(value.into()), len, "{}", value);
+        assert_eq!(actual_len(u128::from(value)), len, "{}", value);
+    }
+
+    assert_eq!(encoded_len_u128(u64::MAX as u128 + 1), 17);
+    assert_eq!(actual_len(u64::MAX as u128 + 1), 17);
+    assert_eq!(encoded_len_u128(u128::MAX), 17);
+}
+
+#[test]
+fn test_encoded_len_signed() {
+    // Zigzag encoding maps `n` to `2n` and `-n` to `2n - 1`
+    let cases: &[(i64, usize)] = &[
+        (0, 1),
+        (-1, 1),
+        (125, 1),
+        (-125, 1),
+        (-126, 3),
+        (126, 3),
+        (i16::MAX as i64, 3),
+        (i16::MIN as i64, 3),
+        (i16::MAX as i64 + 1, 5),
+        (i16::MIN as i64 - 1, 5),
+        (i32::MAX as i64, 5),
+        (i32::MIN as i64, 5),
+        (i32::MAX as i64 + 1, 9),
+        (i32::MIN as i64 - 1, 9),
+        (i64::MAX, 9),
+        (i64::MIN, 9),
+    ];
+    for &(value, len) in cases {
+        assert_eq!(encoded_len_i64(value), len, "{}", value);
+        assert_eq!(actual_len(value), len, "{}", value);
+        assert_eq!(encoded_len_i128(value.into()), len, "{}", value);
+        assert_eq!(actual_len(i128::from(value)), len, "{}", value);
+    }
+
+    assert_eq!(encoded_len_i128(i64::MAX as i128 + 1), 17);
+    assert_eq!(actual_len(i64::MAX as i128 + 1), 17);
+    assert_eq!(encoded_len_i128(i128::MIN), 17);
+}